@@ -0,0 +1,78 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use ark_bn254::Fr;
+use ark_ff::{BigInteger, PrimeField};
+
+// Size in bytes of a bn254 field element in an R1CS file
+const FIELD_SIZE: usize = 32;
+
+// Write an `n`-constraint bn254 chain straight to an R1CS file, without
+// building it in memory first, so benchmarks can use files of hundreds of
+// MB. Wires are [1, out, x, m_1, ..., m_{n-1}], with the links
+// m_{i+1} = (m_i + x) * (m_i + 2) starting from m_0 = x, and a last
+// constraint m_{n-1} * 1 = out. Most constraints take 192 bytes.
+pub fn write_chain_r1cs(path: &Path, n: usize) -> io::Result<()> {
+    assert!(n >= 2);
+    let n_wires = (n + 2) as u32;
+    let mut writer = BufWriter::new(File::create(path)?);
+    
+    writer.write_all(b"r1cs")?;
+    writer.write_all(&1u32.to_le_bytes())?;
+    writer.write_all(&3u32.to_le_bytes())?;
+    
+    // Header: field size, prime, wires, 1 public output, no public inputs,
+    // 1 private input, one label per wire, constraints
+    section(&mut writer, 1, 64)?;
+    writer.write_all(&(FIELD_SIZE as u32).to_le_bytes())?;
+    writer.write_all(&Fr::MODULUS.to_bytes_le())?;
+    for count in [n_wires, 1, 0, 1] {
+        writer.write_all(&count.to_le_bytes())?;
+    }
+    writer.write_all(&(n_wires as u64).to_le_bytes())?;
+    writer.write_all(&(n as u32).to_le_bytes())?;
+    
+    // The first link and the last constraint have shorter linear combinations
+    section(&mut writer, 2, 156 + 192 * (n as u64 - 2) + 120)?;
+    let (x, one, two) = (2, Fr::from(1u64), Fr::from(2u64));
+    // The first link has m_0 = x, which circom would fold into one term
+    lc(&mut writer, &[(x, two)])?;
+    lc(&mut writer, &[(0, two), (x, one)])?;
+    lc(&mut writer, &[(3, one)])?;
+    for m in 3..n_wires - 1 {
+        lc(&mut writer, &[(x, one), (m, one)])?;
+        lc(&mut writer, &[(0, two), (m, one)])?;
+        lc(&mut writer, &[(m + 1, one)])?;
+    }
+    lc(&mut writer, &[(n_wires - 1, one)])?;
+    lc(&mut writer, &[(0, one)])?;
+    lc(&mut writer, &[(1, one)])?;
+    
+    section(&mut writer, 3, 8 * n_wires as u64)?;
+    for label in 0..n_wires as u64 {
+        writer.write_all(&label.to_le_bytes())?;
+    }
+    writer.flush()
+}
+
+fn section(writer: &mut impl Write, section_type: u32, size: u64) -> io::Result<()> {
+    writer.write_all(&section_type.to_le_bytes())?;
+    writer.write_all(&size.to_le_bytes())
+}
+
+fn lc(writer: &mut impl Write, terms: &[(u32, Fr)]) -> io::Result<()> {
+    writer.write_all(&(terms.len() as u32).to_le_bytes())?;
+    for (wire, coefficient) in terms {
+        writer.write_all(&wire.to_le_bytes())?;
+        writer.write_all(&coefficient.into_bigint().to_bytes_le())?;
+    }
+    Ok(())
+}
+
+// A chain circuit of `n` constraints in the temp directory, written once
+// outside the measurements
+pub fn chain_fixture(name: &str, n: usize) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("qa1-bench-{}-{}-{}.r1cs", name, n, std::process::id()));
+    write_chain_r1cs(&path, n).unwrap();
+    path
+}
@@ -0,0 +1,29 @@
+use ark_bn254::Fr;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use dogecoin_zkp_generator_qa1::r1cs::ReadOptions;
+use dogecoin_zkp_generator_qa1::R1CS;
+
+mod common;
+
+// About 400 MB of constraints, the size where per-field read calls add up
+const HUGE: usize = 1 << 21;
+
+fn read_vs_mmap(c: &mut Criterion) {
+    let path = common::chain_fixture("mmap", HUGE);
+    let mut group = c.benchmark_group("R1CS::read_mmap");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(std::fs::metadata(&path).unwrap().len()));
+    
+    let options = ReadOptions::default();
+    group.bench_with_input(BenchmarkId::new("read", HUGE), &path, |b, path| {
+        b.iter(|| R1CS::<Fr>::read_with_options(path, &options).unwrap())
+    });
+    group.bench_with_input(BenchmarkId::new("read_mmap", HUGE), &path, |b, path| {
+        b.iter(|| R1CS::<Fr>::read_mmap(path, &options).unwrap())
+    });
+    group.finish();
+    let _ = std::fs::remove_file(&path);
+}
+
+criterion_group!(benches, read_vs_mmap);
+criterion_main!(benches);
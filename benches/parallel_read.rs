@@ -0,0 +1,29 @@
+use ark_bn254::Fr;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use dogecoin_zkp_generator_qa1::r1cs::ReadOptions;
+use dogecoin_zkp_generator_qa1::R1CS;
+
+mod common;
+
+// About 200 MB of constraints, where decoding them on all cores pays off
+const LARGE: usize = 1 << 20;
+
+fn serial_vs_parallel(c: &mut Criterion) {
+    let path = common::chain_fixture("parallel", LARGE);
+    let mut group = c.benchmark_group("R1CS::read large");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(LARGE as u64));
+    
+    let serial = ReadOptions::default();
+    let parallel = ReadOptions { parallel: true, ..ReadOptions::default() };
+    for (name, options) in [("serial", &serial), ("parallel", &parallel)] {
+        group.bench_with_input(BenchmarkId::new(name, LARGE), &path, |b, path| {
+            b.iter(|| R1CS::<Fr>::read_with_options(path, options).unwrap())
+        });
+    }
+    group.finish();
+    let _ = std::fs::remove_file(&path);
+}
+
+criterion_group!(benches, serial_vs_parallel);
+criterion_main!(benches);
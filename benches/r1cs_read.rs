@@ -0,0 +1,64 @@
+use std::path::PathBuf;
+use ark_bn254::Fr;
+use ark_relations::lc;
+use ark_relations::r1cs::{ConstraintSystem, Variable};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use dogecoin_zkp_generator_qa1::r1cs::ReadOptions;
+use dogecoin_zkp_generator_qa1::R1CS;
+
+// Constraint counts for the small, medium and large circuits
+const SIZES: [usize; 3] = [1 << 10, 1 << 14, 1 << 17];
+
+// Like `create_hardcoded_r1cs`'s `x * y = z`, scaled up to `n` constraints: a chain
+// m_{i+1} = (m_i + x) * (m_i + 2), with the last link equal to the public
+// output, so each linear combination has a couple of terms as circom's do
+fn scaled_r1cs(n: usize) -> R1CS<Fr> {
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    let out = cs.new_input_variable(|| Ok(Fr::from(0u64))).unwrap();
+    let x = cs.new_witness_variable(|| Ok(Fr::from(3u64))).unwrap();
+    let mut m = x;
+    for _ in 0..n - 1 {
+        let next = cs.new_witness_variable(|| Ok(Fr::from(0u64))).unwrap();
+        cs.enforce_constraint(lc!() + m + x, lc!() + m + (Fr::from(2u64), Variable::One), lc!() + next).unwrap();
+        m = next;
+    }
+    cs.enforce_constraint(lc!() + m, lc!() + Variable::One, lc!() + out).unwrap();
+    R1CS::from_constraint_system(cs)
+}
+
+// Write each circuit once, outside the measurements
+fn fixture(n: usize) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("qa1-bench-{}-{}.r1cs", n, std::process::id()));
+    scaled_r1cs(n).write(&path).unwrap();
+    path
+}
+
+fn read(c: &mut Criterion) {
+    let mut group = c.benchmark_group("R1CS::read");
+    group.sample_size(10);
+    for n in SIZES {
+        let path = fixture(n);
+        group.throughput(Throughput::Elements(n as u64));
+        group.bench_with_input(BenchmarkId::new("sequential", n), &path, |b, path| {
+            b.iter(|| R1CS::<Fr>::read(path).unwrap())
+        });
+        let parallel = ReadOptions { parallel: true, ..ReadOptions::default() };
+        group.bench_with_input(BenchmarkId::new("parallel", n), &path, |b, path| {
+            b.iter(|| R1CS::<Fr>::read_with_options(path, &parallel).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("streaming", n), &path, |b, path| {
+            b.iter(|| R1CS::<Fr>::constraints_iter(path).unwrap().count())
+        });
+        let dump = path.with_extension("parsed");
+        R1CS::<Fr>::read(&path).unwrap().save_parsed(&dump, &path).unwrap();
+        group.bench_with_input(BenchmarkId::new("load_parsed", n), &path, |b, path| {
+            b.iter(|| R1CS::<Fr>::load_parsed(&dump, path).unwrap())
+        });
+        let _ = std::fs::remove_file(&dump);
+        let _ = std::fs::remove_file(&path);
+    }
+    group.finish();
+}
+
+criterion_group!(benches, read);
+criterion_main!(benches);
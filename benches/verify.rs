@@ -0,0 +1,34 @@
+use ark_bn254::{Bn254, Fr};
+use ark_groth16::{prepare_verifying_key, Groth16};
+use ark_snark::SNARK;
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use criterion::{criterion_group, criterion_main, Criterion};
+use dogecoin_zkp_generator_qa1::r1cs::{create_hardcoded_r1cs, hardcoded_witness};
+use dogecoin_zkp_generator_qa1::{groth16, prove, setup, CircuitFromR1CS, Witness};
+
+// Verification cost depends on the number of public inputs, not the circuit
+// size, so the hardcoded `x * y = z` circuit (one public output) will do
+fn verify(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(336);
+    let r1cs = create_hardcoded_r1cs::<Fr>().unwrap();
+    let pk = setup::<Bn254, _>(CircuitFromR1CS::new_with_dummy(&r1cs).unwrap(), &mut rng).unwrap();
+    let witness = Witness {
+        field_size: r1cs.header().field_size,
+        prime_bytes: r1cs.header().prime_bytes.clone(),
+        values: hardcoded_witness(5, 7),
+    };
+    let proof = prove(&pk, CircuitFromR1CS::new(&r1cs, witness).unwrap(), &mut rng).unwrap();
+    let public_inputs = [Fr::from(35u64)];
+
+    let pvk = prepare_verifying_key(&pk.vk);
+    c.bench_function("verify_with_processed_vk", |b| {
+        b.iter(|| assert!(Groth16::<Bn254>::verify_with_processed_vk(&pvk, &public_inputs, &proof).unwrap()))
+    });
+    // What `verify` pays per call, preparing the key included
+    c.bench_function("groth16::verify", |b| {
+        b.iter(|| assert!(groth16::verify(&pk.vk, &public_inputs, &proof).unwrap()))
+    });
+}
+
+criterion_group!(benches, verify);
+criterion_main!(benches);
@@ -0,0 +1,66 @@
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::Path;
+use ark_ec::pairing::Pairing;
+use ark_groth16::{Proof, ProvingKey, VerifyingKey};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+
+/// Convert an arkworks serialization error into an I/O error
+fn to_io_error(e: SerializationError) -> io::Error {
+    match e {
+        SerializationError::IoError(e) => e,
+        e => io::Error::new(io::ErrorKind::InvalidData, e.to_string()),
+    }
+}
+
+// All artifacts are written with `Compress::Yes` (`serialize_compressed`)
+// and read back with `Compress::Yes, Validate::Yes`
+// (`deserialize_compressed`), so the two sides always agree on the encoding
+// and every loaded point is checked to be on the curve and in the right
+// subgroup.
+
+/// Write a value to `path` in arkworks' compressed canonical encoding
+fn save<T: CanonicalSerialize, P: AsRef<Path>>(value: &T, path: P) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    value.serialize_compressed(&mut writer).map_err(to_io_error)?;
+    writer.flush()
+}
+
+/// Read a value written by `save`, checking that all points are valid
+fn load<T: CanonicalDeserialize, P: AsRef<Path>>(path: P) -> io::Result<T> {
+    let reader = BufReader::new(File::open(path)?);
+    T::deserialize_compressed(reader).map_err(to_io_error)
+}
+
+/// Save a Groth16 proof
+pub fn save_proof<E: Pairing, P: AsRef<Path>>(proof: &Proof<E>, path: P) -> io::Result<()> {
+    save(proof, path)
+}
+
+/// Load a Groth16 proof
+pub fn load_proof<E: Pairing, P: AsRef<Path>>(path: P) -> io::Result<Proof<E>> {
+    load(path)
+}
+
+/// Save a Groth16 proving key (which includes its verifying key).
+///
+/// Treat the saved file as sensitive: together with a witness it is all
+/// anyone needs to produce proofs for the circuit.
+pub fn save_proving_key<E: Pairing, P: AsRef<Path>>(pk: &ProvingKey<E>, path: P) -> io::Result<()> {
+    save(pk, path)
+}
+
+/// Load a Groth16 proving key
+pub fn load_proving_key<E: Pairing, P: AsRef<Path>>(path: P) -> io::Result<ProvingKey<E>> {
+    load(path)
+}
+
+/// Save a Groth16 verifying key
+pub fn save_verifying_key<E: Pairing, P: AsRef<Path>>(vk: &VerifyingKey<E>, path: P) -> io::Result<()> {
+    save(vk, path)
+}
+
+/// Load a Groth16 verifying key
+pub fn load_verifying_key<E: Pairing, P: AsRef<Path>>(path: P) -> io::Result<VerifyingKey<E>> {
+    load(path)
+}
@@ -0,0 +1,143 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use ark_ec::pairing::Pairing;
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_ff::{Field, One, PrimeField, Zero};
+use ark_groth16::{Groth16, PreparedVerifyingKey, Proof};
+use ark_relations::r1cs::SynthesisError;
+use ark_std::rand::rngs::StdRng;
+use ark_std::rand::SeedableRng;
+use ark_std::UniformRand;
+
+/// Errors that can occur while verifying a batch of proofs
+#[derive(Debug)]
+pub enum BatchError {
+    /// There isn't exactly one set of public inputs per proof
+    CountMismatch { inputs: usize, proofs: usize },
+    /// arkworks rejected the verifying key or inputs
+    Synthesis(SynthesisError),
+}
+
+impl fmt::Display for BatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BatchError::CountMismatch { inputs, proofs } => write!(
+                f,
+                "Got {} sets of public inputs for {} proofs; each proof needs exactly one",
+                inputs, proofs
+            ),
+            BatchError::Synthesis(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for BatchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BatchError::Synthesis(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<SynthesisError> for BatchError {
+    fn from(e: SynthesisError) -> Self {
+        BatchError::Synthesis(e)
+    }
+}
+
+/// Verify many proofs against one verifying key, returning whether each
+/// proof is valid for its public inputs.
+///
+/// All proofs are first checked together with a single random linear
+/// combination of their pairing equations, which needs one pairing per proof
+/// plus two, instead of three per proof. If that combined check fails, each
+/// proof is verified on its own to find the bad ones.
+///
+/// `inputs` and `proofs` must have the same length, or this returns
+/// [`BatchError::CountMismatch`].
+pub fn verify_batch<E: Pairing>(
+    pvk: &PreparedVerifyingKey<E>,
+    inputs: &[Vec<E::ScalarField>],
+    proofs: &[Proof<E>],
+) -> Result<Vec<bool>, BatchError> {
+    if inputs.len() != proofs.len() {
+        return Err(BatchError::CountMismatch { inputs: inputs.len(), proofs: proofs.len() });
+    }
+
+    // Wrong-length inputs are an error here, just as in a single verification
+    let prepared_inputs = inputs
+        .iter()
+        .map(|public| Groth16::<E>::prepare_inputs(pvk, public))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if proofs.is_empty() || batch_check(pvk, &prepared_inputs, proofs) {
+        return Ok(vec![true; proofs.len()]);
+    }
+
+    proofs
+        .iter()
+        .zip(&prepared_inputs)
+        .map(|(proof, prepared)| Groth16::<E>::verify_proof_with_prepared_inputs(pvk, proof, prepared))
+        .collect::<Result<_, _>>()
+        .map_err(BatchError::from)
+}
+
+// Check every proof's equation e(A, B) = e(alpha, beta) * e(inputs, gamma) * e(C, delta)
+// at once, scaling each by a fresh random factor so that invalid proofs can't
+// cancel each other out
+fn batch_check<E: Pairing>(pvk: &PreparedVerifyingKey<E>, prepared_inputs: &[E::G1], proofs: &[Proof<E>]) -> bool {
+    let mut rng = StdRng::from_entropy();
+    let factors: Vec<E::ScalarField> = proofs.iter().map(|_| E::ScalarField::rand(&mut rng)).collect();
+
+    let inputs_affine = E::G1::normalize_batch(prepared_inputs);
+    let c_affine: Vec<E::G1Affine> = proofs.iter().map(|proof| proof.c).collect();
+    let inputs_sum = E::G1::msm_unchecked(&inputs_affine, &factors);
+    let c_sum = E::G1::msm_unchecked(&c_affine, &factors);
+
+    let mut g1: Vec<E::G1Prepared> = proofs
+        .iter()
+        .zip(&factors)
+        .map(|(proof, factor)| (proof.a * factor).into_affine().into())
+        .collect();
+    let mut g2: Vec<E::G2Prepared> = proofs.iter().map(|proof| proof.b.into()).collect();
+    g1.push(inputs_sum.into_affine().into());
+    g2.push(pvk.gamma_g2_neg_pc.clone());
+    g1.push(c_sum.into_affine().into());
+    g2.push(pvk.delta_g2_neg_pc.clone());
+
+    let factor_sum = factors.iter().fold(E::ScalarField::zero(), |acc, factor| acc + factor);
+    let expected = pvk.alpha_g1_beta_g2.pow(factor_sum.into_bigint());
+
+    match E::final_exponentiation(E::multi_miller_loop(g1, g2)) {
+        Some(result) => result.0 == expected,
+        None => expected.is_one(),
+    }
+}
+
+/// Pair up the JSON files of two directories by file name, e.g.
+/// `proofs/3.json` with `public/3.json`, in sorted order
+pub fn pair_json_files(proof_dir: &Path, public_dir: &Path) -> io::Result<Vec<(PathBuf, PathBuf)>> {
+    let mut proof_files: Vec<PathBuf> = fs::read_dir(proof_dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<_, _>>()?;
+    proof_files.retain(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "json"));
+    proof_files.sort();
+
+    proof_files
+        .into_iter()
+        .map(|proof| {
+            let public = public_dir.join(proof.file_name().unwrap());
+            if !public.is_file() {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("no public inputs {} for proof {}", public.display(), proof.display())
+                ));
+            }
+            Ok((proof, public))
+        })
+        .collect()
+}
@@ -0,0 +1,90 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The files making up a circom artifact bundle directory
+#[derive(Debug, Clone)]
+pub struct Bundle {
+    pub dir: PathBuf,
+    pub r1cs: PathBuf,
+    pub sym: Option<PathBuf>,
+    pub input: Option<PathBuf>,
+    pub witness: Option<PathBuf>,
+}
+
+impl Bundle {
+    /// Discover the bundle files in `dir`.
+    ///
+    /// The circuit is `circuit.r1cs`, or the only `*.r1cs` file in the
+    /// directory. The symbol file and witness are looked up next to it by the
+    /// same stem (`circuit.sym`), and the witness may also be `witness.wtns`.
+    /// At least one of `input.json` or a witness file must be present.
+    pub fn discover<P: AsRef<Path>>(dir: P) -> io::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        if !dir.is_dir() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("Bundle directory not found: {}", dir.display())
+            ));
+        }
+
+        let r1cs = Self::find_r1cs(&dir)?;
+        let stem = r1cs.file_stem().map(|s| s.to_os_string()).unwrap_or_default();
+
+        let sym = Some(dir.join(&stem).with_extension("sym")).filter(|p| p.is_file());
+        let input = Some(dir.join("input.json")).filter(|p| p.is_file());
+        let witness = [dir.join("witness.wtns"), dir.join(&stem).with_extension("wtns")]
+            .into_iter()
+            .find(|p| p.is_file());
+
+        if input.is_none() && witness.is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("Bundle {} has neither input.json nor a .wtns witness", dir.display())
+            ));
+        }
+
+        Ok(Self {
+            dir,
+            r1cs,
+            sym,
+            input,
+            witness,
+        })
+    }
+
+    fn find_r1cs(dir: &Path) -> io::Result<PathBuf> {
+        let preferred = dir.join("circuit.r1cs");
+        if preferred.is_file() {
+            return Ok(preferred);
+        }
+
+        let candidates: Vec<PathBuf> = fs::read_dir(dir)?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == "r1cs"))
+            .collect();
+
+        match candidates.len() {
+            1 => Ok(candidates.into_iter().next().unwrap()),
+            0 => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No .r1cs file in bundle {}", dir.display())
+            )),
+            n => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Found {} .r1cs files in bundle {}; name the circuit circuit.r1cs", n, dir.display())
+            )),
+        }
+    }
+
+    /// Print the discovered files
+    pub fn print_info(&self) {
+        println!("Bundle directory: {}", self.dir.display());
+        println!("  Circuit: {}", self.r1cs.display());
+        let show = |p: &Option<PathBuf>| p.as_ref().map_or("-".to_string(), |p| p.display().to_string());
+        println!("  Symbols: {}", show(&self.sym));
+        println!("  Input:   {}", show(&self.input));
+        println!("  Witness: {}", show(&self.witness));
+    }
+}
@@ -0,0 +1,76 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use log::debug;
+use serde::{Deserialize, Serialize};
+use crate::r1cs::{R1CSError, R1CSHeader};
+
+/// A parsed R1CS header saved next to its source file as `<file>.meta`,
+/// valid as long as the source keeps the same size and modification time
+#[derive(Debug, Serialize, Deserialize)]
+struct Sidecar {
+    source_size: u64,
+    source_mtime_secs: u64,
+    source_mtime_nanos: u32,
+    header: R1CSHeader,
+}
+
+/// The sidecar path for an R1CS file, e.g. `circuit.r1cs.meta`
+pub fn sidecar_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".meta");
+    PathBuf::from(name)
+}
+
+/// Read an R1CS header from its `.meta` sidecar if the sidecar matches the
+/// file's current size and modification time. Otherwise parse the header
+/// from the file and write a fresh sidecar for next time.
+///
+/// A sidecar that can't be read or written is never an error: the header is
+/// simply parsed from the file.
+pub fn read_header(path: &Path) -> Result<R1CSHeader, R1CSError> {
+    let sidecar = sidecar_path(path);
+    let (size, secs, nanos) = source_stamp(path)?;
+
+    match load_sidecar(&sidecar) {
+        Some(cached) if (cached.source_size, cached.source_mtime_secs, cached.source_mtime_nanos) == (size, secs, nanos) => {
+            debug!("Using cached R1CS header from {}", sidecar.display());
+            return Ok(cached.header);
+        }
+        Some(_) => debug!("Cached R1CS header {} is stale", sidecar.display()),
+        None => {}
+    }
+
+    let header = R1CSHeader::read(path)?;
+    let fresh = Sidecar {
+        source_size: size,
+        source_mtime_secs: secs,
+        source_mtime_nanos: nanos,
+        header,
+    };
+    match serde_json::to_vec(&fresh) {
+        Ok(json) => {
+            if let Err(e) = fs::write(&sidecar, json) {
+                debug!("Could not write {}: {}", sidecar.display(), e);
+            }
+        }
+        Err(e) => debug!("Could not encode R1CS header cache: {}", e),
+    }
+    Ok(fresh.header)
+}
+
+// The file's size and modification time, which together key the cache
+fn source_stamp(path: &Path) -> io::Result<(u64, u64, u32)> {
+    let metadata = fs::metadata(path)?;
+    let mtime = metadata
+        .modified()?
+        .duration_since(UNIX_EPOCH)
+        .map_err(io::Error::other)?;
+    Ok((metadata.len(), mtime.as_secs(), mtime.subsec_nanos()))
+}
+
+fn load_sidecar(path: &Path) -> Option<Sidecar> {
+    let bytes = fs::read(path).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
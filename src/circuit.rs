@@ -0,0 +1,313 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use ark_ff::PrimeField;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError, Variable};
+use log::{debug, info, trace};
+use zeroize::Zeroize;
+use crate::r1cs::{R1CSError, R1CS};
+use crate::witness::Witness;
+
+/// Errors building a [`CircuitFromR1CS`], caught before the constraints are
+/// handed to arkworks
+#[derive(Debug)]
+pub enum CircuitError {
+    /// The circuit can't be proven with Groth16, or the witness is for
+    /// another field (see [`R1CS::assert_groth16_compatible`] and
+    /// [`Witness::assert_prime_matches`])
+    R1CS(R1CSError),
+    /// The witness doesn't have one value per circuit wire
+    WitnessLengthMismatch { expected: u32, got: usize },
+    /// The witness value of wire 0, the constant ONE, isn't 1
+    OneWireNotOne,
+    /// A constraint references a wire the circuit doesn't have
+    WireOutOfRange { constraint: usize, wire: u32, num_wires: u32 },
+}
+
+impl fmt::Display for CircuitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CircuitError::R1CS(e) => write!(f, "{}", e),
+            CircuitError::WitnessLengthMismatch { expected, got } => write!(
+                f,
+                "Witness has {} values, but the circuit has {} wires",
+                got, expected
+            ),
+            CircuitError::OneWireNotOne => write!(f, "Witness wire 0 must be 1, as it is the constant ONE"),
+            CircuitError::WireOutOfRange { constraint, wire, num_wires } => write!(
+                f,
+                "Constraint #{} references wire {}, but the circuit has only {} wires",
+                constraint, wire, num_wires
+            ),
+        }
+    }
+}
+
+impl Error for CircuitError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CircuitError::R1CS(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<R1CSError> for CircuitError {
+    fn from(e: R1CSError) -> Self {
+        CircuitError::R1CS(e)
+    }
+}
+
+impl From<CircuitError> for io::Error {
+    fn from(e: CircuitError) -> Self {
+        match e {
+            CircuitError::R1CS(e) => e.into(),
+            e => io::Error::new(io::ErrorKind::InvalidData, e),
+        }
+    }
+}
+
+/// An arkworks circuit built from a parsed circom R1CS and a full wire
+/// assignment (the witness).
+///
+/// The R1CS is borrowed, so the setup and proving circuits can share one
+/// parse of the file.
+pub struct CircuitFromR1CS<'a, F: PrimeField> {
+    r1cs: &'a R1CS<F>,
+    witness_values: Vec<F>,
+}
+
+impl<'a, F: PrimeField> CircuitFromR1CS<'a, F> {
+    /// Build the circuit with witness values, e.g. loaded from a `.wtns` file.
+    ///
+    /// The witness must have one value per wire, be for the circuit's field
+    /// and have 1 for the ONE wire, so a bad witness is reported here rather
+    /// than as an arkworks `AssignmentMissing` deep inside proving (see
+    /// [`Witness::assert_fits`]).
+    pub fn new(r1cs: &'a R1CS<F>, mut witness: Witness<F>) -> Result<Self, CircuitError> {
+        check_circuit(r1cs)?;
+        witness.assert_fits(r1cs)?;
+        
+        Ok(Self {
+            r1cs,
+            witness_values: std::mem::take(&mut witness.values),
+        })
+    }
+    
+    /// Build the circuit with made-up witness values, which is enough for
+    /// setup but won't satisfy the constraints when proving
+    pub fn new_with_dummy(r1cs: &'a R1CS<F>) -> Result<Self, CircuitError> {
+        check_circuit(r1cs)?;
+        
+        let num_wires = r1cs.num_wires() as usize;
+        let mut witness_values = vec![F::zero(); num_wires];
+        
+        // Set ONE wire
+        witness_values[0] = F::one();
+        
+        // For demonstration, set simple values for public inputs
+        // In a real scenario, these would be the actual input values
+        for i in 1..=r1cs.num_public_values() as usize {
+            if i < witness_values.len() {
+                witness_values[i] = F::from(i as u64);
+            }
+        }
+        
+        // For private inputs, set some sample values
+        for (i, value) in witness_values.iter_mut().enumerate().skip(r1cs.num_public_values() as usize + 1) {
+            *value = F::from((i * 10) as u64);
+        }
+        
+        debug!("Initialized witness values:");
+        for (i, val) in witness_values.iter().enumerate().take(10) {
+            debug!("  x{} = {:?}", i, val);
+        }
+        if num_wires > 10 {
+            debug!("  ... and {} more values", num_wires - 10);
+        }
+        
+        Ok(Self {
+            r1cs,
+            witness_values,
+        })
+    }
+    
+    /// Get the public inputs for verification: the values of the instance
+    /// variables in the order `generate_constraints` allocates them, i.e.
+    /// public outputs, then public inputs. The ONE wire is not included; it
+    /// is arkworks' implicit `Variable::One`.
+    pub fn get_public_inputs(&self) -> Vec<F> {
+        let public_count = self.r1cs.num_public_values() as usize;
+        self.witness_values.iter().skip(1).take(public_count).copied().collect()
+    }
+    
+    /// Get the parsed circuit
+    pub fn r1cs(&self) -> &'a R1CS<F> {
+        self.r1cs
+    }
+    
+    /// Get the full wire assignment, starting with the ONE wire
+    pub fn witness_values(&self) -> &[F] {
+        &self.witness_values
+    }
+}
+
+// Check that Groth16 can prove the circuit and that every term is on one of
+// its wires. The parser checks wire ids already, but circuits can also be
+// built or edited in code.
+fn check_circuit<F: PrimeField>(r1cs: &R1CS<F>) -> Result<(), CircuitError> {
+    r1cs.assert_groth16_compatible()?;
+    
+    let num_wires = r1cs.num_wires();
+    for (idx, constraint) in r1cs.constraints().iter().enumerate() {
+        let mut terms = constraint.a_terms.iter().chain(&constraint.b_terms).chain(&constraint.c_terms);
+        if let Some(term) = terms.find(|term| term.wire_id >= num_wires) {
+            return Err(CircuitError::WireOutOfRange { constraint: idx, wire: term.wire_id, num_wires });
+        }
+    }
+    Ok(())
+}
+
+// The witness values include the private inputs
+impl<F: PrimeField> Drop for CircuitFromR1CS<'_, F> {
+    fn drop(&mut self) {
+        self.witness_values.zeroize();
+    }
+}
+
+impl<F: PrimeField> ConstraintSynthesizer<F> for CircuitFromR1CS<'_, F> {
+    fn generate_constraints(
+        self,
+        cs: ConstraintSystemRef<F>,
+    ) -> Result<(), SynthesisError> {
+        synthesize(self.r1cs, cs, |wire| self.witness_values.get(wire).copied())
+    }
+}
+
+/// A circuit like [`CircuitFromR1CS`] whose witness values come from a
+/// closure instead of a pre-built vector, e.g. to fetch them from an HSM or
+/// compute them on demand without materializing the whole witness.
+///
+/// During proving, `generate_constraints` calls the closure exactly once
+/// per wire, in increasing wire order: the public outputs and inputs
+/// (wires `1..=num_public_values`), then the private wires up to
+/// `num_wires - 1`. It is never called for wire 0, the constant ONE. In
+/// setup the values aren't needed and the closure isn't called at all.
+/// [`LazyCircuitFromR1CS::get_public_inputs`] calls it again for the public
+/// wires.
+pub struct LazyCircuitFromR1CS<'a, F: PrimeField> {
+    r1cs: &'a R1CS<F>,
+    witness_fn: Box<dyn Fn(usize) -> F + 'a>,
+}
+
+impl<'a, F: PrimeField> LazyCircuitFromR1CS<'a, F> {
+    /// Build the circuit with a closure returning the value of a wire index.
+    /// The values can't be checked up front, so wrong ones only show up when
+    /// proving (arkworks asserts the constraints hold in debug builds) or as
+    /// a proof that doesn't verify.
+    pub fn new(r1cs: &'a R1CS<F>, witness_fn: Box<dyn Fn(usize) -> F + 'a>) -> Result<Self, CircuitError> {
+        check_circuit(r1cs)?;
+        Ok(Self { r1cs, witness_fn })
+    }
+    
+    /// Get the public inputs for verification, in the same order as
+    /// [`CircuitFromR1CS::get_public_inputs`]
+    pub fn get_public_inputs(&self) -> Vec<F> {
+        (1..=self.r1cs.num_public_values() as usize).map(|wire| (self.witness_fn)(wire)).collect()
+    }
+    
+    /// Get the parsed circuit
+    pub fn r1cs(&self) -> &'a R1CS<F> {
+        self.r1cs
+    }
+}
+
+impl<F: PrimeField> ConstraintSynthesizer<F> for LazyCircuitFromR1CS<'_, F> {
+    fn generate_constraints(
+        self,
+        cs: ConstraintSystemRef<F>,
+    ) -> Result<(), SynthesisError> {
+        synthesize(self.r1cs, cs, |wire| Some((self.witness_fn)(wire)))
+    }
+}
+
+// Allocate the circuit's wires and enforce its constraints, taking wire
+// values from `value`; `None` is a missing assignment. arkworks only calls
+// the allocation closures when proving, each once and in wire order.
+fn synthesize<F: PrimeField>(
+    r1cs: &R1CS<F>,
+    cs: ConstraintSystemRef<F>,
+    value: impl Fn(usize) -> Option<F>,
+) -> Result<(), SynthesisError> {
+    info!("Generating constraints for R1CS circuit...");
+    
+    // Allocate variables
+    let num_wires = r1cs.num_wires() as usize;
+    let num_public = r1cs.num_public_values() as usize;
+    
+    debug!("Allocating {} variables ({} public, {} private)...", 
+             num_wires, num_public + 1, num_wires - num_public - 1);
+    
+    // Wire 0 is the constant ONE, which arkworks already provides
+    let mut variables = Vec::with_capacity(num_wires);
+    variables.push(Variable::One);
+    
+    // Allocate the instance variables from exactly the values the verifier
+    // is given (public outputs, then public inputs), so their number and
+    // order always match
+    for wire in 1..=num_public {
+        variables.push(cs.new_input_variable(|| value(wire).ok_or(SynthesisError::AssignmentMissing))?);
+    }
+    
+    // Allocate private witness variables
+    for wire in variables.len()..num_wires {
+        variables.push(cs.new_witness_variable(|| value(wire).ok_or(SynthesisError::AssignmentMissing))?);
+    }
+    
+    // Add constraints
+    let constraints = r1cs.constraints();
+    debug!("Adding {} constraints to the circuit...", constraints.len());
+    
+    for (idx, constraint) in constraints.iter().enumerate() {
+        // Create linear combinations for A, B, and C. As in circom, an
+        // empty side is the zero linear combination, whichever side it is
+        // (linear constraints are written as 0 * 0 = C), and a term on
+        // wire 0 is a constant, `coefficient * ONE`, so a side with only
+        // such terms is a constant.
+        let mut a_lc = ark_relations::r1cs::LinearCombination::<F>::zero();
+        for term in &constraint.a_terms {
+            if term.wire_id as usize >= variables.len() {
+                return Err(SynthesisError::AssignmentMissing);
+            }
+            a_lc += (term.coefficient, variables[term.wire_id as usize]);
+        }
+        
+        let mut b_lc = ark_relations::r1cs::LinearCombination::<F>::zero();
+        for term in &constraint.b_terms {
+            if term.wire_id as usize >= variables.len() {
+                return Err(SynthesisError::AssignmentMissing);
+            }
+            b_lc += (term.coefficient, variables[term.wire_id as usize]);
+        }
+        
+        let mut c_lc = ark_relations::r1cs::LinearCombination::<F>::zero();
+        for term in &constraint.c_terms {
+            if term.wire_id as usize >= variables.len() {
+                return Err(SynthesisError::AssignmentMissing);
+            }
+            c_lc += (term.coefficient, variables[term.wire_id as usize]);
+        }
+        
+        // Enforce the constraint: A * B = C
+        cs.enforce_constraint(a_lc, b_lc, c_lc)?;
+        
+        if idx < 3 || idx + 1 == constraints.len() {
+            trace!("  Added constraint #{}: {}", idx, constraint);
+        } else if idx == 3 {
+            trace!("  ... and {} more constraints", constraints.len().saturating_sub(4));
+        }
+    }
+    
+    info!("Circuit generation complete with {} constraints", constraints.len());
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use std::fs::File;
+use std::io::{self, BufReader, Cursor, Read, Seek, SeekFrom};
+use std::path::Path;
+use flate2::read::GzDecoder;
+use log::info;
+use ruzstd::decoding::StreamingDecoder;
+use crate::r1cs::R1CSError;
+
+/// Largest decompressed size accepted, so a small compressed file can't
+/// expand into an unbounded allocation
+pub const MAX_DECOMPRESSED_SIZE: u64 = 4 << 30;
+
+/// How a file on disk is compressed, detected from its magic bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    /// Detect the compression format from the first bytes of a file
+    pub fn detect(magic: &[u8]) -> Self {
+        if magic.starts_with(&[0x1f, 0x8b]) {
+            Compression::Gzip
+        } else if magic.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Compression::Zstd
+        } else {
+            Compression::None
+        }
+    }
+}
+
+/// A file opened for parsing: read directly if uncompressed, otherwise
+/// decompressed into memory first, since the parsers need `Seek`
+pub enum Source {
+    File(BufReader<File>),
+    Memory(Cursor<Vec<u8>>),
+}
+
+impl Read for Source {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Source::File(reader) => reader.read(buf),
+            Source::Memory(reader) => reader.read(buf),
+        }
+    }
+}
+
+impl Seek for Source {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        match self {
+            Source::File(reader) => reader.seek(pos),
+            Source::Memory(reader) => reader.seek(pos),
+        }
+    }
+}
+
+/// Open a possibly gzip- or zstd-compressed file for parsing
+pub fn open<P: AsRef<Path>>(path: P) -> Result<Source, R1CSError> {
+    let mut file = File::open(&path)?;
+
+    let mut magic = [0u8; 4];
+    let n = read_up_to(&mut file, &mut magic)?;
+    file.seek(SeekFrom::Start(0))?;
+
+    let compression = Compression::detect(&magic[..n]);
+    let decoder: Box<dyn Read> = match compression {
+        Compression::None => return Ok(Source::File(BufReader::new(file))),
+        Compression::Gzip => Box::new(GzDecoder::new(BufReader::new(file))),
+        Compression::Zstd => Box::new(
+            StreamingDecoder::new(BufReader::new(file))
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?,
+        ),
+    };
+
+    info!("Decompressing {:?} file {}", compression, path.as_ref().display());
+
+    // Read one byte past the cap to tell "exactly at the cap" from "over it"
+    let mut data = Vec::new();
+    decoder.take(MAX_DECOMPRESSED_SIZE + 1).read_to_end(&mut data)?;
+    if data.len() as u64 > MAX_DECOMPRESSED_SIZE {
+        return Err(R1CSError::DecompressedTooLarge(MAX_DECOMPRESSED_SIZE));
+    }
+
+    Ok(Source::Memory(Cursor::new(data)))
+}
+
+/// Fill as much of `buf` as the reader has, returning the number of bytes read
+fn read_up_to<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(filled)
+}
@@ -0,0 +1,211 @@
+use std::error::Error;
+use std::fmt;
+use ark_ec::pairing::Pairing;
+use ark_ff::PrimeField;
+use ark_groth16::{prepare_verifying_key, Groth16, Proof, ProvingKey, VerifyingKey};
+use ark_relations::r1cs::{ConstraintSynthesizer, SynthesisError};
+use ark_snark::SNARK;
+use ark_std::rand::{CryptoRng, RngCore};
+use crate::circuit::CircuitFromR1CS;
+use crate::r1cs::R1CS;
+
+/// Errors that can occur while verifying a proof
+#[derive(Debug)]
+pub enum VerifyError {
+    /// The number of public inputs doesn't match the circuit's public
+    /// outputs plus public inputs (`R1CS::num_public_values`)
+    PublicInputLengthMismatch { expected: usize, got: usize },
+    /// arkworks rejected the verifying key or inputs
+    Synthesis(SynthesisError),
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerifyError::PublicInputLengthMismatch { expected, got } => write!(
+                f,
+                "Got {} public inputs, but the verifying key expects {} (the circuit's public outputs and inputs, without ONE)",
+                got, expected
+            ),
+            VerifyError::Synthesis(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for VerifyError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            VerifyError::Synthesis(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<SynthesisError> for VerifyError {
+    fn from(e: SynthesisError) -> Self {
+        VerifyError::Synthesis(e)
+    }
+}
+
+/// Run the circuit-specific Groth16 setup. The circuit's witness values are
+/// not used, so one built with `CircuitFromR1CS::new_with_dummy` will do.
+///
+/// Whoever knows the randomness drawn from `rng` can forge proofs, so a
+/// single-party setup like this is only as trustworthy as its RNG.
+pub fn setup<E: Pairing, R: RngCore + CryptoRng>(
+    circuit: CircuitFromR1CS<'_, E::ScalarField>,
+    rng: &mut R,
+) -> Result<ProvingKey<E>, SynthesisError> {
+    Groth16::<E>::generate_random_parameters_with_reduction(circuit, rng)
+}
+
+/// Prove that the circuit's witness satisfies its constraints. `rng` blinds
+/// the proof and must not be predictable, or the proof can leak the witness.
+///
+/// The circuit is a [`CircuitFromR1CS`] or a
+/// [`LazyCircuitFromR1CS`](crate::circuit::LazyCircuitFromR1CS) for the
+/// R1CS the key was set up for.
+pub fn prove<E: Pairing, R: RngCore + CryptoRng>(
+    pk: &ProvingKey<E>,
+    circuit: impl ConstraintSynthesizer<E::ScalarField>,
+    rng: &mut R,
+) -> Result<Proof<E>, SynthesisError> {
+    Groth16::<E>::prove(pk, circuit, rng)
+}
+
+/// Verify a proof against its public inputs (public outputs, then public
+/// inputs, without the ONE wire).
+///
+/// The verifying key has one input commitment per public value plus one for
+/// ONE, so a wrong number of inputs is reported as such up front.
+pub fn verify<E: Pairing>(
+    vk: &VerifyingKey<E>,
+    public_inputs: &[E::ScalarField],
+    proof: &Proof<E>,
+) -> Result<bool, VerifyError> {
+    let expected = vk.gamma_abc_g1.len().saturating_sub(1);
+    if public_inputs.len() != expected {
+        return Err(VerifyError::PublicInputLengthMismatch {
+            expected,
+            got: public_inputs.len(),
+        });
+    }
+
+    let pvk = prepare_verifying_key(vk);
+    Ok(Groth16::<E>::verify_with_processed_vk(&pvk, public_inputs, proof)?)
+}
+
+/// Rerandomize a proof into a fresh one for the same statement, without
+/// the witness or proving key. The result verifies against the same public
+/// inputs whenever `proof` does, and can't be linked to it, so one proof
+/// can be shown several times unlinkably.
+///
+/// Like [`prove`], `rng` must not be predictable.
+pub fn rerandomize_proof<E: Pairing, R: RngCore + CryptoRng>(
+    proof: &Proof<E>,
+    vk: &VerifyingKey<E>,
+    rng: &mut R,
+) -> Proof<E> {
+    Groth16::<E>::rerandomize_proof(vk, proof, rng)
+}
+
+/// What a [`SynthesisError`] from [`setup`] or [`prove`] most likely comes
+/// down to, found by cross-referencing the circuit and witness
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SynthesisDiagnosis {
+    /// The witness violates this constraint (see [`R1CS::check_witness`])
+    UnsatisfiedConstraint(usize),
+    /// This constraint refers to a wire the witness has no value for
+    MissingAssignment { constraint: usize, wire: u32, witness_len: usize },
+    /// The QAP needs an evaluation domain of `2^log_size` points, but the
+    /// scalar field only has FFT domains up to `2^max_log_size`
+    DomainTooLarge { log_size: u32, max_log_size: u32 },
+    /// These wires appear in no constraint
+    UnconstrainedWires(Vec<u32>),
+    /// Nothing in the circuit or witness explains the error
+    Unknown,
+}
+
+impl fmt::Display for SynthesisDiagnosis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SynthesisDiagnosis::UnsatisfiedConstraint(idx) => write!(
+                f,
+                "the witness does not satisfy constraint #{}; check the inputs the witness was computed from",
+                idx
+            ),
+            SynthesisDiagnosis::MissingAssignment { constraint, wire, witness_len } => write!(
+                f,
+                "constraint #{} uses wire {}, but the witness only has {} values; it may be for another circuit",
+                constraint, wire, witness_len
+            ),
+            SynthesisDiagnosis::DomainTooLarge { log_size, max_log_size } => write!(
+                f,
+                "the circuit needs an FFT domain of 2^{} points, but the curve's scalar field supports at most 2^{}; \
+                 split the circuit or reduce its constraints",
+                log_size, max_log_size
+            ),
+            SynthesisDiagnosis::UnconstrainedWires(wires) => write!(
+                f,
+                "{} wire(s) appear in no constraint (first: {}); recompile with circom --O1 or --O2 to remove them",
+                wires.len(),
+                wires.first().map_or(String::new(), u32::to_string)
+            ),
+            SynthesisDiagnosis::Unknown => write!(f, "no cause found in the circuit or witness"),
+        }
+    }
+}
+
+/// Explain a [`SynthesisError`] from setting up or proving `r1cs`, pointing
+/// at the offending constraint where possible. Pass the witness the circuit
+/// was built with, if it had a real one, so it can be checked against the
+/// constraints.
+pub fn diagnose_synthesis_error<F: PrimeField>(
+    error: &SynthesisError,
+    r1cs: &R1CS<F>,
+    witness: Option<&[F]>,
+) -> SynthesisDiagnosis {
+    match error {
+        SynthesisError::PolynomialDegreeTooLarge => {
+            // arkworks' QAP reduction evaluates over one point per
+            // constraint and per instance variable (ONE and the public values)
+            let size = r1cs.num_constraints() as u64 + 1 + r1cs.num_public_values() as u64;
+            SynthesisDiagnosis::DomainTooLarge {
+                log_size: size.next_power_of_two().trailing_zeros(),
+                max_log_size: F::TWO_ADICITY,
+            }
+        }
+        SynthesisError::UnconstrainedVariable => {
+            let wires = r1cs.unconstrained_wires();
+            if wires.is_empty() {
+                SynthesisDiagnosis::Unknown
+            } else {
+                SynthesisDiagnosis::UnconstrainedWires(wires)
+            }
+        }
+        SynthesisError::AssignmentMissing | SynthesisError::Unsatisfiable => match witness {
+            Some(witness) => diagnose_witness(r1cs, witness),
+            None => SynthesisDiagnosis::Unknown,
+        },
+        _ => SynthesisDiagnosis::Unknown,
+    }
+}
+
+// Find the first constraint the witness can't be evaluated on or doesn't
+// satisfy
+fn diagnose_witness<F: PrimeField>(r1cs: &R1CS<F>, witness: &[F]) -> SynthesisDiagnosis {
+    for (idx, constraint) in r1cs.constraints().iter().enumerate() {
+        let mut terms = constraint.a_terms.iter().chain(&constraint.b_terms).chain(&constraint.c_terms);
+        if let Some(term) = terms.find(|term| term.wire_id as usize >= witness.len()) {
+            return SynthesisDiagnosis::MissingAssignment {
+                constraint: idx,
+                wire: term.wire_id,
+                witness_len: witness.len(),
+            };
+        }
+    }
+    match r1cs.check_witness(witness) {
+        Err(idx) => SynthesisDiagnosis::UnsatisfiedConstraint(idx),
+        Ok(()) => SynthesisDiagnosis::Unknown,
+    }
+}
@@ -0,0 +1,26 @@
+//! Groth16 proofs for circom circuits with arkworks: parse `.r1cs` and
+//! `.wtns` files, build an arkworks circuit from them, and set up, prove
+//! and verify over bn254 or bls12-381.
+
+pub mod artifacts;
+pub mod batch;
+pub mod bundle;
+pub mod cache;
+pub mod circuit;
+pub mod compression;
+pub mod groth16;
+pub mod public_hash;
+pub mod r1cs;
+pub mod search;
+pub mod snarkjs_export;
+pub mod snarkjs_import;
+pub mod solidity;
+pub mod solve;
+pub mod sym;
+pub mod witness;
+pub mod workflow;
+
+pub use circuit::{CircuitError, CircuitFromR1CS, LazyCircuitFromR1CS};
+pub use groth16::{diagnose_synthesis_error, prove, rerandomize_proof, setup, verify, SynthesisDiagnosis, VerifyError};
+pub use r1cs::{KnownCurve, R1CSError, R1CS};
+pub use witness::Witness;
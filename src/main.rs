@@ -1,346 +1,1563 @@
-mod r1cs;
-
-use ark_bls12_381::{Bls12_381, Fr};
-use ark_ff::{Zero, One};
-use ark_groth16::{prepare_verifying_key, Groth16};
-use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+use ark_bls12_381::Bls12_381;
+use ark_bn254::Bn254;
+use ark_ff::PrimeField;
 use ark_std::rand::{rngs::StdRng, SeedableRng};
-use std::io;
-use std::path::PathBuf;
+use std::io::{self, BufWriter, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::fmt;
 use std::fs;
-use ark_snark::SNARK;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::process::ExitCode;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use clap::parser::ValueSource;
+use clap::{ArgAction, ArgMatches, Args, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use indicatif::ProgressBar;
+use log::{debug, info, LevelFilter};
+use serde::Deserialize;
+use dogecoin_zkp_generator_qa1::{
+    artifacts, batch, bundle, groth16, r1cs, search, snarkjs_export, snarkjs_import, solidity, solve, sym, witness,
+    SynthesisDiagnosis,
+};
+use dogecoin_zkp_generator_qa1::workflow::{self, Event, Hooks, Phase, PublicInput, WorkflowError};
+use dogecoin_zkp_generator_qa1::public_hash::{hash_public_inputs, HashFunction};
+use dogecoin_zkp_generator_qa1::snarkjs_export::{Endianness, SnarkjsCurve};
 
-struct CircuitFromR1CS {
-    r1cs: r1cs::R1CS,
-    witness_values: Vec<Fr>,
+// Verify the keys and proof a previous run saved to --out-dir. The files
+// don't record their curve, so it comes from --curve, or the R1CS if given.
+fn verify_saved(cli: &Cli) -> io::Result<()> {
+    let out_dir = cli.out_dir.as_deref().expect("--verify-only requires --out-dir");
+    let curve = match (cli.curve, &cli.r1cs) {
+        (Some(curve), _) => curve,
+        (None, Some(r1cs)) => select_curve(r1cs, None, &cli.read_options())?,
+        (None, None) => Curve::Bn254,
+    };
+    println!("🔧 Using curve: {}", curve);
+    
+    let vk = out_dir.join("verifying_key.bin");
+    let public = [PublicInput::File(out_dir.join("public.json"))];
+    let proof = out_dir.join("proof.bin");
+    let endianness = cli.endianness.map(Into::into);
+    match curve {
+        Curve::Bn254 => verify::<Bn254>(&vk, &public, &proof, HashFunction::default(), false, endianness),
+        Curve::Bls12_381 => verify::<Bls12_381>(&vk, &public, &proof, HashFunction::default(), false, endianness),
+    }
 }
 
-impl CircuitFromR1CS {
-    fn new(r1cs: r1cs::R1CS) -> Self {
-        let num_wires = r1cs.num_wires() as usize;
-        let mut witness_values = vec![Fr::zero(); num_wires];
-        
-        // Set ONE wire
-        witness_values[0] = Fr::one();
-        
-        // For demonstration, set simple values for public inputs
-        // In a real scenario, these would be the actual input values
-        for i in 1..=r1cs.num_public_values() as usize {
-            if i < witness_values.len() {
-                witness_values[i] = Fr::from(i as u64);
+// Search a directory tree for the R1CS file, refusing to guess between several
+fn search_r1cs_file(root: &Path, pattern: &str, max_depth: usize) -> io::Result<PathBuf> {
+    println!("🔍 Searching {} for {} (depth {})...", root.display(), pattern, max_depth);
+    
+    let mut matches = search::find_files(root, pattern, max_depth)?;
+    match matches.len() {
+        0 => {
+            println!("❌ No file matching {} under {}", pattern, root.display());
+            println!("Use --search-root, --search-pattern or --max-depth to look elsewhere.");
+            Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no file matching {} under {}", pattern, root.display())
+            ))
+        }
+        1 => Ok(matches.remove(0)),
+        n => {
+            println!("❌ Found {} files matching {}:", n, pattern);
+            for path in &matches {
+                println!("   - {}", path.display());
             }
+            println!("Pass one with --r1cs, or narrow --search-pattern.");
+            Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{} files match {}", n, pattern)
+            ))
         }
-        
-        // For private inputs, set some sample values
-        for i in (r1cs.num_public_values() as usize + 1)..num_wires {
-            witness_values[i] = Fr::from((i * 10) as u64);
+    }
+}
+
+// Print what we can about a file that failed to parse as R1CS
+fn print_r1cs_diagnostics(r1cs_path: &Path) {
+    // If the file exists but parsing failed, print more detailed information
+    if r1cs_path.exists() {
+        if let Ok(metadata) = fs::metadata(r1cs_path) {
+            debug!("   File exists and is {} bytes", metadata.len());
+            
+            // Check the magic bytes and version without parsing the rest
+            if let Ok(file) = fs::File::open(r1cs_path) {
+                match <r1cs::R1CS>::probe(file) {
+                    Ok((version, sections)) => debug!("   File is R1CS version {} with {} sections", version, sections),
+                    Err(e) => debug!("   {}", e),
+                }
+            }
         }
-        
-        println!("Initialized witness values:");
-        for (i, val) in witness_values.iter().enumerate().take(10) {
-            println!("  x{} = {:?}", i, val);
+    }
+}
+
+/// Hash function for the public input hash `verify` prints
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum PublicHash {
+    Sha256,
+    Keccak256,
+}
+
+impl From<PublicHash> for HashFunction {
+    fn from(hash: PublicHash) -> Self {
+        match hash {
+            PublicHash::Sha256 => HashFunction::Sha256,
+            PublicHash::Keccak256 => HashFunction::Keccak256,
+        }
+    }
+}
+
+/// Byte order for hex public inputs (`--endianness`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum PublicEndianness {
+    /// Little-endian, as arkworks serializes field elements
+    Le,
+    /// Big-endian, as the EVM reads a uint256
+    Be,
+}
+
+impl From<PublicEndianness> for Endianness {
+    fn from(endianness: PublicEndianness) -> Self {
+        match endianness {
+            PublicEndianness::Le => Endianness::Little,
+            PublicEndianness::Be => Endianness::Big,
         }
-        if num_wires > 10 {
-            println!("  ... and {} more values", num_wires - 10);
+    }
+}
+
+/// Pairing-friendly curve to run Groth16 over
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Deserialize)]
+enum Curve {
+    /// alt_bn128, circom's default curve
+    #[serde(rename = "bn254")]
+    Bn254,
+    /// BLS12-381 (circom `--prime bls12381`)
+    #[value(name = "bls12-381")]
+    #[serde(rename = "bls12-381")]
+    Bls12_381,
+}
+
+impl From<r1cs::KnownCurve> for Curve {
+    fn from(curve: r1cs::KnownCurve) -> Self {
+        match curve {
+            r1cs::KnownCurve::Bn254 => Curve::Bn254,
+            r1cs::KnownCurve::Bls12_381 => Curve::Bls12_381,
         }
-        
-        Self {
-            r1cs,
-            witness_values,
+    }
+}
+
+impl From<Curve> for r1cs::KnownCurve {
+    fn from(curve: Curve) -> Self {
+        match curve {
+            Curve::Bn254 => r1cs::KnownCurve::Bn254,
+            Curve::Bls12_381 => r1cs::KnownCurve::Bls12_381,
         }
     }
+}
+
+impl fmt::Display for Curve {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Curve::Bn254 => write!(f, "bn254"),
+            Curve::Bls12_381 => write!(f, "bls12-381"),
+        }
+    }
+}
+
+// Set when stdout carries machine-readable output (`--json`), so progress
+// messages go to stderr instead
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+// Print a progress message, to stderr if stdout is reserved for JSON
+macro_rules! status {
+    ($($arg:tt)*) => {
+        if JSON_OUTPUT.load(Ordering::Relaxed) {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
+
+// Write a JSON value to a file
+fn write_json(path: &Path, value: &serde_json::Value) -> io::Result<()> {
+    fs::write(path, serde_json::to_string_pretty(value)?)
+}
+
+/// A proof was checked and rejected, as opposed to an error that kept it
+/// from being checked. `main` exits with status 1 for this and 2 for any
+/// other error.
+#[derive(Debug)]
+struct VerificationFailed;
+
+impl fmt::Display for VerificationFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "proof verification failed")
+    }
+}
+
+impl std::error::Error for VerificationFailed {}
+
+/// `check` found a constraint the witness doesn't satisfy. Like a rejected
+/// proof, `main` exits with status 1 for this.
+#[derive(Debug)]
+struct WitnessUnsatisfied(usize);
+
+impl fmt::Display for WitnessUnsatisfied {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "witness does not satisfy constraint #{}", self.0)
+    }
+}
+
+impl std::error::Error for WitnessUnsatisfied {}
+
+// Set by --progress when stderr is a terminal
+static PROGRESS: AtomicBool = AtomicBool::new(false);
+
+// Run `f` under a spinner on stderr, for steps that can take minutes
+// without reporting how far along they are
+fn with_spinner<T>(message: &'static str, f: impl FnOnce() -> T) -> T {
+    if !PROGRESS.load(Ordering::Relaxed) {
+        return f();
+    }
+    let spinner = ProgressBar::new_spinner().with_message(message);
+    spinner.enable_steady_tick(Duration::from_millis(100));
+    let result = f();
+    spinner.finish_and_clear();
+    result
+}
+
+// How long each workflow phase took, in the order they ran
+static TIMINGS: Mutex<Vec<(&str, Duration)>> = Mutex::new(Vec::new());
+
+// Run one phase of the workflow, logging how long it took and recording it
+// for the --timings summary
+fn timed<T>(phase: &'static str, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+    info!("{} took {:.2?}", phase, elapsed);
+    TIMINGS.lock().unwrap().push((phase, elapsed));
+    result
+}
+
+// Print the recorded phase durations as a table
+fn print_timings() {
+    let timings = TIMINGS.lock().unwrap();
+    if timings.is_empty() {
+        return;
+    }
+    status!("\n⏱️  Timings:");
+    for (phase, elapsed) in timings.iter() {
+        status!("  {:<8} {:>10.2?}", format!("{}:", phase), elapsed);
+    }
+}
+
+/// Generate and verify a Groth16 proof for a circom R1CS circuit.
+///
+/// Without a subcommand, runs setup, prove and verify in one go.
+#[derive(Parser)]
+#[command(name = "qa1", version, args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    
+    /// Path to the R1CS file (absolute or relative)
+    #[arg(long, conflicts_with_all = ["bundle", "search"])]
+    r1cs: Option<PathBuf>,
+    
+    /// Search a directory tree for the R1CS file
+    #[arg(long, conflicts_with = "bundle")]
+    search: bool,
     
-    // Get the public inputs for verification
-    fn get_public_inputs(&self) -> Vec<Fr> {
-        let mut public_inputs = Vec::new();
+    /// Directory to search from
+    #[arg(long, default_value = ".", requires = "search")]
+    search_root: PathBuf,
+    
+    /// File name glob to search for (`*` and `?` wildcards)
+    #[arg(long, default_value = "*.r1cs", requires = "search")]
+    search_pattern: String,
+    
+    /// How many directories below the search root to look in
+    #[arg(long, default_value_t = 5, requires = "search")]
+    max_depth: usize,
+    
+    /// Take the circuit (and witness) from a circom artifact bundle directory
+    #[arg(long)]
+    bundle: Option<PathBuf>,
+    
+    /// Witness file in snarkjs .wtns format
+    #[arg(long)]
+    wtns: Option<PathBuf>,
+    
+    /// circom .sym file, used to name signals in diagnostics
+    #[arg(long)]
+    sym: Option<PathBuf>,
+    
+    /// Directory to save the proving key, verifying key and proof to
+    /// (binary, plus snarkjs-compatible JSON)
+    #[arg(long)]
+    out_dir: Option<PathBuf>,
+    
+    /// Skip verifying the new proof locally (e.g. when it will be verified elsewhere)
+    #[arg(long)]
+    no_verify: bool,
+    
+    /// Only verify the proof previously saved to --out-dir, without setup or proving
+    #[arg(long, requires = "out_dir", conflicts_with_all = ["no_verify", "bundle", "search", "wtns"])]
+    verify_only: bool,
+    
+    /// Fail unless the first public output computed by the witness equals this decimal or 0x-hex value
+    #[arg(long, value_name = "VALUE", conflicts_with = "verify_only")]
+    expect_output: Option<String>,
+    
+    /// Curve to prove over (default: detected from the R1CS prime)
+    #[arg(long, value_enum)]
+    curve: Option<Curve>,
+    
+    #[command(flatten)]
+    setup_rng: SetupRng,
+    
+    #[command(flatten)]
+    setup_limits: SetupLimits,
+    
+    /// Parse constraints on all cores (faster for large circuits)
+    #[arg(long, global = true)]
+    parallel: bool,
+    
+    /// Cache parsed R1CS headers in a `<file>.meta` sidecar and parsed circuits in a
+    /// `<file>.parsed` dump, refreshed when the file changes
+    #[arg(long, global = true)]
+    cache: bool,
+    
+    /// Reject R1CS coefficients that aren't reduced modulo the field prime, instead of reducing them
+    #[arg(long, global = true)]
+    strict: bool,
+    
+    /// Cap the threads used for parallel parsing and for arkworks' parallel
+    /// MSMs and FFTs during setup and proving (default: all cores)
+    #[arg(long, global = true, value_parser = clap::value_parser!(u16).range(1..))]
+    threads: Option<u16>,
+    
+    /// Write public.json as 32-byte 0x-hex words in this byte order instead of
+    /// snarkjs' decimal strings, and read hex public inputs in it
+    #[arg(long, global = true, value_enum)]
+    endianness: Option<PublicEndianness>,
+    
+    /// Treat this many leading non-ONE wires as public, overriding the R1CS
+    /// header (for files whose header counts are wrong)
+    #[arg(long, global = true, value_name = "N")]
+    public_count: Option<u32>,
+    
+    /// Show progress bars while parsing constraints and a spinner during setup (only on a terminal)
+    #[arg(long, global = true)]
+    progress: bool,
+    
+    /// Print how long parsing, setup, proving and verifying took
+    #[arg(long, global = true)]
+    timings: bool,
+    
+    /// Read default flag values from this TOML file instead of ./qa1.toml
+    #[arg(long, global = true, value_name = "FILE")]
+    config: Option<PathBuf>,
+    
+    /// Log more detail (-v for progress, -vv for parsing details, -vvv for every constraint)
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+}
+
+impl Cli {
+    // How to parse R1CS files, from the global flags
+    fn read_options(&self) -> r1cs::ReadOptions {
+        r1cs::ReadOptions {
+            parallel: self.parallel,
+            cache: self.cache,
+            strict: self.strict,
+            progress: PROGRESS.load(Ordering::Relaxed),
+            public_count: self.public_count,
+        }
+    }
+    
+    // Fill in flags left off the command line from the config file; values
+    // given on the command line always win
+    fn apply_config(&mut self, config: &Config, matches: &ArgMatches) {
+        let unset = |id| matches.value_source(id) != Some(ValueSource::CommandLine);
         
-        // Add public outputs and inputs
-        let public_count = self.r1cs.num_public_values() as usize;
-        for i in 1..=public_count {
-            if i < self.witness_values.len() {
-                public_inputs.push(self.witness_values[i]);
+        if unset("curve") {
+            self.curve = config.curve.or(self.curve);
+        }
+        if unset("search_root") {
+            if let Some(root) = &config.search_root {
+                self.search_root = root.clone();
+            }
+        }
+        if unset("search_pattern") {
+            if let Some(pattern) = &config.search_pattern {
+                self.search_pattern = pattern.clone();
+            }
+        }
+        if unset("max_depth") {
+            self.max_depth = config.max_depth.unwrap_or(self.max_depth);
+        }
+        if unset("threads") {
+            self.threads = config.threads.or(self.threads);
+        }
+        if unset("endianness") {
+            self.endianness = config.endianness.or(self.endianness);
+        }
+        for (id, flag, value) in [
+            ("parallel", &mut self.parallel, config.parallel),
+            ("cache", &mut self.cache, config.cache),
+            ("strict", &mut self.strict, config.strict),
+            ("progress", &mut self.progress, config.progress),
+            ("timings", &mut self.timings, config.timings),
+        ] {
+            if unset(id) {
+                *flag = value.unwrap_or(*flag);
             }
         }
         
-        public_inputs
+        match (&mut self.command, matches.subcommand()) {
+            (Some(command), Some((_, sub_matches))) => command.apply_config(config, sub_matches),
+            _ => {
+                self.setup_rng.apply_config(config, matches);
+                self.setup_limits.apply_config(config, matches);
+            }
+        }
+    }
+}
+
+/// Default flag values from a `qa1.toml` file. Keys are the long flag names
+/// in snake_case, e.g. `search_root = "circuits"` for `--search-root`.
+///
+/// Precedence is command line, then config file, then built-in defaults.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    curve: Option<Curve>,
+    search_root: Option<PathBuf>,
+    search_pattern: Option<String>,
+    max_depth: Option<usize>,
+    threads: Option<u16>,
+    setup_seed: Option<u64>,
+    max_constraints: Option<u32>,
+    endianness: Option<PublicEndianness>,
+    parallel: Option<bool>,
+    cache: Option<bool>,
+    strict: Option<bool>,
+    progress: Option<bool>,
+    timings: Option<bool>,
+}
+
+/// The config file read when `--config` isn't given, from the working directory
+const DEFAULT_CONFIG: &str = "qa1.toml";
+
+impl Config {
+    // Read `--config`, or ./qa1.toml if it exists; no file means no defaults
+    fn load(path: Option<&Path>) -> io::Result<Option<Config>> {
+        let path = match path {
+            Some(path) => path,
+            None if Path::new(DEFAULT_CONFIG).is_file() => Path::new(DEFAULT_CONFIG),
+            None => return Ok(None),
+        };
+        let text = fs::read_to_string(path).map_err(|e| {
+            io::Error::new(e.kind(), format!("could not read config file {}: {}", path.display(), e))
+        })?;
+        let invalid = |e: &dyn fmt::Display| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("invalid config file {}: {}", path.display(), e))
+        };
+        let config: Config = toml::from_str(&text).map_err(|e| invalid(&e))?;
+        // The same range --threads accepts
+        if config.threads == Some(0) {
+            return Err(invalid(&"threads must be at least 1"));
+        }
+        info!("Loaded defaults from {}", path.display());
+        Ok(Some(config))
     }
 }
 
-impl ConstraintSynthesizer<Fr> for CircuitFromR1CS {
-    fn generate_constraints(
-        self,
-        cs: ConstraintSystemRef<Fr>,
-    ) -> Result<(), SynthesisError> {
-        println!("Generating constraints for R1CS circuit...");
+/// Where the setup randomness comes from. Neither choice is a secure
+/// ceremony, so one has to be made explicitly.
+#[derive(Args)]
+struct SetupRng {
+    /// Seed the setup RNG with a fixed value (reproducible keys, for testing only)
+    #[arg(long, conflicts_with = "setup_entropy")]
+    setup_seed: Option<u64>,
+    
+    /// Seed the setup RNG from the OS (fresh keys from an insecure single-party setup)
+    #[arg(long)]
+    setup_entropy: bool,
+}
+
+impl SetupRng {
+    // Take the config file's seed unless the command line chose an RNG
+    fn apply_config(&mut self, config: &Config, matches: &ArgMatches) {
+        let chosen = ["setup_seed", "setup_entropy"]
+            .into_iter()
+            .any(|id| matches.value_source(id) == Some(ValueSource::CommandLine));
+        if !chosen {
+            self.setup_seed = config.setup_seed.or(self.setup_seed);
+        }
+    }
+    
+    // Build the setup RNG, refusing to pick a seed silently
+    fn rng(&self) -> io::Result<StdRng> {
+        match (self.setup_seed, self.setup_entropy) {
+            (Some(seed), _) => {
+                println!("⚠️  Setup RNG seeded with {}: anyone who knows the seed can forge proofs", seed);
+                Ok(StdRng::seed_from_u64(seed))
+            }
+            (None, true) => {
+                println!("⚠️  ============================================================");
+                println!("⚠️  WARNING: keys come from an INSECURE single-party setup.");
+                println!("⚠️  The toxic waste existed in this process's memory; whoever");
+                println!("⚠️  controlled it could forge proofs. Use a multi-party");
+                println!("⚠️  ceremony for anything that matters.");
+                println!("⚠️  ============================================================");
+                Ok(StdRng::from_entropy())
+            }
+            (None, false) => {
+                println!("❌ Setup needs an explicit RNG choice: --setup-seed <u64> or --setup-entropy");
+                Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "no setup RNG chosen (use --setup-seed or --setup-entropy)"
+                ))
+            }
+        }
+    }
+}
+
+/// How big a circuit setup will take on. Setup memory grows with the
+/// constraint count, so huge circuits have to be asked for explicitly.
+#[derive(Args)]
+struct SetupLimits {
+    /// Refuse to run setup on circuits with more constraints than this
+    #[arg(long, default_value_t = 5_000_000)]
+    max_constraints: u32,
+}
+
+impl SetupLimits {
+    // Take the config file's limit unless one is given on the command line
+    fn apply_config(&mut self, config: &Config, matches: &ArgMatches) {
+        if matches.value_source("max_constraints") != Some(ValueSource::CommandLine) {
+            self.max_constraints = config.max_constraints.unwrap_or(self.max_constraints);
+        }
+    }
+}
+
+// A `verify --public` argument: `<wire>=<value>` sets one public wire, and
+// anything else is a file path
+fn parse_public_arg(arg: &str) -> Result<PublicInput, String> {
+    match arg.split_once('=') {
+        Some((wire, value)) if !wire.is_empty() && wire.bytes().all(|b| b.is_ascii_digit()) => Ok(PublicInput::Value {
+            wire: wire.parse().map_err(|e| format!("bad wire index {}: {}", wire, e))?,
+            value: value.to_string(),
+        }),
+        _ => Ok(PublicInput::File(PathBuf::from(arg))),
+    }
+}
+
+/// Individual steps of the Groth16 workflow, mirroring snarkjs
+#[derive(Subcommand)]
+enum Command {
+    /// Parse and validate an R1CS file and print its summary, without running Groth16
+    Info {
+        /// Path to the R1CS file
+        #[arg(long)]
+        r1cs: PathBuf,
         
-        // Allocate variables
-        let num_wires = self.r1cs.num_wires() as usize;
-        let num_public = self.r1cs.num_public_values() as usize;
+        /// Curve whose field to parse into (default: detected from the R1CS prime)
+        #[arg(long, value_enum)]
+        curve: Option<Curve>,
+    },
+    
+    /// Dump the parsed header and constraints as JSON, coefficients in decimal
+    DumpJson {
+        /// Path to the R1CS file
+        #[arg(long)]
+        r1cs: PathBuf,
         
-        println!("Allocating {} variables ({} public, {} private)...", 
-                 num_wires, num_public + 1, num_wires - num_public - 1);
+        /// Where to write the JSON (default: stdout)
+        #[arg(long)]
+        out: Option<PathBuf>,
         
-        // Allocate ONE wire (constant 1)
-        let one_var = cs.new_input_variable(|| Ok(Fr::one()))?;
+        /// Read and write one constraint at a time instead of holding the
+        /// whole circuit in memory; skips the checks done before proving
+        #[arg(long)]
+        stream: bool,
         
-        let mut variables = vec![one_var];
+        /// Curve whose field to parse into (default: detected from the R1CS prime)
+        #[arg(long, value_enum)]
+        curve: Option<Curve>,
+    },
+    
+    /// Print constraint density and wire usage statistics
+    Stats {
+        /// Path to the R1CS file
+        #[arg(long)]
+        r1cs: PathBuf,
         
-        // Allocate public input variables (public outputs + public inputs)
-        for i in 1..=num_public {
-            if i < self.witness_values.len() {
-                let var = cs.new_input_variable(|| Ok(self.witness_values[i]))?;
-                variables.push(var);
-            }
-        }
+        /// Curve whose field to parse into (default: detected from the R1CS prime)
+        #[arg(long, value_enum)]
+        curve: Option<Curve>,
         
-        // Allocate private witness variables
-        for i in (num_public + 1)..num_wires {
-            if i < self.witness_values.len() {
-                let var = cs.new_witness_variable(|| Ok(self.witness_values[i]))?;
-                variables.push(var);
-            }
-        }
+        /// Print the statistics as JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    
+    /// Run the circuit-specific setup and save the proving and verifying keys
+    Setup {
+        /// Path to the R1CS file
+        #[arg(long)]
+        r1cs: PathBuf,
         
-        // Add constraints
-        let constraints = self.r1cs.constraints();
-        println!("Adding {} constraints to the circuit...", constraints.len());
+        /// Where to save the proving key
+        #[arg(long)]
+        pk: PathBuf,
         
-        for (idx, constraint) in constraints.iter().enumerate() {
-            // Create linear combinations for A, B, and C
-            let mut a_lc = ark_relations::r1cs::LinearCombination::<Fr>::zero();
-            for term in &constraint.a_terms {
-                if term.wire_id as usize >= variables.len() {
-                    return Err(SynthesisError::AssignmentMissing);
-                }
-                a_lc = a_lc + (term.coefficient, variables[term.wire_id as usize]);
+        /// Where to save the verifying key
+        #[arg(long)]
+        vk: PathBuf,
+        
+        /// Curve to set up over (default: detected from the R1CS prime)
+        #[arg(long, value_enum)]
+        curve: Option<Curve>,
+        
+        #[command(flatten)]
+        setup_rng: SetupRng,
+        
+        #[command(flatten)]
+        setup_limits: SetupLimits,
+    },
+    
+    /// Cross-check a witness: the parsed constraints, arkworks' constraint
+    /// system and a fresh proof must all agree
+    Selfcheck {
+        /// Path to the R1CS file
+        #[arg(long)]
+        r1cs: PathBuf,
+        
+        /// Witness file in snarkjs .wtns format
+        #[arg(long)]
+        wtns: PathBuf,
+        
+        /// Curve to prove over (default: detected from the R1CS prime)
+        #[arg(long, value_enum)]
+        curve: Option<Curve>,
+        
+        #[command(flatten)]
+        setup_rng: SetupRng,
+        
+        #[command(flatten)]
+        setup_limits: SetupLimits,
+    },
+    
+    /// Set up, prove and verify the built-in `x * y = z` circuit, without any files
+    Demo {
+        /// First private input
+        #[arg(long, default_value_t = 5)]
+        x: u64,
+        
+        /// Second private input
+        #[arg(long, default_value_t = 7)]
+        y: u64,
+        
+        /// Curve to prove over
+        #[arg(long, value_enum, default_value_t = Curve::Bn254)]
+        curve: Curve,
+        
+        #[command(flatten)]
+        setup_rng: SetupRng,
+    },
+    
+    /// Check that a witness satisfies the constraints with native field arithmetic, without Groth16
+    Check {
+        /// Path to the R1CS file
+        #[arg(long)]
+        r1cs: PathBuf,
+        
+        /// Witness file in snarkjs .wtns format
+        #[arg(long)]
+        wtns: PathBuf,
+        
+        /// Curve whose field to check in (default: detected from the R1CS prime)
+        #[arg(long, value_enum)]
+        curve: Option<Curve>,
+    },
+    
+    /// Prove with a saved proving key and a witness
+    Prove {
+        /// Path to the R1CS file the proving key was set up for
+        #[arg(long)]
+        r1cs: PathBuf,
+        
+        /// Proving key saved by `setup`
+        #[arg(long)]
+        pk: PathBuf,
+        
+        /// Witness file in snarkjs .wtns format
+        #[arg(long)]
+        wtns: PathBuf,
+        
+        /// Where to save the proof
+        #[arg(long)]
+        proof: PathBuf,
+        
+        /// Where to save the public inputs (snarkjs public.json format)
+        #[arg(long)]
+        public: PathBuf,
+        
+        /// Curve to prove over (default: detected from the R1CS prime)
+        #[arg(long, value_enum)]
+        curve: Option<Curve>,
+    },
+    
+    /// Verify a saved proof against public inputs
+    Verify {
+        /// Verifying key saved by `setup`
+        #[arg(long)]
+        vk: PathBuf,
+        
+        /// Public inputs as a JSON array of decimal or 0x-hex strings (snarkjs
+        /// public.json), and/or `<wire>=<value>` to set public wire 1, 2, ...
+        /// directly; repeatable, with values overriding the file's
+        #[arg(long, required = true, value_parser = parse_public_arg)]
+        public: Vec<PublicInput>,
+        
+        /// Proof saved by `prove`
+        #[arg(long)]
+        proof: PathBuf,
+        
+        /// Curve the keys and proof are over
+        #[arg(long, value_enum, default_value_t = Curve::Bn254)]
+        curve: Curve,
+        
+        /// Hash function for the printed public input hash (keccak256 as on the EVM)
+        #[arg(long, value_enum, default_value_t = PublicHash::Sha256)]
+        hash: PublicHash,
+        
+        /// Print the result as JSON: {"verified", "public_inputs", "error"}
+        #[arg(long)]
+        json: bool,
+    },
+    
+    /// Generate a Solidity verifier contract for a verifying key (bn254 only)
+    ExportSolidity {
+        /// Verifying key saved by `setup`
+        #[arg(long)]
+        vk: PathBuf,
+        
+        /// Where to write the contract
+        #[arg(long, default_value = "Verifier.sol")]
+        out: PathBuf,
+        
+        /// Curve the key is over; must be bn254, the only curve with EVM pairing precompiles
+        #[arg(long, value_enum)]
+        curve: Curve,
+    },
+    
+    /// Verify a snarkjs bls12-381 proof with its verification_key.json, without setup
+    VerifySnarkjs {
+        /// snarkjs verification_key.json
+        #[arg(long)]
+        vk: PathBuf,
+        
+        /// snarkjs proof.json
+        #[arg(long)]
+        proof: PathBuf,
+        
+        /// snarkjs public.json
+        #[arg(long)]
+        public: PathBuf,
+    },
+    
+    /// Verify a directory of snarkjs bls12-381 proofs against one verifying key
+    VerifyBatch {
+        /// snarkjs verification_key.json
+        #[arg(long)]
+        vk: PathBuf,
+        
+        /// Directory of snarkjs proof JSON files
+        #[arg(long)]
+        proofs: PathBuf,
+        
+        /// Directory of public input JSON files, named like the proofs they belong to
+        #[arg(long)]
+        public: PathBuf,
+    },
+}
+
+impl Command {
+    // Fill in the subcommand's curve and setup flags from the config file,
+    // where it takes them and they aren't on the command line
+    fn apply_config(&mut self, config: &Config, matches: &ArgMatches) {
+        let curve_unset = matches.value_source("curve") != Some(ValueSource::CommandLine);
+        match self {
+            Command::Info { curve, .. }
+            | Command::DumpJson { curve, .. }
+            | Command::Stats { curve, .. }
+            | Command::Check { curve, .. }
+            | Command::Prove { curve, .. } if curve_unset => {
+                *curve = config.curve.or(*curve);
             }
-            
-            let mut b_lc = ark_relations::r1cs::LinearCombination::<Fr>::zero();
-            if constraint.b_terms.is_empty() {
-                // If B is empty, use 1 (ONE_WIRE)
-                b_lc = b_lc + (Fr::one(), variables[0]);
-            } else {
-                for term in &constraint.b_terms {
-                    if term.wire_id as usize >= variables.len() {
-                        return Err(SynthesisError::AssignmentMissing);
-                    }
-                    b_lc = b_lc + (term.coefficient, variables[term.wire_id as usize]);
+            Command::Setup { curve, setup_rng, setup_limits, .. }
+            | Command::Selfcheck { curve, setup_rng, setup_limits, .. } => {
+                if curve_unset {
+                    *curve = config.curve.or(*curve);
                 }
+                setup_rng.apply_config(config, matches);
+                setup_limits.apply_config(config, matches);
             }
-            
-            let mut c_lc = ark_relations::r1cs::LinearCombination::<Fr>::zero();
-            for term in &constraint.c_terms {
-                if term.wire_id as usize >= variables.len() {
-                    return Err(SynthesisError::AssignmentMissing);
+            Command::Demo { curve, setup_rng, .. } => {
+                if curve_unset {
+                    *curve = config.curve.unwrap_or(*curve);
                 }
-                c_lc = c_lc + (term.coefficient, variables[term.wire_id as usize]);
+                setup_rng.apply_config(config, matches);
             }
-            
-            // Enforce the constraint: A * B = C
-            cs.enforce_constraint(a_lc, b_lc, c_lc)?;
-            
-            if idx < 3 || idx == constraints.len() - 1 {
-                println!("  Added constraint #{}: {}", idx, constraint);
-            } else if idx == 3 {
-                println!("  ... and {} more constraints", constraints.len() - 4);
+            Command::Verify { curve, .. } if curve_unset => {
+                *curve = config.curve.unwrap_or(*curve);
             }
+            // export-solidity only takes bn254, and the rest have no such flags
+            _ => {}
         }
-        
-        println!("Circuit generation complete with {} constraints", constraints.len());
-        Ok(())
-    }
-}
-
-// Try to find a file with the given name in various locations
-fn find_file(filename: &str) -> Option<PathBuf> {
-    // List of possible directories to search
-    let search_dirs = [
-        "/home/administrator/work",
-        "/home/administrator/work/circomlib-cff5ab6",
-        "/home/administrator",
-        ".",
-        "./work",
-        "../work",
-        "/home/administrator/qa1",
-        "/tmp",
-    ];
-    
-    // First, try exact paths
-    let exact_paths = [
-        format!("/home/administrator/work/circomlib-cff5ab6/{}", filename),
-        format!("/home/administrator/work/{}", filename),
-        format!("/home/administrator/{}", filename),
-        format!("./{}", filename),
-    ];
-    
-    for path_str in exact_paths.iter() {
-        let path = PathBuf::from(path_str);
-        if path.exists() {
-            println!("Found file at exact path: {}", path.display());
-            return Some(path);
-        }
-    }
-    
-    // Try to find the file recursively
-    for dir in search_dirs.iter() {
-        let dir_path = PathBuf::from(dir);
-        if !dir_path.exists() || !dir_path.is_dir() {
-            continue;
-        }
-        
-        // Try to find file directly in this directory
-        let file_path = dir_path.join(filename);
-        if file_path.exists() {
-            println!("Found file in directory: {}", file_path.display());
-            return Some(file_path);
-        }
-        
-        // Try to recursively search (with depth limit)
-        if let Ok(entries) = fs::read_dir(&dir_path) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_dir() {
-                    // Check one level down
-                    let nested_file = path.join(filename);
-                    if nested_file.exists() {
-                        println!("Found file in subdirectory: {}", nested_file.display());
-                        return Some(nested_file);
-                    }
-                }
-            }
+    }
+}
+
+fn main() -> ExitCode {
+    let matches = Cli::command().get_matches();
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    
+    // RUST_LOG, if set, takes precedence over -v
+    let level = match cli.verbose {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Info,
+        2 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    };
+    env_logger::Builder::new()
+        .filter_level(level)
+        .parse_default_env()
+        .init();
+    
+    // Command line flags override the config file, which overrides defaults
+    match Config::load(cli.config.as_deref()) {
+        Ok(Some(config)) => cli.apply_config(&config, &matches),
+        Ok(None) => {}
+        Err(e) => {
+            println!("❌ {}", e);
+            return ExitCode::from(2);
         }
     }
+    PROGRESS.store(cli.progress && io::stderr().is_terminal(), Ordering::Relaxed);
     
-    None
-}
-
-fn main() -> io::Result<()> {
-    println!("🔍 Searching for R1CS file...");
-    
-    // Try to find the multiplexer.r1cs file
-    let r1cs_filename = "multiplexer.r1cs";
-    let r1cs_path = match find_file(r1cs_filename) {
-        Some(path) => path,
-        None => {
-            // Also try Decoder@multiplexer.r1cs
-            let alt_filename = "Decoder@multiplexer.r1cs";
-            match find_file(alt_filename) {
-                Some(path) => path,
-                None => {
-                    println!("❌ Could not find R1CS file. Looked for:");
-                    println!("   - {}", r1cs_filename);
-                    println!("   - {}", alt_filename);
-                    println!("Please place an R1CS file in one of the search directories.");
-                    
-                    // Return a descriptive error
-                    return Err(io::Error::new(
-                        io::ErrorKind::NotFound,
-                        "R1CS file not found in any of the search locations"
-                    ));
-                }
-            }
+    let run = || match &cli.command {
+        Some(command) => run_command(command, &cli.read_options(), cli.endianness.map(Into::into)),
+        None => run_workflow(&cli),
+    };
+    // Everything rayon runs, arkworks included, goes through this pool
+    let result = match cli.threads {
+        Some(threads) => rayon::ThreadPoolBuilder::new()
+            .num_threads(threads as usize)
+            .build()
+            .map_err(io::Error::other)
+            .and_then(|pool| pool.install(run)),
+        None => run(),
+    };
+    if cli.timings {
+        print_timings();
+    }
+    
+    // 0: success, 1: a proof or witness was rejected, 2: anything else went wrong
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) if e.get_ref().is_some_and(|inner| inner.is::<VerificationFailed>() || inner.is::<WitnessUnsatisfied>()) => {
+            ExitCode::from(1)
+        }
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+// Run setup, prove and verify in one go, as without a subcommand
+fn run_workflow(cli: &Cli) -> io::Result<()> {
+    if cli.verify_only {
+        return verify_saved(cli);
+    }
+    
+    // `--bundle <dir>` takes the circuit from a circom artifact bundle
+    let bundle = match &cli.bundle {
+        Some(dir) => {
+            let bundle = bundle::Bundle::discover(dir)?;
+            bundle.print_info();
+            Some(bundle)
         }
+        None => None,
+    };
+    
+    let r1cs_path = if let Some(path) = &cli.r1cs {
+        if !path.is_file() {
+            println!("❌ R1CS file does not exist: {}", path.display());
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("R1CS file not found: {}", path.display())
+            ));
+        }
+        path.clone()
+    } else if let Some(bundle) = &bundle {
+        bundle.r1cs.clone()
+    } else if cli.search {
+        search_r1cs_file(&cli.search_root, &cli.search_pattern, cli.max_depth)?
+    } else {
+        println!("❌ No R1CS file given. Use --r1cs <path>, --bundle <dir>, or --search.");
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "no R1CS file given"
+        ));
     };
     
     println!("📂 Using R1CS file: {}", r1cs_path.display());
+    let curve = select_curve(&r1cs_path, cli.curve, &cli.read_options())?;
     
-    // Parse the R1CS file
-    let r1cs = match r1cs::R1CS::read(&r1cs_path) {
+    match curve {
+        Curve::Bn254 => run::<Bn254>(cli, &r1cs_path, bundle.as_ref()),
+        Curve::Bls12_381 => run::<Bls12_381>(cli, &r1cs_path, bundle.as_ref()),
+    }
+}
+
+// Pick the curve for an R1CS file (see `workflow::select_curve`), printing
+// why none fits
+fn select_curve(r1cs_path: &Path, requested: Option<Curve>, options: &r1cs::ReadOptions) -> io::Result<Curve> {
+    let curve = match workflow::select_curve(r1cs_path, requested.map(Into::into), options) {
+        Ok(curve) => Curve::from(curve),
+        Err(WorkflowError::R1CS(e)) => {
+            status!("❌ Failed to read R1CS file: {}", e);
+            print_r1cs_diagnostics(r1cs_path);
+            return Err(e.into());
+        }
+        Err(e) => {
+            status!("❌ {}", e);
+            return Err(e.into());
+        }
+    };
+    status!("🔧 Using curve: {}", curve);
+    Ok(curve)
+}
+
+// Parse an R1CS file into the scalar field `F` (see `workflow::load_r1cs`),
+// warning when --public-count overrides the header's count
+fn load_r1cs<F: PrimeField>(r1cs_path: &Path, options: &r1cs::ReadOptions) -> io::Result<r1cs::R1CS<F>> {
+    let r1cs = match timed("parse", || workflow::load_r1cs::<F, _>(r1cs_path, options)) {
         Ok(r1cs) => {
-            println!("✅ Successfully parsed R1CS file");
+            status!("✅ Successfully parsed R1CS file");
+            info!("Loaded {}", r1cs);
             r1cs
         },
         Err(e) => {
-            println!("❌ Failed to read R1CS file: {}", e);
-            
-            // If the file exists but parsing failed, print more detailed information
-            if r1cs_path.exists() {
-                if let Ok(metadata) = fs::metadata(&r1cs_path) {
-                    println!("   File exists and is {} bytes", metadata.len());
-                    
-                    // Try to read the first few bytes to check if it's a valid R1CS file
-                    if let Ok(mut file) = fs::File::open(&r1cs_path) {
-                        use std::io::Read;
-                        let mut buffer = [0; 8];
-                        if let Ok(n) = file.read(&mut buffer) {
-                            println!("   First {} bytes: {:?}", n, &buffer[..n]);
-                            
-                            // Check for r1cs magic number (first 4 bytes should be "r1cs" in ASCII)
-                            if n >= 4 && &buffer[0..4] == b"r1cs" {
-                                println!("   File has correct r1cs magic number");
-                            } else {
-                                println!("   File does NOT have correct r1cs magic number");
-                                println!("   Expected: [114, 49, 99, 115] (ASCII 'r1cs')");
-                            }
-                        }
-                    }
+            status!("❌ Failed to read R1CS file: {}", e);
+            print_r1cs_diagnostics(r1cs_path);
+            return Err(e.into());
+        }
+    };
+    
+    if let Some(count) = options.public_count {
+        // The parsed circuit only has the overridden count, so ask the header
+        let header = r1cs::R1CSHeader::read_with_options(r1cs_path, options)?;
+        let declared = header.n_pub_out + header.n_pub_in;
+        if count != declared {
+            status!("⚠️  --public-count {} overrides the {} public values declared in the R1CS header;", count, declared);
+            status!("⚠️  keys and proofs made this way only match others made with the same override");
+        }
+    }
+    Ok(r1cs)
+}
+
+// Stop before proving a circuit Groth16 can't prove, e.g. one using custom
+// gates, without loading its proving key first
+fn require_groth16<F: PrimeField>(r1cs: &r1cs::R1CS<F>) -> io::Result<()> {
+    r1cs.assert_groth16_compatible().map_err(|e| {
+        status!("❌ {}", e);
+        io::Error::from(e)
+    })
+}
+
+// Print why a workflow failed and turn the error into the command's
+// result. Constraints are shown with signal names where `symbols` has them.
+fn workflow_failure<F: PrimeField>(e: WorkflowError, r1cs: &r1cs::R1CS<F>, symbols: Option<&sym::SymbolTable>) -> io::Error {
+    let constraint = |idx: usize| {
+        let constraint = &r1cs.constraints()[idx];
+        match symbols {
+            Some(symbols) => constraint.display_with_symbols(symbols),
+            None => constraint.to_string(),
+        }
+    };
+    
+    match &e {
+        WorkflowError::Unsatisfied(idx) => println!("❌ Witness does not satisfy constraint #{}: {}", idx, constraint(*idx)),
+        WorkflowError::Synthesis { phase, error, diagnosis } => {
+            let what = match phase {
+                Phase::Setup => "generate Groth16 parameters",
+                Phase::Prove | Phase::Verify => "generate proof",
+            };
+            println!("❌ Failed to {}: {}", what, error);
+            println!("   Likely cause: {}", diagnosis);
+            if let SynthesisDiagnosis::UnsatisfiedConstraint(idx) | SynthesisDiagnosis::MissingAssignment { constraint: idx, .. } = diagnosis {
+                println!("   Constraint #{}: {}", idx, constraint(*idx));
+            }
+        }
+        WorkflowError::TooManyConstraints { num_constraints, .. } => {
+            println!("❌ {}", e);
+            println!("   Setup memory grows with the constraint count; if this machine can take it,");
+            println!("   raise the limit with --max-constraints {}", num_constraints);
+        }
+        WorkflowError::NoWitness => {
+            println!("❌ {}: pass --wtns <file>, or run the `setup` subcommand for the keys alone", e);
+        }
+        WorkflowError::NoPublicOutput => println!("❌ --expect-output needs a circuit with a public output"),
+        WorkflowError::OutputMismatch { actual, expected } => {
+            println!("❌ Public output is {}, but --expect-output expected {}", actual, expected);
+        }
+        WorkflowError::Solve(solve::SolveError::Unresolved(wires)) => {
+            println!("❌ {}", e);
+            for &wire in wires.iter().take(10) {
+                println!("  wire {}: {}", wire, symbols.and_then(|symbols| r1cs.wire_name(wire as u32, symbols)).unwrap_or("?"));
+            }
+        }
+        WorkflowError::TranslationBug(message) => {
+            println!("❌ BUG: {}; generate_constraints does not match the parsed constraints", message);
+        }
+        e => println!("❌ {}", e),
+    }
+    e.into()
+}
+
+// Prints the progress of a library workflow and times its phases for
+// --timings
+struct Status<'a, F: PrimeField> {
+    r1cs: &'a r1cs::R1CS<F>,
+}
+
+impl<F: PrimeField> Hooks<F> for Status<'_, F> {
+    fn phase<T, E>(&mut self, phase: Phase, run: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+        let result = match phase {
+            Phase::Setup => {
+                println!("\nRunning Groth16 setup...");
+                timed(phase.name(), || with_spinner("Running Groth16 setup", run))
+            }
+            Phase::Prove => {
+                println!("\nGenerating Groth16 proof...");
+                timed(phase.name(), run)
+            }
+            Phase::Verify => {
+                status!("\nVerifying proof...");
+                timed(phase.name(), run)
+            }
+        };
+        match (phase, &result) {
+            (Phase::Setup, Ok(_)) => println!("✅ Successfully generated Groth16 parameters"),
+            (Phase::Prove, Ok(_)) => println!("✅ Successfully generated proof"),
+            _ => {}
+        }
+        result
+    }
+    
+    fn event(&mut self, event: Event<'_, F>) {
+        match event {
+            Event::OutputMatches(value) => println!("✅ Public output matches the expected value {}", value.into_bigint()),
+            Event::WitnessSatisfied { num_constraints } => println!("✅ Witness satisfies all {} constraints", num_constraints),
+            Event::Evaluated { native, arkworks } => {
+                match native {
+                    Ok(()) => println!("✅ Witness satisfies all {} parsed constraints", self.r1cs.constraints().len()),
+                    Err(idx) => println!("⚠️  Witness does not satisfy parsed constraint #{}: {}", idx, self.r1cs.constraints()[idx]),
                 }
+                println!("{} arkworks constraint system is {}", if arkworks { "✅" } else { "⚠️ " },
+                         if arkworks { "satisfied" } else { "not satisfied" });
             }
-            
-            return Err(e);
         }
+    }
+}
+
+// Run a single workflow step
+fn run_command(command: &Command, options: &r1cs::ReadOptions, endianness: Option<Endianness>) -> io::Result<()> {
+    match command {
+        Command::Info { r1cs, curve } => {
+            match select_curve(r1cs, *curve, options)? {
+                Curve::Bn254 => info::<Bn254>(r1cs, options),
+                Curve::Bls12_381 => info::<Bls12_381>(r1cs, options),
+            }
+        }
+        Command::DumpJson { r1cs, out, stream, curve } => {
+            JSON_OUTPUT.store(out.is_none(), Ordering::Relaxed);
+            match select_curve(r1cs, *curve, options)? {
+                Curve::Bn254 => dump_json::<Bn254>(r1cs, out.as_deref(), *stream, options),
+                Curve::Bls12_381 => dump_json::<Bls12_381>(r1cs, out.as_deref(), *stream, options),
+            }
+        }
+        Command::Stats { r1cs, curve, json } => {
+            JSON_OUTPUT.store(*json, Ordering::Relaxed);
+            match select_curve(r1cs, *curve, options)? {
+                Curve::Bn254 => stats::<Bn254>(r1cs, *json, options),
+                Curve::Bls12_381 => stats::<Bls12_381>(r1cs, *json, options),
+            }
+        }
+        Command::Setup { r1cs, pk, vk, curve, setup_rng, setup_limits } => {
+            let rng = setup_rng.rng()?;
+            match select_curve(r1cs, *curve, options)? {
+                Curve::Bn254 => setup::<Bn254>(r1cs, pk, vk, rng, setup_limits, options),
+                Curve::Bls12_381 => setup::<Bls12_381>(r1cs, pk, vk, rng, setup_limits, options),
+            }
+        }
+        Command::Selfcheck { r1cs, wtns, curve, setup_rng, setup_limits } => {
+            let rng = setup_rng.rng()?;
+            match select_curve(r1cs, *curve, options)? {
+                Curve::Bn254 => selfcheck::<Bn254>(r1cs, wtns, rng, setup_limits, options),
+                Curve::Bls12_381 => selfcheck::<Bls12_381>(r1cs, wtns, rng, setup_limits, options),
+            }
+        }
+        Command::Demo { x, y, curve, setup_rng } => {
+            let rng = setup_rng.rng()?;
+            println!("🔧 Using curve: {}", curve);
+            match curve {
+                Curve::Bn254 => demo::<Bn254>(*x, *y, rng),
+                Curve::Bls12_381 => demo::<Bls12_381>(*x, *y, rng),
+            }
+        }
+        Command::Check { r1cs, wtns, curve } => {
+            match select_curve(r1cs, *curve, options)? {
+                Curve::Bn254 => check::<Bn254>(r1cs, wtns, options),
+                Curve::Bls12_381 => check::<Bls12_381>(r1cs, wtns, options),
+            }
+        }
+        Command::Prove { r1cs, pk, wtns, proof, public, curve } => {
+            match select_curve(r1cs, *curve, options)? {
+                Curve::Bn254 => prove::<Bn254>(r1cs, pk, wtns, proof, public, options, endianness),
+                Curve::Bls12_381 => prove::<Bls12_381>(r1cs, pk, wtns, proof, public, options, endianness),
+            }
+        }
+        Command::Verify { vk, public, proof, curve, hash, json } => {
+            JSON_OUTPUT.store(*json, Ordering::Relaxed);
+            status!("🔧 Using curve: {}", curve);
+            match curve {
+                Curve::Bn254 => verify::<Bn254>(vk, public, proof, (*hash).into(), *json, endianness),
+                Curve::Bls12_381 => verify::<Bls12_381>(vk, public, proof, (*hash).into(), *json, endianness),
+            }
+        }
+        Command::ExportSolidity { vk, out, curve } => export_solidity(vk, out, *curve),
+        Command::VerifySnarkjs { vk, proof, public } => verify_snarkjs(vk, proof, public),
+        Command::VerifyBatch { vk, proofs, public } => verify_batch(vk, proofs, public),
+    }
+}
+
+// Parse an R1CS file, which checks its wire ids and constraint count, and
+// print its summary. Whether Groth16 can prove the circuit is only a
+// warning here, since the file itself is valid either way.
+fn info<E: SnarkjsCurve>(r1cs_path: &Path, options: &r1cs::ReadOptions) -> io::Result<()> {
+    let r1cs = load_r1cs::<E::ScalarField>(r1cs_path, options)?;
+    r1cs.print_info();
+    println!("✅ Wire ids and constraint count are valid");
+    Ok(())
+}
+
+// Parse an R1CS file and print its constraint statistics
+fn stats<E: SnarkjsCurve>(r1cs_path: &Path, json: bool, options: &r1cs::ReadOptions) -> io::Result<()> {
+    let r1cs = load_r1cs::<E::ScalarField>(r1cs_path, options)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&r1cs.stats())?);
+    } else {
+        r1cs.print_stats();
+    }
+    Ok(())
+}
+
+// Write an R1CS file as JSON to a file or stdout
+fn dump_json<E: SnarkjsCurve>(
+    r1cs_path: &Path,
+    out: Option<&Path>,
+    stream: bool,
+    options: &r1cs::ReadOptions,
+) -> io::Result<()> {
+    let mut writer: Box<dyn Write> = match out {
+        Some(path) => Box::new(BufWriter::new(fs::File::create(path)?)),
+        None => Box::new(BufWriter::new(io::stdout().lock())),
     };
     
-    // Print detailed R1CS information
+    if stream {
+        r1cs::R1CS::<E::ScalarField>::constraints_iter(r1cs_path)
+            .and_then(|constraints| constraints.write_json(writer))
+            .map_err(|e| {
+                status!("❌ Failed to dump R1CS file: {}", e);
+                io::Error::from(e)
+            })?;
+    } else {
+        let r1cs = load_r1cs::<E::ScalarField>(r1cs_path, options)?;
+        serde_json::to_writer_pretty(&mut writer, &r1cs.to_json())?;
+        writeln!(writer)?;
+        writer.flush()?;
+    }
+    
+    if let Some(path) = out {
+        status!("✅ R1CS written as JSON to {}", path.display());
+    }
+    Ok(())
+}
+
+// Generate Groth16 keys for an R1CS file and save them
+fn setup<E: SnarkjsCurve>(
+    r1cs_path: &Path,
+    pk_path: &Path,
+    vk_path: &Path,
+    mut rng: StdRng,
+    limits: &SetupLimits,
+    options: &r1cs::ReadOptions,
+) -> io::Result<()> {
+    let r1cs = load_r1cs::<E::ScalarField>(r1cs_path, options)?;
     r1cs.print_info();
+    let params = workflow::setup::<E, _, _>(&r1cs, limits.max_constraints, &mut rng, &mut Status { r1cs: &r1cs })
+        .map_err(|e| workflow_failure(e, &r1cs, None))?;
     
-    // Create circuit from R1CS
-    println!("\nCreating circuit from R1CS...");
-    let circuit = CircuitFromR1CS::new(r1cs);
-    
-    // Generate Groth16 parameters
-    println!("\nRunning Groth16 setup...");
-    let mut rng = StdRng::seed_from_u64(123456789);
-    
-    let params = match Groth16::<Bls12_381>::generate_random_parameters_with_reduction(
-        circuit,
-        &mut rng,
-    ) {
-        Ok(params) => {
-            println!("✅ Successfully generated Groth16 parameters");
-            params
-        },
-        Err(e) => {
-            println!("❌ Failed to generate Groth16 parameters: {}", e);
-            return Err(io::Error::new(io::ErrorKind::Other, format!("{}", e)));
+    artifacts::save_proving_key(&params, pk_path)?;
+    artifacts::save_verifying_key(&params.vk, vk_path)?;
+    println!("💾 Saved proving key to {} and verifying key to {}", pk_path.display(), vk_path.display());
+    
+    Ok(())
+}
+
+// Cross-check a witness with `workflow::selfcheck`: the parsed constraints,
+// arkworks' constraint system and a fresh proof must agree
+fn selfcheck<E: SnarkjsCurve>(
+    r1cs_path: &Path,
+    wtns_path: &Path,
+    mut rng: StdRng,
+    limits: &SetupLimits,
+    options: &r1cs::ReadOptions,
+) -> io::Result<()> {
+    let r1cs = load_r1cs::<E::ScalarField>(r1cs_path, options)?;
+    let witness = workflow::load_witness(wtns_path, &r1cs).map_err(|e| workflow_failure(e, &r1cs, None))?;
+    
+    match workflow::selfcheck::<E, _, _>(&r1cs, witness, limits.max_constraints, &mut rng, &mut Status { r1cs: &r1cs }) {
+        Ok(()) => {
+            println!("✅ Self-check passed: the constraints, arkworks and the proof agree");
+            Ok(())
+        }
+        Err(WorkflowError::Unsatisfied(idx)) => {
+            println!("❌ The witness is invalid (constraint #{}); both evaluators agree", idx);
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("witness does not satisfy constraint #{}", idx)
+            ))
         }
+        Err(e) => Err(workflow_failure(e, &r1cs, None)),
+    }
+}
+
+// Check a witness against the parsed constraints natively, printing the
+// first constraint it fails with the values of its three sides. Nothing
+// here is Groth16, so circuits only a Groth16 setup rejects, e.g. with
+// custom gates or no constraints, can still be checked.
+fn check<E: SnarkjsCurve>(r1cs_path: &Path, wtns_path: &Path, options: &r1cs::ReadOptions) -> io::Result<()> {
+    let r1cs = load_r1cs::<E::ScalarField>(r1cs_path, options)?;
+    let witness = workflow::load_witness(wtns_path, &r1cs).map_err(|e| workflow_failure(e, &r1cs, None))?;
+    witness.assert_fits(&r1cs).map_err(|e| {
+        println!("❌ {}", e);
+        io::Error::from(e)
+    })?;
+
+    let idx = match timed("check", || r1cs.check_witness(&witness.values)) {
+        Ok(()) => {
+            println!("✅ Witness satisfies all {} constraints", r1cs.constraints().len());
+            return Ok(());
+        }
+        Err(idx) => idx,
+    };
+    let constraint = &r1cs.constraints()[idx];
+    println!("❌ Witness does not satisfy constraint #{}: {}", idx, constraint);
+    if let Some((a, b, c)) = constraint.evaluate(&witness.values) {
+        println!("  A·w = {}", a.into_bigint());
+        println!("  B·w = {}", b.into_bigint());
+        println!("  C·w = {}", c.into_bigint());
+        println!("  (A·w)(B·w) = {}", (a * b).into_bigint());
+    }
+    Err(io::Error::other(WitnessUnsatisfied(idx)))
+}
+
+// Run parse -> witness -> setup -> check -> prove -> verify on the built-in
+// `x * y = z` circuit. The circuit is written out and parsed back, so the
+// demo goes through the same parser as a circom file.
+fn demo<E: SnarkjsCurve>(x: u64, y: u64, mut rng: StdRng) -> io::Result<()> {
+    let mut bytes = Vec::new();
+    r1cs::create_hardcoded_r1cs::<E::ScalarField>()?.write_to(&mut bytes)?;
+    let r1cs = r1cs::R1CS::<E::ScalarField>::from_reader(io::Cursor::new(bytes))?;
+    r1cs.print_info();
+    
+    let values = r1cs::hardcoded_witness::<E::ScalarField>(x, y);
+    println!("\n🧮 Witness [1, z, x, y] = [1, {}, {}, {}]", values[1].into_bigint(), x, y);
+    let witness = witness::Witness {
+        field_size: r1cs.header().field_size,
+        prime_bytes: r1cs.header().prime_bytes.clone(),
+        values,
     };
     
-    // We need to read the R1CS file again for proof generation
-    let r1cs = r1cs::R1CS::read(&r1cs_path)?;
-    let circuit_for_proving = CircuitFromR1CS::new(r1cs);
+    let mut hooks = Status { r1cs: &r1cs };
+    let fail = |e| workflow_failure(e, &r1cs, None);
+    let output = workflow::run::<E, _, _>(&r1cs, Some(witness), u32::MAX, None, &mut rng, &mut hooks).map_err(fail)?;
+    println!("\nProof is of z = {}", output.public_inputs[0].into_bigint());
+    verification_outcome(output.verify(&mut hooks).map_err(io::Error::from))
+}
+
+// Prove with a saved proving key, saving the proof and its public inputs
+fn prove<E: SnarkjsCurve>(
+    r1cs_path: &Path,
+    pk_path: &Path,
+    wtns_path: &Path,
+    proof_path: &Path,
+    public_path: &Path,
+    options: &r1cs::ReadOptions,
+    endianness: Option<Endianness>,
+) -> io::Result<()> {
+    let r1cs = load_r1cs::<E::ScalarField>(r1cs_path, options)?;
+    require_groth16(&r1cs)?;
+    let fail = |e| workflow_failure(e, &r1cs, None);
+    let witness = workflow::load_witness(wtns_path, &r1cs).map_err(fail)?;
+    let pk = artifacts::load_proving_key::<E, _>(pk_path)?;
+    let (proof, public_inputs) = workflow::prove(&r1cs, witness, &pk, &mut Status { r1cs: &r1cs }).map_err(fail)?;
+    
+    artifacts::save_proof(&proof, proof_path)?;
+    write_json(public_path, &public_inputs_json(&public_inputs, endianness))?;
+    println!("💾 Saved proof to {} and public inputs to {}", proof_path.display(), public_path.display());
     
-    // Get public inputs for verification
-    let public_inputs = circuit_for_proving.get_public_inputs();
-    println!("\nPublic inputs for verification: {} values", public_inputs.len());
-    for (i, input) in public_inputs.iter().enumerate() {
-        println!("  Public input #{}: {:?}", i, input);
+    Ok(())
+}
+
+// public.json contents: snarkjs' decimal strings, or hex words in the
+// `--endianness` byte order
+fn public_inputs_json<F: PrimeField>(inputs: &[F], endianness: Option<Endianness>) -> serde_json::Value {
+    match endianness {
+        Some(endianness) => snarkjs_export::public_inputs_to_hex_json(inputs, endianness),
+        None => snarkjs_export::public_inputs_to_json(inputs),
     }
+}
+
+// Verify a saved proof against a saved verifying key and public inputs.
+// With `json`, the result goes to stdout as `{"verified", "public_inputs",
+// "error"}`, and errors loading the inputs are reported there too.
+fn verify<E: SnarkjsCurve>(
+    vk_path: &Path,
+    public: &[PublicInput],
+    proof_path: &Path,
+    hash: HashFunction,
+    json: bool,
+    endianness: Option<Endianness>,
+) -> io::Result<()> {
+    let mut public_inputs = Vec::new();
+    let outcome = (|| {
+        let vk = artifacts::load_verifying_key::<E, _>(vk_path)?;
+        let proof = artifacts::load_proof::<E, _>(proof_path)?;
+        let num_public = vk.gamma_abc_g1.len().saturating_sub(1);
+        // Hex values are big-endian (a plain number) unless --endianness says otherwise
+        public_inputs = workflow::resolve_public_inputs(public, num_public, endianness.unwrap_or(Endianness::Big))
+            .map_err(|e| {
+                status!("❌ {}", e);
+                io::Error::from(e)
+            })?;
+        let digest = hash_public_inputs(&public_inputs, hash);
+        status!("🔒 Public input hash ({}): 0x{}", hash, hex::encode(digest));
+        
+        status!("\nVerifying proof...");
+        timed("verify", || groth16::verify(&vk, &public_inputs, &proof)).map_err(io::Error::other)
+    })();
     
-    // Generate proof
-    println!("\nGenerating Groth16 proof...");
-    let proof = match Groth16::<Bls12_381>::prove(&params, circuit_for_proving, &mut rng) {
-        Ok(proof) => {
-            println!("✅ Successfully generated proof");
-            proof
-        },
+    if json {
+        let report = serde_json::json!({
+            "verified": matches!(outcome, Ok(true)),
+            "public_inputs": public_inputs_json(&public_inputs, endianness),
+            "error": outcome.as_ref().err().map(|e| e.to_string()),
+        });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    }
+    verification_outcome(outcome)
+}
+
+// Report a verification result, turning a rejected proof into a
+// `VerificationFailed` error so the process exits with status 1
+fn verification_outcome(outcome: io::Result<bool>) -> io::Result<()> {
+    match outcome {
+        Ok(true) => {
+            status!("✅ Proof verified successfully!");
+            Ok(())
+        }
+        Ok(false) => {
+            status!("❌ Proof verification failed!");
+            Err(io::Error::other(VerificationFailed))
+        }
         Err(e) => {
-            println!("❌ Failed to generate proof: {}", e);
-            return Err(io::Error::new(io::ErrorKind::Other, format!("{}", e)));
+            status!("❌ Error during verification: {}", e);
+            Err(e)
         }
+    }
+}
+
+// Write a Solidity verifier contract for a saved bn254 verifying key
+fn export_solidity(vk_path: &Path, out: &Path, curve: Curve) -> io::Result<()> {
+    if curve != Curve::Bn254 {
+        println!("❌ Solidity verifiers need --curve bn254: the EVM only has pairing precompiles for bn254");
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("cannot export a Solidity verifier for {}", curve)
+        ));
+    }
+    
+    let vk = artifacts::load_verifying_key::<Bn254, _>(vk_path)?;
+    fs::write(out, solidity::export_solidity_verifier(&vk))?;
+    println!("✅ Solidity verifier written to {}", out.display());
+    Ok(())
+}
+
+// Verify one proof from snarkjs JSON files, e.g. ones snarkjs itself produced
+fn verify_snarkjs(vk_path: &Path, proof_path: &Path, public_path: &Path) -> io::Result<()> {
+    println!("\nVerifying proof...");
+    let outcome = timed("verify", || snarkjs_import::verify_from_snarkjs_artifacts(vk_path, public_path, proof_path));
+    verification_outcome(outcome.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string())))
+}
+
+// Verify every proof in a directory of snarkjs JSON files in one batch
+fn verify_batch(vk_path: &Path, proof_dir: &Path, public_dir: &Path) -> io::Result<()> {
+    let import_error = |path: &Path, e: snarkjs_import::ImportError| {
+        println!("❌ Failed to import {}: {}", path.display(), e);
+        io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), e))
     };
     
-    // Verify proof locally
-    println!("\nVerifying proof locally...");
-    let pvk = prepare_verifying_key(&params.vk);
+    let vk = snarkjs_import::vk_from_json(&read_json(vk_path)?).map_err(|e| import_error(vk_path, e))?;
+    let pairs = batch::pair_json_files(proof_dir, public_dir)?;
+    let mut proofs = Vec::with_capacity(pairs.len());
+    let mut inputs = Vec::with_capacity(pairs.len());
+    for (proof_path, public_path) in &pairs {
+        proofs.push(snarkjs_import::proof_from_json(&read_json(proof_path)?)
+            .map_err(|e| import_error(proof_path, e))?);
+        inputs.push(snarkjs_import::public_inputs_from_json(&read_json(public_path)?)
+            .map_err(|e| import_error(public_path, e))?);
+    }
+    
+    println!("\nVerifying {} proofs...", proofs.len());
+    let pvk = ark_groth16::prepare_verifying_key(&vk);
+    let results = match timed("verify", || batch::verify_batch(&pvk, &inputs, &proofs)) {
+        Ok(results) => results,
+        Err(e) => {
+            println!("❌ Error during verification: {}", e);
+            return Err(io::Error::other(e));
+        }
+    };
     
-    match Groth16::<Bls12_381>::verify_with_processed_vk(&pvk, &public_inputs, &proof) {
-        Ok(true) => println!("✅ Proof verified successfully!"),
-        Ok(false) => println!("❌ Proof verification failed!"),
-        Err(e) => println!("❌ Error during verification: {}", e),
+    for ((proof_path, _), valid) in pairs.iter().zip(&results) {
+        let mark = if *valid { "✅" } else { "❌" };
+        println!("{} {}", mark, proof_path.display());
+    }
+    let num_valid = results.iter().filter(|valid| **valid).count();
+    println!("{} of {} proofs verified", num_valid, results.len());
+    
+    if num_valid < results.len() {
+        return Err(io::Error::other(VerificationFailed));
+    }
+    Ok(())
+}
+
+// Read a JSON file
+fn read_json(path: &Path) -> io::Result<serde_json::Value> {
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+// Set up, prove and verify the circuit over the pairing engine `E`
+fn run<E: SnarkjsCurve>(cli: &Cli, r1cs_path: &Path, bundle: Option<&bundle::Bundle>) -> io::Result<()> {
+    // `--sym <file>` names the signals; otherwise use the bundle's, if any
+    let sym_path = cli.sym.clone().or_else(|| bundle.and_then(|b| b.sym.clone()));
+    let symbols = match &sym_path {
+        Some(path) => Some(sym::SymbolTable::read(path)?),
+        None => None,
+    };
+    
+    // Parse the R1CS file and print detailed information
+    let r1cs = load_r1cs::<E::ScalarField>(r1cs_path, &cli.read_options())?;
+    r1cs.print_info();
+    let fail = |e| workflow_failure(e, &r1cs, symbols.as_ref());
+    
+    // `--wtns <file>` supplies the witness; otherwise use the bundle's, if any
+    let witness_path = cli.wtns.clone().or_else(|| bundle.and_then(|b| b.witness.clone()));
+    let witness = match &witness_path {
+        Some(path) => Some(workflow::load_witness(path, &r1cs).map_err(fail)?),
+        None => None,
+    };
+    
+    // A bundle without a witness can have one solved from its input.json
+    let input_path = bundle.and_then(|b| b.input.as_deref());
+    let witness = match (witness, input_path, &symbols) {
+        (None, Some(input), Some(symbols)) => {
+            println!("\nSolving witness from {}...", input.display());
+            let witness = workflow::solve_witness(&r1cs, input, symbols).map_err(fail)?;
+            println!("✅ Solved all {} wires", witness.values.len());
+            // Keep the solved witness for other tools, e.g. snarkjs
+            if let Some(out_dir) = &cli.out_dir {
+                fs::create_dir_all(out_dir)?;
+                let path = out_dir.join("witness.wtns");
+                witness.write(&path)?;
+                println!("💾 Saved solved witness to {}", path.display());
+            }
+            Some(witness)
+        }
+        (None, Some(input), None) => {
+            println!("❌ Solving {} needs the circuit's .sym file; add it, or a .wtns witness, to the bundle", input.display());
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "bundle has input.json but no .sym file to solve it with"));
+        }
+        (witness, _, _) => witness,
+    };
+    
+    let expected_output = match &cli.expect_output {
+        Some(text) => Some(witness::parse_field_element::<E::ScalarField>(text).ok_or_else(|| {
+            println!("❌ --expect-output {} is not a decimal or 0x-hex field element", text);
+            io::Error::new(io::ErrorKind::InvalidInput, format!("invalid --expect-output {}", text))
+        })?),
+        None => None,
+    };
+    
+    // Set up and prove; a wrong --expect-output stops the run before setup
+    let mut rng = cli.setup_rng.rng()?;
+    println!("\nCreating circuit from R1CS...");
+    let mut hooks = Status { r1cs: &r1cs };
+    let max_constraints = cli.setup_limits.max_constraints;
+    let output = workflow::run::<E, _, _>(&r1cs, witness, max_constraints, expected_output.as_ref(), &mut rng, &mut hooks)
+        .map_err(fail)?;
+    
+    println!("\nPublic inputs for verification: {} values", output.public_inputs.len());
+    for (i, input) in output.public_inputs.iter().enumerate() {
+        println!("  Public input #{}: {:?}", i, input);
+    }
+    
+    // Save the keys and proof so they can be reused without redoing setup
+    let public_json = public_inputs_json(&output.public_inputs, cli.endianness.map(Into::into));
+    if let Some(out_dir) = &cli.out_dir {
+        fs::create_dir_all(out_dir)?;
+        artifacts::save_proving_key(&output.pk, out_dir.join("proving_key.bin"))?;
+        artifacts::save_verifying_key(&output.pk.vk, out_dir.join("verifying_key.bin"))?;
+        artifacts::save_proof(&output.proof, out_dir.join("proof.bin"))?;
+        write_json(&out_dir.join("verification_key.json"), &snarkjs_export::vk_to_json(&output.pk.vk))?;
+        write_json(&out_dir.join("proof.json"), &snarkjs_export::proof_to_json(&output.proof))?;
+        write_json(&out_dir.join("public.json"), &public_json)?;
+        println!("💾 Saved proving key, verifying key and proof to {}", out_dir.display());
+    } else if let Some(bundle) = bundle {
+        write_json(&bundle.dir.join("proof.json"), &snarkjs_export::proof_to_json(&output.proof))?;
+        write_json(&bundle.dir.join("public.json"), &public_json)?;
+        println!("💾 Saved proof.json and public.json to {}", bundle.dir.display());
+    }
+    
+    // Verify proof locally
+    if cli.no_verify {
+        println!("\n⏭️  Skipping local verification (--no-verify)");
+    } else {
+        verification_outcome(output.verify(&mut hooks).map_err(io::Error::from))?;
     }
     
     println!("\nR1CS processing complete!");
     
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,38 @@
+use std::fmt;
+use ark_ff::{BigInteger, PrimeField};
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+
+/// Hash function for [`hash_public_inputs`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HashFunction {
+    /// SHA-256
+    #[default]
+    Sha256,
+    /// Keccak-256 as the EVM's `keccak256` computes it (not NIST SHA3-256)
+    Keccak256,
+}
+
+impl fmt::Display for HashFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HashFunction::Sha256 => write!(f, "sha256"),
+            HashFunction::Keccak256 => write!(f, "keccak256"),
+        }
+    }
+}
+
+/// Hash a proof's public inputs, for verifiers that commit to them.
+///
+/// The hash is taken over the concatenation of each input as a canonical
+/// (fully reduced) little-endian integer of the field's full byte width,
+/// 32 bytes for both bn254 and bls12-381, in public input order. This is
+/// the same encoding arkworks' `serialize_compressed` uses for a field
+/// element. No inputs hash the empty string.
+pub fn hash_public_inputs<F: PrimeField>(inputs: &[F], function: HashFunction) -> [u8; 32] {
+    let bytes: Vec<u8> = inputs.iter().flat_map(|input| input.into_bigint().to_bytes_le()).collect();
+    match function {
+        HashFunction::Sha256 => Sha256::digest(&bytes).into(),
+        HashFunction::Keccak256 => Keccak256::digest(&bytes).into(),
+    }
+}
@@ -1,20 +1,388 @@
-use std::fs::File;
+use std::collections::BTreeMap;
 use std::path::Path;
-use std::io::{self, Read, Seek, SeekFrom};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::error::Error;
 use std::fmt;
-use byteorder::{LittleEndian, ReadBytesExt};
+use std::marker::PhantomData;
+use std::ops::Index;
+use bincode::Options;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use indicatif::{ProgressBar, ProgressStyle};
+use log::{debug, info, warn};
+use num_bigint::BigUint;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
 use ark_bls12_381::Fr;
-use ark_ff::PrimeField;
+use ark_ff::{BigInteger, PrimeField};
+use ark_relations::r1cs::{ConstraintMatrices, ConstraintSystemRef};
+use crate::cache;
+use crate::compression::{self, Source};
+use crate::sym::SymbolTable;
 
-/// Wrapper for R1CS file data with additional utility methods
-pub struct R1CS {
+/// Largest field element size (in bytes) accepted in a header
+const MAX_FIELD_SIZE: u32 = 64;
+
+/// Errors that can occur while parsing an R1CS file
+#[derive(Debug)]
+pub enum R1CSError {
+    /// The file does not start with the `r1cs` magic bytes
+    BadMagic,
+    /// The file ends before the 12-byte magic, version and section count
+    TruncatedPreamble(usize),
+    /// The file format version is not supported
+    UnsupportedVersion(u32),
+    /// A section declares more bytes than the file contains
+    TruncatedSection { section_type: u32, expected: u64, got: u64 },
+    /// A field element has a different length than the header's field size
+    FieldSizeMismatch { expected: u32, got: usize },
+    /// The header declares a field size that isn't a sane byte count
+    InvalidFieldSize(u32),
+    /// The header declares fewer wires than ONE plus its public and private inputs
+    InconsistentHeader { n_wires: u32, n_pub_out: u32, n_pub_in: u32, n_prvt_in: u32 },
+    /// The header section is missing, or a section that depends on it came first
+    MissingHeader,
+    /// A witness file does not start with the `wtns` magic bytes
+    BadWitnessMagic,
+    /// The witness file's prime isn't the R1CS prime, so it was computed
+    /// over another curve (both little-endian, without zero padding)
+    WitnessCurveMismatch { r1cs: Vec<u8>, witness: Vec<u8> },
+    /// Parsing a section consumed a different number of bytes than it declares
+    SectionSizeMismatch { section_type: u32, declared: u64, consumed: u64 },
+    /// The constraints section holds a different number of constraints than the header declares
+    ConstraintCountMismatch { declared: u32, parsed: usize },
+    /// A constraint term refers to a wire the circuit doesn't have
+    WireIndexOutOfRange { constraint: usize, matrix: Matrix, wire: u32, num_wires: u32 },
+    /// A coefficient's bytes encode a value of at least the field prime,
+    /// rejected when reading with [`ReadOptions::strict`]
+    UnreducedCoefficient { constraint: usize, wire: u32 },
+    /// A linear combination declares more terms than the circuit has wires,
+    /// which only a corrupted file can do
+    ImplausibleTermCount { terms: u32, num_wires: u32 },
+    /// The wire map section doesn't have one entry per wire
+    WireMapSizeMismatch { expected: u32, got: u64 },
+    /// The wire map gives a wire a label id past the header's label count
+    LabelOutOfRange { wire: u32, label: u64, n_labels: u64 },
+    /// A public count override leaves no room for the ONE wire in the circuit
+    PublicCountOutOfRange { requested: u32, num_wires: u32 },
+    /// The header's prime is not the modulus of the field being decoded into
+    /// (both little-endian, without zero padding)
+    ModulusMismatch { expected: Vec<u8>, got: Vec<u8> },
+    /// A line of a `.sym` file isn't `labelId,varIdx,componentId,name`
+    MalformedSymbolLine(usize),
+    /// The circuit uses custom gates, which Groth16 can't represent
+    CustomGatesUnsupported,
+    /// The circuit has no constraints, so a proof for it would accept any
+    /// public inputs
+    NoConstraints,
+    /// A compressed file expands beyond the size limit (in bytes)
+    DecompressedTooLarge(u64),
+    /// A file passed to [`R1CS::load_parsed`] isn't a dump written by
+    /// [`R1CS::save_parsed`], or is from another version of its format
+    BadParsedMagic,
+    /// A parsed dump was saved from a different version of its R1CS file
+    StaleParsedDump,
+    /// A public input is neither a decimal nor a `0x` hex field element
+    InvalidPublicInput { index: usize, value: String },
+    /// An `input.json` signal has no entry in the symbol file
+    UnknownSignal(String),
+    /// An `input.json` value is neither a decimal nor a `0x` hex field element
+    InvalidInputValue { signal: String, value: String },
+    /// A JSON file (e.g. `public.json`) could not be parsed
+    Json(serde_json::Error),
+    /// An underlying I/O error
+    Io(io::Error),
+}
+
+impl fmt::Display for R1CSError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            R1CSError::BadMagic => write!(f, "Invalid R1CS file: wrong magic bytes"),
+            R1CSError::TruncatedPreamble(got) => write!(
+                f,
+                "Invalid R1CS file: only {} bytes, too short for the 12-byte preamble",
+                got
+            ),
+            R1CSError::UnsupportedVersion(version) => write!(f, "Unsupported R1CS version: {}", version),
+            R1CSError::TruncatedSection { section_type, expected, got } => write!(
+                f,
+                "Section type {} is truncated: expected {} bytes, got {}",
+                section_type, expected, got
+            ),
+            R1CSError::SectionSizeMismatch { section_type, declared, consumed } => write!(
+                f,
+                "Section type {} declares {} bytes, but parsing it consumed {}",
+                section_type, declared, consumed
+            ),
+            R1CSError::ConstraintCountMismatch { declared, parsed } => write!(
+                f,
+                "Header declares {} constraints, but the constraints section holds {}",
+                declared, parsed
+            ),
+            R1CSError::WireIndexOutOfRange { constraint, matrix, wire, num_wires } => write!(
+                f,
+                "Constraint #{} refers to wire {} in {}, but the circuit has {} wires",
+                constraint, wire, matrix, num_wires
+            ),
+            R1CSError::UnreducedCoefficient { constraint, wire } => write!(
+                f,
+                "Constraint #{} has a coefficient for wire {} that is not reduced modulo the field prime",
+                constraint, wire
+            ),
+            R1CSError::ImplausibleTermCount { terms, num_wires } => write!(
+                f,
+                "Linear combination declares {} terms, but the circuit only has {} wires",
+                terms, num_wires
+            ),
+            R1CSError::FieldSizeMismatch { expected, got } => write!(
+                f,
+                "Field element is {} bytes, expected {}",
+                got, expected
+            ),
+            R1CSError::InvalidFieldSize(size) => write!(
+                f,
+                "Invalid field size {}: expected a multiple of 8 bytes up to {}",
+                size, MAX_FIELD_SIZE
+            ),
+            R1CSError::InconsistentHeader { n_wires, n_pub_out, n_pub_in, n_prvt_in } => write!(
+                f,
+                "Header declares {} wires, but ONE + {} public outputs + {} public inputs + {} private inputs needs {}; \
+                 the header may be read with the wrong field size",
+                n_wires, n_pub_out, n_pub_in, n_prvt_in,
+                1 + *n_pub_out as u64 + *n_pub_in as u64 + *n_prvt_in as u64
+            ),
+            R1CSError::MissingHeader => write!(f, "Header section is missing or appears after a section that depends on it"),
+            R1CSError::BadWitnessMagic => write!(f, "Invalid witness file: wrong magic bytes"),
+            R1CSError::WireMapSizeMismatch { expected, got } => write!(
+                f,
+                "Wire map has {} entries, but the header declares {} wires",
+                got, expected
+            ),
+            R1CSError::LabelOutOfRange { wire, label, n_labels } => write!(
+                f,
+                "Wire {} has label {}, but the header declares only {} labels",
+                wire, label, n_labels
+            ),
+            R1CSError::PublicCountOutOfRange { requested, num_wires } => write!(
+                f,
+                "Can't treat {} wires as public: the circuit has only {} wires besides ONE",
+                requested, num_wires.saturating_sub(1)
+            ),
+            R1CSError::ModulusMismatch { expected, got } => write!(
+                f,
+                "R1CS prime 0x{} does not match the field modulus 0x{}",
+                to_hex(got), to_hex(expected)
+            ),
+            R1CSError::WitnessCurveMismatch { r1cs, witness } => write!(
+                f,
+                "Witness prime 0x{} does not match the R1CS prime 0x{}; the witness is for another curve",
+                to_hex(witness), to_hex(r1cs)
+            ),
+            R1CSError::MalformedSymbolLine(line) => write!(f, "Malformed symbol file line {}", line),
+            R1CSError::NoConstraints => write!(
+                f,
+                "The circuit has no constraints, so a proof would accept any public inputs"
+            ),
+            R1CSError::CustomGatesUnsupported => write!(
+                f,
+                "Circuit uses custom gates, which Groth16 can't prove; recompile it with circom --O1 or --O2 without custom templates"
+            ),
+            R1CSError::DecompressedTooLarge(limit) => write!(f, "Decompressed file exceeds {} bytes", limit),
+            R1CSError::BadParsedMagic => write!(f, "Not a parsed R1CS dump, or one from another version of this tool"),
+            R1CSError::StaleParsedDump => write!(f, "Parsed R1CS dump is stale: the R1CS file has changed since it was saved"),
+            R1CSError::InvalidPublicInput { index, value } => write!(
+                f,
+                "Public input #{} is not a valid field element: {:?}",
+                index, value
+            ),
+            R1CSError::UnknownSignal(name) => write!(f, "Input signal {} is not in the symbol file", name),
+            R1CSError::InvalidInputValue { signal, value } => write!(
+                f,
+                "Input signal {} is not a valid field element: {}",
+                signal, value
+            ),
+            R1CSError::Json(e) => write!(f, "JSON error: {}", e),
+            R1CSError::Io(e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl Error for R1CSError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            R1CSError::Json(e) => Some(e),
+            R1CSError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for R1CSError {
+    fn from(e: serde_json::Error) -> Self {
+        R1CSError::Json(e)
+    }
+}
+
+impl From<io::Error> for R1CSError {
+    fn from(e: io::Error) -> Self {
+        R1CSError::Io(e)
+    }
+}
+
+impl From<R1CSError> for io::Error {
+    fn from(e: R1CSError) -> Self {
+        match e {
+            R1CSError::Io(e) => e,
+            e => io::Error::new(io::ErrorKind::InvalidData, e),
+        }
+    }
+}
+
+/// Wrapper for R1CS file data with additional utility methods.
+///
+/// Coefficients are decoded into the scalar field `F` of the proving curve,
+/// which defaults to bls12-381's `Fr`.
+///
+/// Two circuits are equal when their headers, constraints, wire maps and
+/// custom gates are; constraints compare regardless of the order of their
+/// terms (see [`R1CSConstraint`]).
+#[derive(Clone, PartialEq)]
+pub struct R1CS<F: PrimeField = Fr> {
     header: R1CSHeader,
-    constraints: Vec<R1CSConstraint>,
+    constraints: Vec<R1CSConstraint<F>>,
+    wire_to_label: Vec<u64>,
+    custom_gates: Vec<CustomGate<F>>,
+    custom_gate_uses: Vec<CustomGateUse>,
+}
+
+/// A custom gate template declared in section 4. circom only emits these
+/// for PLONKish circuits using `pragma custom_templates`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomGate<F: PrimeField = Fr> {
+    pub name: String,
+    pub parameters: Vec<F>,
+}
+
+/// An application of a custom gate to some wires, from section 5
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomGateUse {
+    /// Index into the custom gates declared in section 4
+    pub gate: u32,
+    pub wires: Vec<u64>,
+}
+
+/// One of the three linear combinations of a constraint `A · B = C`, i.e.
+/// a row of that matrix
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Matrix {
+    A,
+    B,
+    C,
+}
+
+impl fmt::Display for Matrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Matrix::A => write!(f, "A"),
+            Matrix::B => write!(f, "B"),
+            Matrix::C => write!(f, "C"),
+        }
+    }
+}
+
+/// Options controlling how an R1CS file is parsed
+#[derive(Debug, Clone, Default)]
+pub struct ReadOptions {
+    /// Decode constraints on all cores with rayon
+    pub parallel: bool,
+    /// Keep parsed headers in a `.meta` sidecar next to the file, see
+    /// [`R1CSHeader::read_with_options`], and parsed circuits in a `.parsed`
+    /// dump, see [`R1CS::save_parsed`]
+    pub cache: bool,
+    /// Reject coefficients that aren't reduced modulo the header's prime
+    /// with [`R1CSError::UnreducedCoefficient`], instead of reducing them
+    pub strict: bool,
+    /// Show a bar on stderr counting constraints as they are parsed
+    pub progress: bool,
+    /// Treat this many leading non-ONE wires as public instead of the
+    /// header's count, see [`R1CS::override_public_count`]
+    pub public_count: Option<u32>,
+}
+
+/// Summary of an R1CS circuit, as returned by [`R1CS::info`]
+#[derive(Debug, Clone, Serialize)]
+pub struct R1CSInfo {
+    /// Size of a field element in bytes
+    pub field_size: u32,
+    /// Prime field modulus as big-endian hex (`0x...`)
+    pub prime: String,
+    pub num_wires: u32,
+    pub num_public_outputs: u32,
+    pub num_public_inputs: u32,
+    pub num_private_inputs: u32,
+    pub num_labels: u64,
+    pub num_constraints: u32,
+}
+
+/// Where a circuit's constraints concentrate, as returned by [`R1CS::stats`]
+#[derive(Debug, Clone, Serialize)]
+pub struct R1CSStats {
+    pub num_constraints: usize,
+    /// Terms with a non-zero coefficient in each of the A, B and C matrices
+    pub a_terms: usize,
+    pub b_terms: usize,
+    pub c_terms: usize,
+    /// Average non-zero terms per constraint, over A, B and C together
+    pub avg_terms_per_constraint: f64,
+    pub max_terms_per_constraint: usize,
+    /// Index of the first constraint with the most terms
+    pub max_terms_constraint: Option<usize>,
+    /// How many wires are touched by each number of constraints, e.g.
+    /// `{0: 2, 1: 40, 3: 5}` is two unused wires, 40 wires in one
+    /// constraint and five in three
+    pub wire_usage: BTreeMap<usize, usize>,
+    /// Wires other than ONE and the inputs that no constraint uses, see
+    /// [`R1CS::unconstrained_wires`]
+    pub unconstrained_wires: Vec<u32>,
+}
+
+/// A curve whose scalar field circom can compile circuits over, and which
+/// this crate can prove with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KnownCurve {
+    /// alt_bn128, circom's default
+    Bn254,
+    /// circom `--prime bls12381`
+    Bls12_381,
+}
+
+impl KnownCurve {
+    /// Every known curve, in the order [`R1CSHeader::detect_curve`] tries them
+    pub const ALL: [KnownCurve; 2] = [KnownCurve::Bn254, KnownCurve::Bls12_381];
+    
+    /// The curve's scalar field modulus, which circom writes as the R1CS prime
+    pub fn modulus(self) -> BigUint {
+        match self {
+            KnownCurve::Bn254 => ark_bn254::Fr::MODULUS.into(),
+            KnownCurve::Bls12_381 => Fr::MODULUS.into(),
+        }
+    }
+}
+
+impl fmt::Display for KnownCurve {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KnownCurve::Bn254 => write!(f, "bn254"),
+            KnownCurve::Bls12_381 => write!(f, "bls12-381"),
+        }
+    }
 }
 
 /// Structure to hold R1CS header information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct R1CSHeader {
+    /// Size of a field element in bytes (32 for bn254 and bls12-381 scalars)
     pub field_size: u32,
     pub prime_bytes: Vec<u8>,
     pub n_wires: u32,
@@ -23,196 +391,567 @@ pub struct R1CSHeader {
     pub n_prvt_in: u32,
     pub n_labels: u64,
     pub n_constraints: u32,
+    /// Format version of the file the header was read from
+    pub version: u32,
+    /// Version 2 headers flag circuits that use custom gates (PLONKish only)
+    pub use_custom_gates: bool,
 }
 
-/// Represents a term in a linear combination (wire index and coefficient)
-#[derive(Debug, Clone)]
-pub struct Term {
+impl R1CSHeader {
+    /// Read only the header section of an R1CS file, skipping all other
+    /// sections. This is enough to inspect the prime before choosing which
+    /// field to decode the constraints into.
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self, R1CSError> {
+        let mut reader = compression::open(&path)?;
+        let (version, sections) = scan_sections(&mut reader)?;
+        let section = find_section(&sections, 1).ok_or(R1CSError::MissingHeader)?;
+        read_section(&mut reader, section, |r| read_header_section(r, version, section.size))
+    }
+    
+    /// The header as JSON, with the prime as big-endian hex (`0x...`) as in [`R1CSInfo`]
+    pub fn to_json(&self) -> serde_json::Value {
+        json!({
+            "version": self.version,
+            "field_size": self.field_size,
+            "prime": format!("0x{}", to_hex(trim_le(&self.prime_bytes))),
+            "n_wires": self.n_wires,
+            "n_pub_out": self.n_pub_out,
+            "n_pub_in": self.n_pub_in,
+            "n_prvt_in": self.n_prvt_in,
+            "n_labels": self.n_labels,
+            "n_constraints": self.n_constraints,
+            "use_custom_gates": self.use_custom_gates,
+        })
+    }
+    
+    /// Read only the header, through the `.r1cs.meta` sidecar cache if
+    /// `options.cache` is set (see [`cache::read_header`])
+    pub fn read_with_options<P: AsRef<Path>>(path: P, options: &ReadOptions) -> Result<Self, R1CSError> {
+        if options.cache {
+            cache::read_header(path.as_ref())
+        } else {
+            Self::read(path)
+        }
+    }
+    
+    /// Get the prime field modulus as an integer
+    pub fn modulus_biguint(&self) -> BigUint {
+        BigUint::from_bytes_le(&self.prime_bytes)
+    }
+    
+    /// Find the curve whose scalar field modulus is the header's prime
+    pub fn detect_curve(&self) -> Option<KnownCurve> {
+        let modulus = self.modulus_biguint();
+        KnownCurve::ALL.into_iter().find(|curve| curve.modulus() == modulus)
+    }
+    
+    /// Check whether the header's prime is the modulus of the field `F`
+    pub fn modulus_matches<F: PrimeField>(&self) -> bool {
+        trim_le(&self.prime_bytes) == trim_le(&F::MODULUS.to_bytes_le())
+    }
+}
+
+/// Represents a term in a linear combination (wire index and coefficient).
+/// Terms order by wire index first, which is how parsed linear combinations
+/// are sorted.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Term<F: PrimeField = Fr> {
     pub wire_id: u32,
-    pub coefficient: Fr,
+    pub coefficient: F,
 }
 
-/// Represents an R1CS constraint in a more accessible format
+/// Represents an R1CS constraint in a more accessible format.
+///
+/// Constraints are equal when each linear combination sums the same
+/// coefficients per wire, whatever order the terms are in.
 #[derive(Debug, Clone)]
-pub struct R1CSConstraint {
-    pub a_terms: Vec<Term>,
-    pub b_terms: Vec<Term>,
-    pub c_terms: Vec<Term>,
+pub struct R1CSConstraint<F: PrimeField = Fr> {
+    pub a_terms: Vec<Term<F>>,
+    pub b_terms: Vec<Term<F>>,
+    pub c_terms: Vec<Term<F>>,
 }
 
-impl fmt::Display for Term {
+impl<F: PrimeField> PartialEq for R1CSConstraint<F> {
+    fn eq(&self, other: &Self) -> bool {
+        let same = |ours: &[Term<F>], theirs: &[Term<F>]| {
+            let (mut ours, mut theirs) = (ours.to_vec(), theirs.to_vec());
+            canonicalize_terms(&mut ours);
+            canonicalize_terms(&mut theirs);
+            ours == theirs
+        };
+        same(&self.a_terms, &other.a_terms) && same(&self.b_terms, &other.b_terms) && same(&self.c_terms, &other.c_terms)
+    }
+}
+
+impl<F: PrimeField> Eq for R1CSConstraint<F> {}
+
+/// Terms print as `3·x7`, with the coefficient in decimal, or as `-x7` for
+/// a coefficient of -1 (which would otherwise print as the prime minus one)
+impl<F: PrimeField> fmt::Display for Term<F> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}·x{}", self.coefficient, self.wire_id)
+        f.write_str(&format_term(&self.coefficient, &format!("x{}", self.wire_id)))
     }
 }
 
-impl fmt::Display for R1CSConstraint {
+impl<F: PrimeField> fmt::Display for R1CSConstraint<F> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // Format A terms
-        let a_str = if self.a_terms.is_empty() {
-            "0".to_string()
-        } else {
-            self.a_terms.iter()
-                .map(|t| format!("{}", t))
-                .collect::<Vec<_>>()
-                .join(" + ")
-        };
+        let name = |wire_id: u32| format!("x{}", wire_id);
+        write!(
+            f,
+            "({}) · ({}) = {}",
+            format_terms(&self.a_terms, name),
+            format_terms(&self.b_terms, name),
+            format_terms(&self.c_terms, name)
+        )
+    }
+}
 
-        // Format B terms
-        let b_str = if self.b_terms.is_empty() {
-            "0".to_string()
-        } else {
-            self.b_terms.iter()
-                .map(|t| format!("{}", t))
-                .collect::<Vec<_>>()
-                .join(" + ")
+impl<F: PrimeField> R1CSConstraint<F> {
+    /// The constraint as JSON: `{"a": [{"wire": 2, "coeff": "3"}, ...], "b": ..., "c": ...}`,
+    /// with coefficients in decimal
+    pub fn to_json(&self) -> serde_json::Value {
+        json!({
+            "a": terms_to_json(&self.a_terms),
+            "b": terms_to_json(&self.b_terms),
+            "c": terms_to_json(&self.c_terms),
+        })
+    }
+    
+    /// Evaluate the A, B and C linear combinations against a full wire
+    /// assignment, or `None` if a term references a wire outside `witness`.
+    /// The constraint holds when `A · B = C`.
+    pub fn evaluate(&self, witness: &[F]) -> Option<(F, F, F)> {
+        Some((
+            evaluate_terms(&self.a_terms, witness)?,
+            evaluate_terms(&self.b_terms, witness)?,
+            evaluate_terms(&self.c_terms, witness)?,
+        ))
+    }
+    
+    /// Render the constraint with signal names from a `.sym` file, e.g.
+    /// `(3·main.in[2]) · (1·main.sel) = 1·main.out`. Wires without a name
+    /// fall back to `xN`.
+    pub fn display_with_symbols(&self, symbols: &SymbolTable) -> String {
+        let name = |wire_id: u32| {
+            symbols
+                .name(wire_id)
+                .map_or_else(|| format!("x{}", wire_id), str::to_string)
         };
+        format!(
+            "({}) · ({}) = {}",
+            format_terms(&self.a_terms, name),
+            format_terms(&self.b_terms, name),
+            format_terms(&self.c_terms, name)
+        )
+    }
+}
 
-        // Format C terms
-        let c_str = if self.c_terms.is_empty() {
-            "0".to_string()
-        } else {
-            self.c_terms.iter()
-                .map(|t| format!("{}", t))
-                .collect::<Vec<_>>()
-                .join(" + ")
-        };
+fn terms_to_json<F: PrimeField>(terms: &[Term<F>]) -> serde_json::Value {
+    terms
+        .iter()
+        .map(|t| json!({ "wire": t.wire_id, "coeff": t.coefficient.into_bigint().to_string() }))
+        .collect()
+}
+
+/// A little-endian integer as exactly `field_size` bytes, zero padded. Values
+/// here are always below the prime, so only zero padding is ever cut off.
+pub(crate) fn field_bytes(le_bytes: &[u8], field_size: u32) -> Vec<u8> {
+    let mut bytes = le_bytes.to_vec();
+    bytes.resize(field_size as usize, 0);
+    bytes
+}
+
+/// Sort a linear combination by wire index and merge terms on the same wire
+/// by summing their coefficients, as circom guarantees neither
+fn canonicalize_terms<F: PrimeField>(terms: &mut Vec<Term<F>>) {
+    terms.sort();
+    terms.dedup_by(|term, kept| {
+        let same_wire = term.wire_id == kept.wire_id;
+        if same_wire {
+            kept.coefficient += term.coefficient;
+        }
+        same_wire
+    });
+}
 
-        write!(f, "({}) · ({}) = {}", a_str, b_str, c_str)
+/// Format a linear combination as `c·name + ...`, or `0` if it has no terms
+fn format_terms<F: PrimeField>(terms: &[Term<F>], name: impl Fn(u32) -> String) -> String {
+    if terms.is_empty() {
+        return "0".to_string();
     }
+    terms
+        .iter()
+        .map(|t| format_term(&t.coefficient, &name(t.wire_id)))
+        .collect::<Vec<_>>()
+        .join(" + ")
 }
 
-impl R1CS {
-    /// Read and parse an R1CS file using direct I/O operations
-    pub fn read<P: AsRef<Path>>(path: P) -> io::Result<Self> {
-        println!("Reading R1CS file from: {}", path.as_ref().display());
-        
-        let mut file = File::open(&path)?;
-        
-        // Read magic bytes "r1cs"
-        let mut magic = [0u8; 4];
-        file.read_exact(&mut magic)?;
+/// Format one term as `c·name`, with `c` in decimal, or `-name` for -1
+fn format_term<F: PrimeField>(coefficient: &F, name: &str) -> String {
+    if *coefficient == -F::one() {
+        format!("-{}", name)
+    } else {
+        format!("{}·{}", coefficient.into_bigint(), name)
+    }
+}
+
+impl<F: PrimeField> R1CS<F> {
+    /// Read and parse an R1CS file, which may be gzip- or zstd-compressed
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self, R1CSError> {
+        Self::read_with_options(path, &ReadOptions::default())
+    }
+    
+    /// Read and parse an R1CS file with non-default parsing options
+    pub fn read_with_options<P: AsRef<Path>>(path: P, options: &ReadOptions) -> Result<Self, R1CSError> {
+        info!("Reading R1CS file from: {}", path.as_ref().display());
         
-        if &magic != b"r1cs" {
-            return Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                "Invalid R1CS file: wrong magic bytes"
-            ));
+        if options.cache && !options.strict {
+            return Self::read_cached(path.as_ref(), options);
         }
-        
-        // Read version
-        let version = file.read_u32::<LittleEndian>()?;
-        if version != 1 {
-            return Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                format!("Unsupported R1CS version: {}", version)
-            ));
+        Self::from_reader_with_options(compression::open(&path)?, options)
+    }
+    
+    // Load the `.parsed` dump of an R1CS file if it is fresh, or else parse
+    // the file and save a new dump. Strict reads always parse, since a dump
+    // holds coefficients already reduced. As with the header cache, a dump
+    // that can't be read or written is never an error.
+    fn read_cached(path: &Path, options: &ReadOptions) -> Result<Self, R1CSError> {
+        let dump = parsed_path(path);
+        let mut r1cs = match Self::load_parsed(&dump, path) {
+            Ok(r1cs) => {
+                debug!("Using parsed R1CS dump {}", dump.display());
+                r1cs
+            }
+            Err(e) => {
+                if dump.exists() {
+                    debug!("Not using {}: {}", dump.display(), e);
+                }
+                // The dump holds the circuit as the file declares it
+                let parse_options = ReadOptions { public_count: None, ..options.clone() };
+                let r1cs = Self::from_reader_with_options(compression::open(path)?, &parse_options)?;
+                if let Err(e) = r1cs.save_parsed(&dump, path) {
+                    debug!("Could not write {}: {}", dump.display(), e);
+                }
+                r1cs
+            }
+        };
+        if let Some(count) = options.public_count {
+            r1cs.override_public_count(count)?;
         }
+        Ok(r1cs)
+    }
+    
+    /// Read and parse an R1CS file through a memory map, which avoids a
+    /// read call per field for very large files. Compressed files can't be
+    /// mapped and are read normally.
+    #[cfg(feature = "mmap")]
+    pub fn read_mmap<P: AsRef<Path>>(path: P, options: &ReadOptions) -> Result<Self, R1CSError> {
+        info!("Memory-mapping R1CS file from: {}", path.as_ref().display());
         
-        // Read number of sections
-        let num_sections = file.read_u32::<LittleEndian>()?;
-        println!("R1CS file has {} sections", num_sections);
+        let file = std::fs::File::open(&path)?;
+        // SAFETY: the map is only read while parsing; as with any mmap, the
+        // file must not be truncated by another process in the meantime
+        let map = unsafe { memmap2::Mmap::map(&file)? };
         
-        // Initialize header with default values
-        let mut header = R1CSHeader {
-            field_size: 0,
-            prime_bytes: Vec::new(),
-            n_wires: 0,
-            n_pub_out: 0,
-            n_pub_in: 0,
-            n_prvt_in: 0,
-            n_labels: 0,
-            n_constraints: 0,
-        };
+        if compression::Compression::detect(&map) != compression::Compression::None {
+            return Self::read_with_options(path, options);
+        }
+        Self::from_reader_with_options(io::Cursor::new(&map[..]), options)
+    }
+    
+    /// Cheaply check that `reader` holds an R1CS file, reading only its first
+    /// 12 bytes. Returns the format version and the declared section count.
+    pub fn probe<R: Read>(mut reader: R) -> Result<(u32, u32), R1CSError> {
+        read_preamble(&mut reader)
+    }
+    
+    /// Parse an R1CS file from any seekable source, such as an in-memory
+    /// `Cursor` or a decompressed buffer. Section bounds are checked against
+    /// the end of the stream.
+    pub fn from_reader<R: Read + Seek>(reader: R) -> Result<Self, R1CSError> {
+        Self::from_reader_with_options(reader, &ReadOptions::default())
+    }
+    
+    /// Parse an R1CS file from any seekable source with non-default parsing options
+    pub fn from_reader_with_options<R: Read + Seek>(mut reader: R, options: &ReadOptions) -> Result<Self, R1CSError> {
+        // First pass: find every section, since they may come in any order
+        let (version, sections) = scan_sections(&mut reader)?;
+        debug!("R1CS file version {} has {} sections", version, sections.len());
+        
+        // Second pass: the header first, as decoding coefficients needs its
+        // field size, then everything else
+        let header_section = find_section(&sections, 1).ok_or(R1CSError::MissingHeader)?;
+        debug!("Reading header section of size {} bytes", header_section.size);
+        let header = read_section(&mut reader, header_section, |r| read_header_section(r, version, header_section.size))?;
         
-        // Read sections
         let mut constraints = Vec::new();
+        let mut wire_to_label = Vec::new();
+        let mut custom_gates = Vec::new();
+        let mut custom_gate_uses = Vec::new();
         
-        for _ in 0..num_sections {
-            let section_type = file.read_u32::<LittleEndian>()?;
-            let section_size = file.read_u64::<LittleEndian>()?;
-            
-            match section_type {
-                1 => { // Header section
-                    println!("Reading header section of size {} bytes", section_size);
-                    header = Self::read_header_section(&mut file)?;
-                }
-                2 => { // Constraints section
-                    println!("Reading constraints section of size {} bytes", section_size);
-                    // For now, we'll just skip this section
-                    let current_pos = file.seek(SeekFrom::Current(0))?;
-                    file.seek(SeekFrom::Start(current_pos + section_size))?;
+        let modulus = options.strict.then(|| header.modulus_biguint());
+        let progress = constraint_progress(options.progress, header.n_constraints);
+        
+        for section in &sections {
+            match section.section_type {
+                1 => {} // Header section, already read
+                2 => { // Constraints section; some tools split constraints over several
+                    debug!("Reading constraints section of size {} bytes", section.size);
+                    let first = constraints.len();
+                    let section_constraints = if options.parallel {
+                        read_section(&mut reader, section, |r| Self::read_constraints_section_parallel(r, &header, section, first, modulus.as_ref(), &progress))?
+                    } else {
+                        read_section(&mut reader, section, |r| Self::read_constraints_section(r, &header, section, first, modulus.as_ref(), &progress))?
+                    };
+                    constraints.extend(section_constraints);
                 }
                 3 => { // Wire map section
-                    println!("Skipping wire map section of size {} bytes", section_size);
-                    let current_pos = file.seek(SeekFrom::Current(0))?;
-                    file.seek(SeekFrom::Start(current_pos + section_size))?;
+                    debug!("Reading wire map section of size {} bytes", section.size);
+                    wire_to_label = read_section(&mut reader, section, |r| Self::read_wire_map_section(r, section.size))?;
                 }
-                4 | 5 => { // Custom gates sections (UltraPlonk specific)
-                    println!("Skipping custom gates section of size {} bytes", section_size);
-                    let current_pos = file.seek(SeekFrom::Current(0))?;
-                    file.seek(SeekFrom::Start(current_pos + section_size))?;
+                4 => { // Custom gates list (PLONKish circuits only)
+                    debug!("Reading custom gates section of size {} bytes", section.size);
+                    custom_gates = read_section(&mut reader, section, |r| Self::read_custom_gates_section(r, header.field_size))?;
                 }
-                _ => {
-                    println!("Skipping unknown section type {} of size {} bytes", section_type, section_size);
-                    let current_pos = file.seek(SeekFrom::Current(0))?;
-                    file.seek(SeekFrom::Start(current_pos + section_size))?;
+                5 => { // Custom gates applications
+                    debug!("Reading custom gate applications section of size {} bytes", section.size);
+                    custom_gate_uses = read_section(&mut reader, section, |r| Self::read_custom_gate_uses_section(r))?;
+                }
+                section_type => {
+                    debug!("Skipping unknown section type {} of size {} bytes", section_type, section.size);
                 }
             }
         }
         
-        println!("Successfully parsed R1CS file header");
+        progress.finish_and_clear();
+        
+        if constraints.len() != header.n_constraints as usize {
+            return Err(R1CSError::ConstraintCountMismatch {
+                declared: header.n_constraints,
+                parsed: constraints.len(),
+            });
+        }
+        
+        check_wire_map(&wire_to_label, &header)?;
+        
+        info!("Successfully parsed R1CS file with {} constraints", constraints.len());
         
-        // For now, we'll return without fully parsing the constraints
-        // This is enough to get the metadata we need
-        Ok(Self { 
+        let mut r1cs = Self { 
             header,
             constraints,
+            wire_to_label,
+            custom_gates,
+            custom_gate_uses,
+        };
+        if let Some(count) = options.public_count {
+            r1cs.override_public_count(count)?;
+        }
+        Ok(r1cs)
+    }
+    
+    // Read one constraints section, whose first constraint is number `first`
+    // of the circuit. The total over all sections is checked by the caller.
+    fn read_constraints_section<R: Read + Seek>(
+        reader: &mut R,
+        header: &R1CSHeader,
+        section: &Section,
+        first: usize,
+        modulus: Option<&BigUint>,
+        progress: &ProgressBar,
+    ) -> Result<Vec<R1CSConstraint<F>>, R1CSError> {
+        // Every constraint takes at least its three term counts, so a corrupted
+        // count can't reserve more than the section could hold
+        let max_constraints = section.size / 12;
+        let mut constraints = Vec::with_capacity((header.n_constraints as u64).min(max_constraints) as usize);
+        
+        // Read constraints until the section runs out, so a section that
+        // ends early shows up as a count mismatch rather than a misparse
+        let section_end = section.offset + section.size;
+        while reader.stream_position()? < section_end {
+            let idx = first + constraints.len();
+            let constraint = Self::read_constraint(reader, header, idx, modulus)?;
+            check_wire_ids(idx, &constraint, header.n_wires)?;
+            constraints.push(constraint);
+            progress.inc(1);
+        }
+        
+        Ok(constraints)
+    }
+    
+    /// Parse the constraints section on all cores. Constraints vary in length,
+    /// so the section is loaded into memory and scanned once for where each
+    /// constraint starts; the constraints are then decoded in parallel, in order.
+    fn read_constraints_section_parallel<R: Read>(
+        reader: &mut R,
+        header: &R1CSHeader,
+        section: &Section,
+        first: usize,
+        modulus: Option<&BigUint>,
+        progress: &ProgressBar,
+    ) -> Result<Vec<R1CSConstraint<F>>, R1CSError> {
+        let mut bytes = vec![0u8; section.size as usize];
+        reader.read_exact(&mut bytes)?;
+        
+        let offsets = constraint_offsets(&bytes, header.field_size).map_err(|consumed| {
+            R1CSError::SectionSizeMismatch {
+                section_type: section.section_type,
+                declared: section.size,
+                consumed,
+            }
+        })?;
+        
+        offsets
+            .par_iter()
+            .enumerate()
+            .map(|(i, &offset)| {
+                let idx = first + i;
+                let constraint = Self::read_constraint(&mut &bytes[offset..], header, idx, modulus)?;
+                check_wire_ids(idx, &constraint, header.n_wires)?;
+                progress.inc(1);
+                Ok(constraint)
+            })
+            .collect()
+    }
+    
+    // Read the `idx`-th constraint. With a `modulus`, unreduced coefficients
+    // are an error rather than silently reduced.
+    fn read_constraint<R: Read>(
+        reader: &mut R,
+        header: &R1CSHeader,
+        idx: usize,
+        modulus: Option<&BigUint>,
+    ) -> Result<R1CSConstraint<F>, R1CSError> {
+        let a_terms = Self::read_linear_combination(reader, header, idx, modulus)?;
+        let b_terms = Self::read_linear_combination(reader, header, idx, modulus)?;
+        let c_terms = Self::read_linear_combination(reader, header, idx, modulus)?;
+        
+        Ok(R1CSConstraint {
+            a_terms,
+            b_terms,
+            c_terms,
         })
     }
     
-    fn read_header_section(file: &mut File) -> io::Result<R1CSHeader> {
-        // Read field element size (in bytes)
-        let field_size = file.read_u32::<LittleEndian>()?;
-        println!("  Field size: {} bytes", field_size);
+    fn read_linear_combination<R: Read>(
+        reader: &mut R,
+        header: &R1CSHeader,
+        idx: usize,
+        modulus: Option<&BigUint>,
+    ) -> Result<Vec<Term<F>>, R1CSError> {
+        // Number of terms, followed by (wire_id, coefficient) pairs. A
+        // well-formed file has at most one term per wire; more than that many
+        // terms is taken as a corrupted count.
+        let n_terms = reader.read_u32::<LittleEndian>()?;
+        if n_terms > header.n_wires {
+            return Err(R1CSError::ImplausibleTermCount { terms: n_terms, num_wires: header.n_wires });
+        }
+        // The header's wire count can be corrupted too, so grow the terms as
+        // they're actually read instead of reserving them all up front
+        let mut terms = Vec::new();
+        let field_size = header.field_size;
+        let mut coeff_bytes = vec![0u8; field_size as usize];
         
-        // Read prime field modulus
-        let mut prime_bytes = vec![0u8; field_size as usize];
-        file.read_exact(&mut prime_bytes)?;
+        for _ in 0..n_terms {
+            let wire_id = reader.read_u32::<LittleEndian>()?;
+            reader.read_exact(&mut coeff_bytes)?;
+            if modulus.is_some_and(|modulus| BigUint::from_bytes_le(&coeff_bytes) >= *modulus) {
+                return Err(R1CSError::UnreducedCoefficient { constraint: idx, wire: wire_id });
+            }
+            
+            terms.push(Term {
+                wire_id,
+                coefficient: bytes_to_fr(&coeff_bytes, field_size)?,
+            });
+        }
         
-        // Read number of wires
-        let n_wires = file.read_u32::<LittleEndian>()?;
-        println!("  Number of wires: {}", n_wires);
+        canonicalize_terms(&mut terms);
+        Ok(terms)
+    }
+    
+    fn read_wire_map_section<R: Read>(reader: &mut R, section_size: u64) -> Result<Vec<u64>, R1CSError> {
+        // One u64 label id per wire
+        let n_entries = section_size / 8;
+        let mut wire_to_label = Vec::with_capacity(n_entries as usize);
         
-        // Read number of public outputs
-        let n_pub_out = file.read_u32::<LittleEndian>()?;
-        println!("  Number of public outputs: {}", n_pub_out);
+        for _ in 0..n_entries {
+            wire_to_label.push(reader.read_u64::<LittleEndian>()?);
+        }
         
-        // Read number of public inputs
-        let n_pub_in = file.read_u32::<LittleEndian>()?;
-        println!("  Number of public inputs: {}", n_pub_in);
+        Ok(wire_to_label)
+    }
+    
+    fn read_custom_gates_section<R: Read>(reader: &mut R, field_size: u32) -> Result<Vec<CustomGate<F>>, R1CSError> {
+        // Gate count, then per gate a NUL-terminated name and its parameters
+        let n_gates = reader.read_u32::<LittleEndian>()?;
+        let mut gates = Vec::new();
+        let mut param_bytes = vec![0u8; field_size as usize];
         
-        // Read number of private inputs
-        let n_prvt_in = file.read_u32::<LittleEndian>()?;
-        println!("  Number of private inputs: {}", n_prvt_in);
+        for _ in 0..n_gates {
+            let mut name = Vec::new();
+            loop {
+                match reader.read_u8()? {
+                    0 => break,
+                    b => name.push(b),
+                }
+            }
+            
+            let n_params = reader.read_u32::<LittleEndian>()?;
+            let mut parameters = Vec::new();
+            for _ in 0..n_params {
+                reader.read_exact(&mut param_bytes)?;
+                parameters.push(bytes_to_fr(&param_bytes, field_size)?);
+            }
+            
+            gates.push(CustomGate {
+                name: String::from_utf8_lossy(&name).into_owned(),
+                parameters,
+            });
+        }
         
-        // Read number of labels
-        let n_labels = file.read_u64::<LittleEndian>()?;
-        println!("  Number of labels: {}", n_labels);
+        Ok(gates)
+    }
+    
+    fn read_custom_gate_uses_section<R: Read>(reader: &mut R) -> Result<Vec<CustomGateUse>, R1CSError> {
+        // Application count, then per application the gate index and its wires
+        let n_uses = reader.read_u32::<LittleEndian>()?;
+        let mut uses = Vec::new();
         
-        // Read number of constraints
-        let n_constraints = file.read_u32::<LittleEndian>()?;
-        println!("  Number of constraints: {}", n_constraints);
+        for _ in 0..n_uses {
+            let gate = reader.read_u32::<LittleEndian>()?;
+            let n_wires = reader.read_u32::<LittleEndian>()?;
+            let mut wires = Vec::new();
+            for _ in 0..n_wires {
+                wires.push(reader.read_u64::<LittleEndian>()?);
+            }
+            uses.push(CustomGateUse { gate, wires });
+        }
         
-        Ok(R1CSHeader {
-            field_size,
-            prime_bytes,
-            n_wires,
-            n_pub_out,
-            n_pub_in,
-            n_prvt_in,
-            n_labels,
-            n_constraints,
+        Ok(uses)
+    }
+    
+    /// Open an R1CS file for streaming: the header is parsed up front, and
+    /// constraints are then read one at a time as the iterator advances,
+    /// so large circuits never have to be held in memory at once.
+    pub fn constraints_iter<P: AsRef<Path>>(path: P) -> Result<ConstraintIter<F, Source>, R1CSError> {
+        let mut reader = compression::open(&path)?;
+        let (version, sections) = scan_sections(&mut reader)?;
+        
+        let header_section = find_section(&sections, 1).ok_or(R1CSError::MissingHeader)?;
+        let header = read_section(&mut reader, header_section, |r| read_header_section(r, version, header_section.size))?;
+        
+        // Constraints may be split over several sections, read in file
+        // order; the iterator moves on to the next as each one runs out
+        let mut constraint_sections: Vec<Section> = sections.into_iter().filter(|s| s.section_type == 2).collect();
+        constraint_sections.reverse();
+        
+        Ok(ConstraintIter {
+            reader,
+            remaining: header.n_constraints,
+            header,
+            sections: constraint_sections,
+            section: None,
+            finished: false,
+            _field: PhantomData,
         })
     }
     
@@ -236,6 +975,27 @@ impl R1CS {
         self.header.n_pub_out + self.header.n_pub_in
     }
     
+    /// Treat the first `count` wires after ONE as public, whatever the
+    /// header says. This is an escape hatch for files whose header counts
+    /// are wrong: public outputs keep their place and the rest are counted as
+    /// public inputs, and private inputs are trimmed to still fit.
+    pub fn override_public_count(&mut self, count: u32) -> Result<(), R1CSError> {
+        let header = &mut self.header;
+        if count >= header.n_wires {
+            return Err(R1CSError::PublicCountOutOfRange { requested: count, num_wires: header.n_wires });
+        }
+        
+        let declared = header.n_pub_out + header.n_pub_in;
+        if count != declared {
+            warn!("Treating {} wires as public, but the R1CS header declares {}", count, declared);
+        }
+        
+        header.n_pub_out = header.n_pub_out.min(count);
+        header.n_pub_in = count - header.n_pub_out;
+        header.n_prvt_in = header.n_prvt_in.min(header.n_wires - 1 - count);
+        Ok(())
+    }
+    
     /// Get the number of private inputs in the circuit
     pub fn num_private_inputs(&self) -> u32 {
         self.header.n_prvt_in
@@ -246,41 +1006,1118 @@ impl R1CS {
         self.header.n_constraints
     }
     
-    /// Get the prime field modulus from the R1CS file
+    /// Get the prime field modulus from the R1CS file, as little-endian bytes
     pub fn prime_field_modulus(&self) -> &[u8] {
         &self.header.prime_bytes
     }
     
+    /// Get the prime field modulus as an integer
+    pub fn modulus_biguint(&self) -> BigUint {
+        self.header.modulus_biguint()
+    }
+    
+    /// Find the curve whose scalar field modulus is the file's prime
+    pub fn detect_curve(&self) -> Option<KnownCurve> {
+        self.header.detect_curve()
+    }
+    
+    /// Check that the file's prime is the modulus of `G`, so coefficients
+    /// aren't silently reduced into the wrong field
+    pub fn assert_modulus_matches<G: PrimeField>(&self) -> Result<(), R1CSError> {
+        if self.header.modulus_matches::<G>() {
+            Ok(())
+        } else {
+            Err(R1CSError::ModulusMismatch {
+                expected: trim_le(&G::MODULUS.to_bytes_le()).to_vec(),
+                got: trim_le(&self.header.prime_bytes).to_vec(),
+            })
+        }
+    }
+    
     /// Get all constraints in the circuit, converted to our internal format
-    pub fn constraints(&self) -> &Vec<R1CSConstraint> {
+    pub fn constraints(&self) -> &Vec<R1CSConstraint<F>> {
         &self.constraints
     }
     
+    /// Get the `idx`-th constraint, or `None` past the end. Indices are
+    /// 0-based, in the order the constraints were parsed.
+    pub fn constraint(&self, idx: usize) -> Option<&R1CSConstraint<F>> {
+        self.constraints.get(idx)
+    }
+    
+    /// Count the terms of the `idx`-th constraint's A, B and C linear
+    /// combinations (0-based, as in [`R1CS::constraint`]), or `None` past the end
+    pub fn num_terms(&self, idx: usize) -> Option<(usize, usize, usize)> {
+        self.constraint(idx).map(|c| (c.a_terms.len(), c.b_terms.len(), c.c_terms.len()))
+    }
+
+    /// Get the label id of each wire, indexed by wire (empty if the file has no wire map)
+    pub fn wire_to_label(&self) -> &[u64] {
+        &self.wire_to_label
+    }
+
+    /// Get the number of labels, i.e. of signals before circom's optimizer
+    /// merged or removed some; a `.sym` file has one line per label
+    pub fn num_labels(&self) -> u64 {
+        self.header.n_labels
+    }
+
+    /// Get the label id of a wire from the wire map, or `None` if the wire
+    /// is out of range or the file has no wire map
+    pub fn label(&self, wire_id: u32) -> Option<u64> {
+        self.wire_to_label.get(wire_id as usize).copied()
+    }
+
+    /// Get a wire's signal name: the `.sym` entry for its label from the
+    /// wire map, or, without a wire map, the first entry on the wire
+    pub fn wire_name<'a>(&self, wire_id: u32, symbols: &'a SymbolTable) -> Option<&'a str> {
+        match self.label(wire_id) {
+            Some(label_id) => symbols.label_name(label_id),
+            None if self.wire_to_label.is_empty() => symbols.name(wire_id),
+            None => None,
+        }
+    }
+
+    /// Get the custom gates declared in the file (empty for plain R1CS)
+    pub fn custom_gates(&self) -> &[CustomGate<F>] {
+        &self.custom_gates
+    }
+
+    /// Get the custom gate applications in the file (empty for plain R1CS)
+    pub fn custom_gate_uses(&self) -> &[CustomGateUse] {
+        &self.custom_gate_uses
+    }
+
+    /// Check whether the circuit uses custom gates, either by the version 2
+    /// header flag or by having custom gate sections
+    pub fn uses_custom_gates(&self) -> bool {
+        self.header.use_custom_gates || !self.custom_gates.is_empty() || !self.custom_gate_uses.is_empty()
+    }
+
+    /// Check that the circuit is plain R1CS that Groth16 can prove.
+    /// Custom gates only exist in PLONKish arithmetizations, so a circuit
+    /// using them would yield a proof that ignores part of the circuit.
+    /// A circuit without constraints is rejected too: its verifier would
+    /// accept any public inputs.
+    pub fn assert_groth16_compatible(&self) -> Result<(), R1CSError> {
+        if self.uses_custom_gates() {
+            return Err(R1CSError::CustomGatesUnsupported);
+        }
+        if self.constraints.is_empty() {
+            return Err(R1CSError::NoConstraints);
+        }
+        Ok(())
+    }
+
+    /// Get mutable access to the constraints for in-place transformations.
+    /// Callers that add or remove constraints or wires are responsible for
+    /// updating `n_constraints`/`n_wires` through `header_mut`.
+    pub fn constraints_mut(&mut self) -> &mut Vec<R1CSConstraint<F>> {
+        &mut self.constraints
+    }
+
+    /// Get the parsed header
+    pub fn header(&self) -> &R1CSHeader {
+        &self.header
+    }
+
+    /// Get mutable access to the header, e.g. to recompute counts after a transformation
+    pub fn header_mut(&mut self) -> &mut R1CSHeader {
+        &mut self.header
+    }
+    
+    /// Check that a full wire assignment satisfies every constraint.
+    ///
+    /// Evaluates `(Σ a_i·w_i) * (Σ b_i·w_i) - (Σ c_i·w_i)` for each constraint
+    /// and returns the index of the first one that doesn't hold, or that
+    /// references a wire outside `witness`. An empty side evaluates to zero,
+    /// as in circom.
+    pub fn check_witness(&self, witness: &[F]) -> Result<(), usize> {
+        for (idx, constraint) in self.constraints.iter().enumerate() {
+            match constraint.evaluate(witness) {
+                Some((a, b, c)) if a * b == c => {}
+                _ => return Err(idx),
+            }
+        }
+        Ok(())
+    }
+    
+    /// Convert the constraints into arkworks' sparse A/B/C matrices, for use
+    /// with other arkworks SNARKs or matrix-level tooling.
+    ///
+    /// circom's wire order (ONE, public outputs, public inputs, private wires)
+    /// is already arkworks' variable order, so wire ids are used as column
+    /// indices directly. An empty side is an empty (zero) row, as in circom.
+    pub fn to_constraint_matrices(&self) -> ConstraintMatrices<F> {
+        let to_row = |terms: &[Term<F>]| -> Vec<(F, usize)> {
+            terms.iter().map(|term| (term.coefficient, term.wire_id as usize)).collect()
+        };
+        
+        let a: Vec<_> = self.constraints.iter().map(|c| to_row(&c.a_terms)).collect();
+        let b: Vec<_> = self.constraints.iter().map(|c| to_row(&c.b_terms)).collect();
+        let c: Vec<_> = self.constraints.iter().map(|c| to_row(&c.c_terms)).collect();
+        
+        let num_instance_variables = 1 + self.num_public_values() as usize;
+        ConstraintMatrices {
+            num_instance_variables,
+            num_witness_variables: (self.num_wires() as usize).saturating_sub(num_instance_variables),
+            num_constraints: self.constraints.len(),
+            a_num_non_zero: a.iter().map(Vec::len).sum(),
+            b_num_non_zero: b.iter().map(Vec::len).sum(),
+            c_num_non_zero: c.iter().map(Vec::len).sum(),
+            a,
+            b,
+            c,
+        }
+    }
+    
+    /// Build an R1CS from an arkworks constraint system, the reverse of
+    /// [`R1CS::to_constraint_matrices`], e.g. to write it out as a `.r1cs`
+    /// fixture with [`R1CS::write`].
+    ///
+    /// arkworks doesn't tell public outputs from inputs, so its instance
+    /// variables all become public inputs, and its witness variables become
+    /// wires without a private input among them. Each wire is its own label.
+    ///
+    /// # Panics
+    ///
+    /// If `cs` is `ConstraintSystemRef::None`.
+    pub fn from_constraint_system(cs: ConstraintSystemRef<F>) -> Self {
+        cs.finalize();
+        let matrices = cs.to_matrices().expect("constraint system should not be None");
+        
+        let to_terms = |row: &[(F, usize)]| -> Vec<Term<F>> {
+            let mut terms: Vec<Term<F>> = row
+                .iter()
+                .map(|&(coefficient, column)| Term { wire_id: column as u32, coefficient })
+                .collect();
+            canonicalize_terms(&mut terms);
+            terms
+        };
+        let constraints: Vec<R1CSConstraint<F>> = (0..matrices.num_constraints)
+            .map(|i| R1CSConstraint {
+                a_terms: to_terms(&matrices.a[i]),
+                b_terms: to_terms(&matrices.b[i]),
+                c_terms: to_terms(&matrices.c[i]),
+            })
+            .collect();
+        
+        let n_wires = (matrices.num_instance_variables + matrices.num_witness_variables) as u32;
+        let prime_bytes = F::MODULUS.to_bytes_le();
+        let header = R1CSHeader {
+            field_size: prime_bytes.len() as u32,
+            prime_bytes,
+            n_wires,
+            n_pub_out: 0,
+            n_pub_in: matrices.num_instance_variables as u32 - 1,
+            n_prvt_in: 0,
+            n_labels: n_wires as u64,
+            n_constraints: constraints.len() as u32,
+            version: 1,
+            use_custom_gates: false,
+        };
+        
+        R1CS {
+            header,
+            constraints,
+            wire_to_label: (0..n_wires as u64).collect(),
+            custom_gates: Vec::new(),
+            custom_gate_uses: Vec::new(),
+        }
+    }
+    
+    /// Write the circuit as a circom `.r1cs` file
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<(), R1CSError> {
+        let mut writer = io::BufWriter::new(std::fs::File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+    
+    /// Write the circuit in circom's `.r1cs` layout: the preamble, then the
+    /// header, constraints and wire map sections, and the custom gate
+    /// sections if the circuit has any. Reading the output back gives the
+    /// same circuit; only unknown sections and header fields are lost.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> Result<(), R1CSError> {
+        let header = &self.header;
+        let mut sections = vec![(1, self.header_section_bytes()), (2, self.constraints_section_bytes())];
+        if !self.wire_to_label.is_empty() {
+            let mut body = Vec::with_capacity(self.wire_to_label.len() * 8);
+            for &label in &self.wire_to_label {
+                body.write_u64::<LittleEndian>(label)?;
+            }
+            sections.push((3, body));
+        }
+        if !self.custom_gates.is_empty() {
+            sections.push((4, self.custom_gates_section_bytes()));
+        }
+        if !self.custom_gate_uses.is_empty() {
+            sections.push((5, self.custom_gate_uses_section_bytes()));
+        }
+        
+        writer.write_all(b"r1cs")?;
+        writer.write_u32::<LittleEndian>(header.version)?;
+        writer.write_u32::<LittleEndian>(sections.len() as u32)?;
+        for (section_type, body) in sections {
+            writer.write_u32::<LittleEndian>(section_type)?;
+            writer.write_u64::<LittleEndian>(body.len() as u64)?;
+            writer.write_all(&body)?;
+        }
+        Ok(())
+    }
+    
+    fn header_section_bytes(&self) -> Vec<u8> {
+        let header = &self.header;
+        let mut body = Vec::new();
+        body.extend_from_slice(&header.field_size.to_le_bytes());
+        body.extend_from_slice(&field_bytes(&header.prime_bytes, header.field_size));
+        for count in [header.n_wires, header.n_pub_out, header.n_pub_in, header.n_prvt_in] {
+            body.extend_from_slice(&count.to_le_bytes());
+        }
+        body.extend_from_slice(&header.n_labels.to_le_bytes());
+        body.extend_from_slice(&(self.constraints.len() as u32).to_le_bytes());
+        if header.version >= 2 {
+            body.extend_from_slice(&(header.use_custom_gates as u32).to_le_bytes());
+        }
+        body
+    }
+    
+    fn custom_gates_section_bytes(&self) -> Vec<u8> {
+        let mut body = (self.custom_gates.len() as u32).to_le_bytes().to_vec();
+        for gate in &self.custom_gates {
+            body.extend_from_slice(gate.name.as_bytes());
+            body.push(0);
+            body.extend_from_slice(&(gate.parameters.len() as u32).to_le_bytes());
+            for parameter in &gate.parameters {
+                body.extend_from_slice(&field_bytes(&parameter.into_bigint().to_bytes_le(), self.header.field_size));
+            }
+        }
+        body
+    }
+    
+    fn custom_gate_uses_section_bytes(&self) -> Vec<u8> {
+        let mut body = (self.custom_gate_uses.len() as u32).to_le_bytes().to_vec();
+        for gate_use in &self.custom_gate_uses {
+            body.extend_from_slice(&gate_use.gate.to_le_bytes());
+            body.extend_from_slice(&(gate_use.wires.len() as u32).to_le_bytes());
+            for &wire in &gate_use.wires {
+                body.extend_from_slice(&wire.to_le_bytes());
+            }
+        }
+        body
+    }
+    
+    fn constraints_section_bytes(&self) -> Vec<u8> {
+        let field_size = self.header.field_size;
+        let mut body = Vec::new();
+        for constraint in &self.constraints {
+            for terms in [&constraint.a_terms, &constraint.b_terms, &constraint.c_terms] {
+                body.extend_from_slice(&(terms.len() as u32).to_le_bytes());
+                for term in terms {
+                    body.extend_from_slice(&term.wire_id.to_le_bytes());
+                    body.extend_from_slice(&field_bytes(&term.coefficient.into_bigint().to_bytes_le(), field_size));
+                }
+            }
+        }
+        body
+    }
+    
+    /// Save the parsed circuit with bincode, for [`R1CS::load_parsed`] to
+    /// read back without circom's parsing: counts are exact, so nothing is
+    /// scanned, and coefficients are stored as canonical field elements, so
+    /// nothing is reduced or sorted again.
+    ///
+    /// The dump is keyed by the SHA-256 of `source`, the R1CS file the circuit
+    /// was parsed from, so a dump of an older version of it is detected.
+    pub fn save_parsed<P: AsRef<Path>, S: AsRef<Path>>(&self, path: P, source: S) -> Result<(), R1CSError> {
+        let key = ParsedKey {
+            magic: *PARSED_MAGIC,
+            format: PARSED_FORMAT,
+            source_sha256: file_digest(source.as_ref())?,
+        };
+        let coefficient_bytes = |coefficient: &F| {
+            let mut bytes = Vec::new();
+            coefficient.serialize_uncompressed(&mut bytes).map(|()| bytes).map_err(io::Error::other)
+        };
+        let constraints = self.constraints.iter().map(|constraint| {
+            let terms = |terms: &[Term<F>]| terms.iter()
+                .map(|term| Ok((term.wire_id, coefficient_bytes(&term.coefficient)?)))
+                .collect::<io::Result<Vec<_>>>();
+            Ok([terms(&constraint.a_terms)?, terms(&constraint.b_terms)?, terms(&constraint.c_terms)?])
+        }).collect::<io::Result<Vec<_>>>()?;
+        let circuit = ParsedCircuit {
+            header: self.header.clone(),
+            constraints,
+            wire_to_label: self.wire_to_label.clone(),
+            custom_gates: self.custom_gates_section_bytes(),
+            custom_gate_uses: self.custom_gate_uses_section_bytes(),
+        };
+        
+        let mut writer = io::BufWriter::new(std::fs::File::create(path)?);
+        parsed_options().serialize_into(&mut writer, &key).map_err(|e| parsed_error(*e))?;
+        parsed_options().serialize_into(&mut writer, &circuit).map_err(|e| parsed_error(*e))?;
+        writer.flush()?;
+        Ok(())
+    }
+    
+    /// Load a circuit saved with [`R1CS::save_parsed`], checking that
+    /// `source` is still the file it was parsed from. A dump of an older
+    /// version of `source` is [`R1CSError::StaleParsedDump`].
+    pub fn load_parsed<P: AsRef<Path>, S: AsRef<Path>>(path: P, source: S) -> Result<Self, R1CSError> {
+        info!("Loading parsed R1CS dump from: {}", path.as_ref().display());
+        let bytes = std::fs::read(path)?;
+        // Lengths in the dump can't claim more than the dump holds
+        let options = parsed_options().with_limit(bytes.len() as u64);
+        let mut reader = &bytes[..];
+        
+        let key: ParsedKey = options.deserialize_from(&mut reader).map_err(|_| R1CSError::BadParsedMagic)?;
+        if &key.magic != PARSED_MAGIC || key.format != PARSED_FORMAT {
+            return Err(R1CSError::BadParsedMagic);
+        }
+        if key.source_sha256 != file_digest(source.as_ref())? {
+            return Err(R1CSError::StaleParsedDump);
+        }
+        let circuit: ParsedCircuit = options.deserialize_from(&mut reader).map_err(|e| parsed_error(*e))?;
+        
+        let header = circuit.header;
+        check_field_size(header.field_size)?;
+        check_header_counts(&header)?;
+        if circuit.constraints.len() != header.n_constraints as usize {
+            return Err(R1CSError::ConstraintCountMismatch {
+                declared: header.n_constraints,
+                parsed: circuit.constraints.len(),
+            });
+        }
+        let terms = |terms: ParsedTerms| terms.into_iter().map(|(wire_id, coefficient)| {
+            let coefficient = F::deserialize_uncompressed(&coefficient[..]).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            Ok(Term { wire_id, coefficient })
+        }).collect::<Result<Vec<_>, R1CSError>>();
+        let mut constraints = Vec::with_capacity(circuit.constraints.len());
+        for (idx, [a, b, c]) in circuit.constraints.into_iter().enumerate() {
+            let constraint = R1CSConstraint { a_terms: terms(a)?, b_terms: terms(b)?, c_terms: terms(c)? };
+            check_wire_ids(idx, &constraint, header.n_wires)?;
+            constraints.push(constraint);
+        }
+        
+        let wire_to_label = circuit.wire_to_label;
+        check_wire_map(&wire_to_label, &header)?;
+        let custom_gates = Self::read_custom_gates_section(&mut &circuit.custom_gates[..], header.field_size)?;
+        let custom_gate_uses = Self::read_custom_gate_uses_section(&mut &circuit.custom_gate_uses[..])?;
+        
+        Ok(Self {
+            header,
+            constraints,
+            wire_to_label,
+            custom_gates,
+            custom_gate_uses,
+        })
+    }
+    
+    /// Get summary information about the circuit, e.g. to emit as JSON
+    pub fn info(&self) -> R1CSInfo {
+        R1CSInfo {
+            field_size: self.header.field_size,
+            prime: format!("0x{}", to_hex(trim_le(&self.header.prime_bytes))),
+            num_wires: self.num_wires(),
+            num_public_outputs: self.num_public_outputs(),
+            num_public_inputs: self.num_public_inputs(),
+            num_private_inputs: self.num_private_inputs(),
+            num_labels: self.num_labels(),
+            num_constraints: self.num_constraints(),
+        }
+    }
+    
+    /// The whole circuit as JSON: `{"header": ..., "constraints": [...]}`, see
+    /// [`R1CSHeader::to_json`] and [`R1CSConstraint::to_json`]. For large
+    /// circuits, [`ConstraintIter::write_json`] writes the same document
+    /// without holding it in memory.
+    pub fn to_json(&self) -> serde_json::Value {
+        json!({
+            "header": self.header.to_json(),
+            "constraints": self.constraints.iter().map(R1CSConstraint::to_json).collect::<Vec<_>>(),
+        })
+    }
+    
+    /// Count the non-zero terms of each matrix and how many constraints
+    /// touch each wire
+    pub fn stats(&self) -> R1CSStats {
+        let non_zero = |terms: &[Term<F>]| terms.iter().filter(|t| !t.coefficient.is_zero()).count();
+        
+        let mut stats = R1CSStats {
+            num_constraints: self.constraints.len(),
+            a_terms: 0,
+            b_terms: 0,
+            c_terms: 0,
+            avg_terms_per_constraint: 0.0,
+            max_terms_per_constraint: 0,
+            max_terms_constraint: None,
+            wire_usage: BTreeMap::new(),
+            unconstrained_wires: self.unconstrained_wires(),
+        };
+        let mut uses = vec![0usize; self.num_wires() as usize];
+        let mut wires = Vec::new();
+        
+        for (idx, constraint) in self.constraints.iter().enumerate() {
+            let (a, b, c) = (non_zero(&constraint.a_terms), non_zero(&constraint.b_terms), non_zero(&constraint.c_terms));
+            stats.a_terms += a;
+            stats.b_terms += b;
+            stats.c_terms += c;
+            if stats.max_terms_constraint.is_none() || a + b + c > stats.max_terms_per_constraint {
+                stats.max_terms_per_constraint = a + b + c;
+                stats.max_terms_constraint = Some(idx);
+            }
+            
+            // Count each wire once per constraint, however many terms it has
+            wires.clear();
+            let all_terms = constraint.a_terms.iter().chain(&constraint.b_terms).chain(&constraint.c_terms);
+            wires.extend(all_terms.filter(|t| !t.coefficient.is_zero()).map(|t| t.wire_id as usize));
+            wires.sort_unstable();
+            wires.dedup();
+            for &wire in &wires {
+                if let Some(count) = uses.get_mut(wire) {
+                    *count += 1;
+                }
+            }
+        }
+        
+        if !self.constraints.is_empty() {
+            let total = stats.a_terms + stats.b_terms + stats.c_terms;
+            stats.avg_terms_per_constraint = total as f64 / self.constraints.len() as f64;
+        }
+        for count in uses {
+            *stats.wire_usage.entry(count).or_insert(0) += 1;
+        }
+        stats
+    }
+    
+    /// Wires that no constraint refers to with a non-zero coefficient,
+    /// leaving out ONE and the public and private inputs (which the prover
+    /// sets directly). In a correct circuit every output and intermediate
+    /// wire is constrained, so any wire returned here usually points to a
+    /// circom bug that lets the prover pick its value freely.
+    pub fn unconstrained_wires(&self) -> Vec<u32> {
+        let mut constrained = vec![false; self.num_wires() as usize];
+        for constraint in &self.constraints {
+            let all_terms = constraint.a_terms.iter().chain(&constraint.b_terms).chain(&constraint.c_terms);
+            for term in all_terms.filter(|t| !t.coefficient.is_zero()) {
+                if let Some(seen) = constrained.get_mut(term.wire_id as usize) {
+                    *seen = true;
+                }
+            }
+        }
+        
+        // Inputs come right after ONE and the outputs
+        let first_input = 1 + self.num_public_outputs();
+        let inputs = first_input..first_input + self.num_public_inputs() + self.num_private_inputs();
+        (1..self.num_wires())
+            .filter(|wire| !inputs.contains(wire) && !constrained[*wire as usize])
+            .collect()
+    }
+    
+    /// Print constraint statistics as a table
+    pub fn print_stats(&self) {
+        let stats = self.stats();
+        println!("R1CS Constraint Statistics:");
+        println!("  Constraints: {}", stats.num_constraints);
+        println!("  Non-zero terms: A {}, B {}, C {}", stats.a_terms, stats.b_terms, stats.c_terms);
+        match stats.max_terms_constraint {
+            Some(idx) => println!(
+                "  Terms per constraint: avg {:.2}, max {} (constraint #{})",
+                stats.avg_terms_per_constraint, stats.max_terms_per_constraint, idx
+            ),
+            None => println!("  Terms per constraint: -"),
+        }
+        println!("  Wires by number of constraints touching them:");
+        println!("    {:>12}  {:>8}", "constraints", "wires");
+        for (constraints, wires) in &stats.wire_usage {
+            println!("    {:>12}  {:>8}", constraints, wires);
+        }
+        print_unconstrained_warning(&stats.unconstrained_wires);
+    }
+    
     /// Print detailed information about the R1CS circuit
     pub fn print_info(&self) {
+        let info = self.info();
         println!("R1CS Circuit Information:");
-        println!("  Total wires: {}", self.num_wires());
-        println!("  Public outputs: {}", self.num_public_outputs());
-        println!("  Public inputs: {}", self.num_public_inputs());
-        println!("  Private inputs: {}", self.num_private_inputs());
-        println!("  Constraints: {}", self.num_constraints());
-        
-        // Print the first few bytes of the prime field modulus
-        let prime_bytes = self.prime_field_modulus();
-        let display_bytes = if prime_bytes.len() > 8 { 8 } else { prime_bytes.len() };
-        println!("  Prime field modulus (first {} bytes): {:?}", 
-                 display_bytes, &prime_bytes[..display_bytes]);
-    }
-}
-
-/// Simple A+B=C circuit for testing when no R1CS file is available
-pub fn create_hardcoded_r1cs() -> io::Result<R1CS> {
-    println!("Creating hardcoded R1CS for testing purposes...");
-    
-    // For now we'll just return an error - if needed, we can implement
-    // a hardcoded simple circuit later
-    Err(io::Error::new(
-        io::ErrorKind::NotFound,
-        "Hardcoded R1CS not implemented - please provide a valid R1CS file"
-    ))
-}
\ No newline at end of file
+        println!("  Total wires: {}", info.num_wires);
+        println!("  Public outputs: {}", info.num_public_outputs);
+        println!("  Public inputs: {}", info.num_public_inputs);
+        println!("  Private inputs: {}", info.num_private_inputs);
+        println!("  Constraints: {}", info.num_constraints);
+        println!("  Labels: {}", info.num_labels);
+        println!("  Prime field modulus: {}", info.prime);
+        if info.num_constraints == 0 {
+            println!("  Warning: the circuit has no constraints, so it can't be proven");
+        }
+        if self.uses_custom_gates() {
+            println!("  Warning: the circuit uses custom gates, so Groth16 can't prove it");
+        }
+        print_unconstrained_warning(&self.unconstrained_wires());
+    }
+}
+
+/// Lazily reads constraints from an R1CS file, see [`R1CS::constraints_iter`].
+///
+/// Yields `n_constraints` items, then an error if the constraints sections
+/// hold anything more, as [`R1CS::read`] would reject. It stops after the
+/// first error.
+pub struct ConstraintIter<F: PrimeField, R: Read + Seek> {
+    reader: R,
+    header: R1CSHeader,
+    remaining: u32,
+    /// Constraints sections not started yet, last section first
+    sections: Vec<Section>,
+    /// The constraints section being read
+    section: Option<Section>,
+    /// Whether the sections have been checked for leftovers, or an error
+    /// already ended the iteration
+    finished: bool,
+    _field: PhantomData<F>,
+}
+
+impl<F: PrimeField, R: Read + Seek> ConstraintIter<F, R> {
+    /// Get the header parsed when the file was opened
+    pub fn header(&self) -> &R1CSHeader {
+        &self.header
+    }
+    
+    /// Write the same JSON document as [`R1CS::to_json`], reading and writing
+    /// one constraint at a time, one per line
+    pub fn write_json<W: Write>(self, mut writer: W) -> Result<(), R1CSError> {
+        write!(writer, "{{\"header\":{},\"constraints\":[", self.header.to_json())?;
+        for (idx, constraint) in self.enumerate() {
+            let separator = if idx == 0 { "" } else { "," };
+            write!(writer, "{}\n{}", separator, constraint?.to_json())?;
+        }
+        writeln!(writer, "\n]}}")?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+impl<F: PrimeField, R: Read + Seek> ConstraintIter<F, R> {
+    // Move on to the next constraints section once the current one is used up
+    fn seek_to_constraint(&mut self, idx: usize) -> Result<(), R1CSError> {
+        loop {
+            let pos = self.reader.stream_position()?;
+            if self.section.is_some_and(|section| pos < section.offset + section.size) {
+                return Ok(());
+            }
+            self.finish_section()?;
+            let section = self.sections.pop().ok_or(R1CSError::ConstraintCountMismatch {
+                declared: self.header.n_constraints,
+                parsed: idx,
+            })?;
+            self.reader.seek(SeekFrom::Start(section.offset))?;
+            self.section = Some(section);
+        }
+    }
+    
+    // Check that the current section was consumed exactly, as `read_section`
+    // does: a constraint running past its end, or bytes left over after the
+    // last constraint, mean the layout isn't what we think it is
+    fn finish_section(&mut self) -> Result<(), R1CSError> {
+        if let Some(section) = self.section {
+            let consumed = self.reader.stream_position()? - section.offset;
+            if consumed != section.size {
+                return Err(R1CSError::SectionSizeMismatch {
+                    section_type: section.section_type,
+                    declared: section.size,
+                    consumed,
+                });
+            }
+        }
+        Ok(())
+    }
+    
+    // After the last constraint, the current section must be used up and any
+    // later constraints section empty
+    fn check_end(&mut self) -> Result<(), R1CSError> {
+        self.finish_section()?;
+        match self.sections.iter().rev().find(|section| section.size > 0) {
+            Some(section) => Err(R1CSError::SectionSizeMismatch {
+                section_type: section.section_type,
+                declared: section.size,
+                consumed: 0,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<F: PrimeField, R: Read + Seek> Iterator for ConstraintIter<F, R> {
+    type Item = Result<R1CSConstraint<F>, R1CSError>;
+    
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            if self.finished {
+                return None;
+            }
+            self.finished = true;
+            return self.check_end().err().map(Err);
+        }
+        
+        let idx = (self.header.n_constraints - self.remaining) as usize;
+        let constraint = self.seek_to_constraint(idx)
+            .and_then(|()| R1CS::read_constraint(&mut self.reader, &self.header, idx, None))
+            .and_then(|constraint| {
+                check_wire_ids(idx, &constraint, self.header.n_wires)?;
+                Ok(constraint)
+            });
+        if constraint.is_ok() {
+            self.remaining -= 1;
+        } else {
+            self.remaining = 0;
+            self.finished = true;
+        }
+        Some(constraint)
+    }
+    
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.remaining as usize + usize::from(!self.finished)))
+    }
+}
+
+impl<F: PrimeField> Index<usize> for R1CS<F> {
+    type Output = R1CSConstraint<F>;
+
+    /// Get the `idx`-th constraint, panicking if it is out of range (like `Vec`)
+    fn index(&self, idx: usize) -> &Self::Output {
+        &self.constraints[idx]
+    }
+}
+
+impl<F: PrimeField> AsRef<[R1CSConstraint<F>]> for R1CS<F> {
+    fn as_ref(&self) -> &[R1CSConstraint<F>] {
+        &self.constraints
+    }
+}
+
+/// A one-line summary, e.g. `R1CS{ wires: 128, pub: 3, priv: 124, constraints: 256 }`,
+/// where `priv` counts every wire that is neither ONE nor public
+impl<F: PrimeField> fmt::Display for R1CS<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let public = self.num_public_values();
+        write!(
+            f,
+            "R1CS{{ wires: {}, pub: {}, priv: {}, constraints: {} }}",
+            self.num_wires(),
+            public,
+            self.num_wires().saturating_sub(1).saturating_sub(public),
+            self.num_constraints()
+        )
+    }
+}
+
+/// Where a section's contents sit in an R1CS file
+#[derive(Debug, Clone, Copy)]
+struct Section {
+    section_type: u32,
+    offset: u64,
+    size: u64,
+}
+
+/// Read the preamble and record every section's type, offset and size,
+/// checking that each fits in the stream
+fn scan_sections<R: Read + Seek>(reader: &mut R) -> Result<(u32, Vec<Section>), R1CSError> {
+    let start = reader.stream_position()?;
+    let stream_len = reader.seek(SeekFrom::End(0))?;
+    reader.seek(SeekFrom::Start(start))?;
+    
+    let (version, num_sections) = read_preamble(reader)?;
+    let mut sections = Vec::new();
+    
+    for _ in 0..num_sections {
+        let section_type = reader.read_u32::<LittleEndian>()?;
+        let size = reader.read_u64::<LittleEndian>()?;
+        let offset = reader.stream_position()?;
+        
+        // Make sure the whole section is actually present in the file
+        let available = stream_len.saturating_sub(offset);
+        if size > available {
+            return Err(R1CSError::TruncatedSection {
+                section_type,
+                expected: size,
+                got: available,
+            });
+        }
+        
+        sections.push(Section { section_type, offset, size });
+        reader.seek(SeekFrom::Start(offset + size))?;
+    }
+    
+    Ok((version, sections))
+}
+
+/// A progress bar for parsing `n_constraints` constraints, or a hidden one
+/// that ignores updates when progress isn't wanted
+fn constraint_progress(enabled: bool, n_constraints: u32) -> ProgressBar {
+    if !enabled {
+        return ProgressBar::hidden();
+    }
+    let bar = ProgressBar::new(n_constraints as u64);
+    bar.set_style(
+        ProgressStyle::with_template("{msg} [{bar:40}] {pos}/{len} ({eta})")
+            .unwrap()
+            .progress_chars("=> "),
+    );
+    bar.set_message("Parsing constraints");
+    bar
+}
+
+/// Find the first section of the given type
+fn find_section(sections: &[Section], section_type: u32) -> Option<&Section> {
+    sections.iter().find(|section| section.section_type == section_type)
+}
+
+/// Seek to a section and parse it, checking that parsing consumed exactly
+/// the section's declared size (a short or long parse means the layout
+/// isn't what we think it is)
+fn read_section<R: Read + Seek, T>(
+    reader: &mut R,
+    section: &Section,
+    parse: impl FnOnce(&mut R) -> Result<T, R1CSError>,
+) -> Result<T, R1CSError> {
+    reader.seek(SeekFrom::Start(section.offset))?;
+    let value = parse(reader)?;
+    
+    let consumed = reader.stream_position()? - section.offset;
+    if consumed != section.size {
+        return Err(R1CSError::SectionSizeMismatch {
+            section_type: section.section_type,
+            declared: section.size,
+            consumed,
+        });
+    }
+    Ok(value)
+}
+
+/// Read the magic bytes, version and section count
+fn read_preamble<R: Read>(reader: &mut R) -> Result<(u32, u32), R1CSError> {
+    let mut preamble = Vec::with_capacity(12);
+    reader.take(12).read_to_end(&mut preamble)?;
+    
+    // Magic bytes "r1cs"; a short file that doesn't even start with them
+    // is reported as not being R1CS rather than as truncated
+    if preamble.len() >= 4 && &preamble[..4] != b"r1cs" {
+        return Err(R1CSError::BadMagic);
+    }
+    if preamble.len() < 12 {
+        return Err(R1CSError::TruncatedPreamble(preamble.len()));
+    }
+    
+    // Version: 1, or 2 with an extended header
+    let version = u32::from_le_bytes(preamble[4..8].try_into().unwrap());
+    if version != 1 && version != 2 {
+        return Err(R1CSError::UnsupportedVersion(version));
+    }
+    
+    Ok((version, u32::from_le_bytes(preamble[8..12].try_into().unwrap())))
+}
+
+/// Magic bytes and format version of [`R1CS::save_parsed`] dumps
+const PARSED_MAGIC: &[u8; 4] = b"qa1r";
+const PARSED_FORMAT: u32 = 2;
+
+/// The start of a [`R1CS::save_parsed`] dump, read before the circuit so a
+/// stale dump isn't decoded at all
+#[derive(Serialize, Deserialize)]
+struct ParsedKey {
+    magic: [u8; 4],
+    format: u32,
+    /// SHA-256 of the R1CS file the circuit was parsed from
+    source_sha256: [u8; 32],
+}
+
+/// A linear combination in a [`R1CS::save_parsed`] dump, as (wire,
+/// coefficient) pairs
+type ParsedTerms = Vec<(u32, Vec<u8>)>;
+
+/// The circuit in a [`R1CS::save_parsed`] dump. Field elements are arkworks'
+/// uncompressed encoding, and the custom gate lists are their R1CS sections.
+#[derive(Serialize, Deserialize)]
+struct ParsedCircuit {
+    header: R1CSHeader,
+    constraints: Vec<[ParsedTerms; 3]>,
+    wire_to_label: Vec<u64>,
+    custom_gates: Vec<u8>,
+    custom_gate_uses: Vec<u8>,
+}
+
+// bincode's options for parsed dumps: fixed-width integers, little-endian
+fn parsed_options() -> impl bincode::Options + Copy {
+    bincode::DefaultOptions::new().with_fixint_encoding()
+}
+
+// A dump bincode can't decode is corrupt, unless reading it failed
+fn parsed_error(e: bincode::ErrorKind) -> R1CSError {
+    match e {
+        bincode::ErrorKind::Io(e) => R1CSError::Io(e),
+        _ => R1CSError::BadParsedMagic,
+    }
+}
+
+/// The dump path [`ReadOptions::cache`] uses for an R1CS file, e.g.
+/// `circuit.r1cs.parsed`
+pub fn parsed_path(path: &Path) -> std::path::PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".parsed");
+    std::path::PathBuf::from(name)
+}
+
+// SHA-256 of a file's raw bytes, which keys its parsed dump
+fn file_digest(path: &Path) -> Result<[u8; 32], R1CSError> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut std::fs::File::open(path)?, &mut hasher)?;
+    Ok(hasher.finalize().into())
+}
+
+/// Reject a field size that isn't a whole number of 64-bit words up to
+/// [`MAX_FIELD_SIZE`], before anything is allocated from it
+pub(crate) fn check_field_size(field_size: u32) -> Result<(), R1CSError> {
+    if field_size == 0 || !field_size.is_multiple_of(8) || field_size > MAX_FIELD_SIZE {
+        return Err(R1CSError::InvalidFieldSize(field_size));
+    }
+    Ok(())
+}
+
+fn read_header_section<R: Read>(reader: &mut R, version: u32, section_size: u64) -> Result<R1CSHeader, R1CSError> {
+    // Read field element size (in bytes, not 64-bit words)
+    let field_size = reader.read_u32::<LittleEndian>()?;
+    debug!("  Field size: {} bytes", field_size);
+    check_field_size(field_size)?;
+    
+    // Read prime field modulus
+    let mut prime_bytes = vec![0u8; field_size as usize];
+    reader.read_exact(&mut prime_bytes)?;
+    
+    // Read number of wires
+    let n_wires = reader.read_u32::<LittleEndian>()?;
+    debug!("  Number of wires: {}", n_wires);
+    
+    // Read number of public outputs
+    let n_pub_out = reader.read_u32::<LittleEndian>()?;
+    debug!("  Number of public outputs: {}", n_pub_out);
+    
+    // Read number of public inputs
+    let n_pub_in = reader.read_u32::<LittleEndian>()?;
+    debug!("  Number of public inputs: {}", n_pub_in);
+    
+    // Read number of private inputs
+    let n_prvt_in = reader.read_u32::<LittleEndian>()?;
+    debug!("  Number of private inputs: {}", n_prvt_in);
+    
+    // Read number of labels
+    let n_labels = reader.read_u64::<LittleEndian>()?;
+    debug!("  Number of labels: {}", n_labels);
+    
+    // Read number of constraints
+    let n_constraints = reader.read_u32::<LittleEndian>()?;
+    debug!("  Number of constraints: {}", n_constraints);
+    
+    // Version 2 headers may go on with a custom gates flag, and possibly
+    // fields this parser doesn't know about, which are skipped
+    let mut use_custom_gates = false;
+    if version >= 2 {
+        let mut extra = section_size.saturating_sub(field_size as u64 + 32);
+        if extra >= 4 {
+            use_custom_gates = reader.read_u32::<LittleEndian>()? != 0;
+            debug!("  Uses custom gates: {}", use_custom_gates);
+            extra -= 4;
+        }
+        io::copy(&mut reader.by_ref().take(extra), &mut io::sink())?;
+    }
+    
+    let header = R1CSHeader {
+        field_size,
+        prime_bytes,
+        n_wires,
+        n_pub_out,
+        n_pub_in,
+        n_prvt_in,
+        n_labels,
+        n_constraints,
+        version,
+        use_custom_gates,
+    };
+    check_header_counts(&header)?;
+    Ok(header)
+}
+
+// circom's wire count also includes intermediate signals, so it can only be
+// larger than ONE plus the inputs, never smaller
+fn check_header_counts(header: &R1CSHeader) -> Result<(), R1CSError> {
+    let min_wires = 1 + header.n_pub_out as u64 + header.n_pub_in as u64 + header.n_prvt_in as u64;
+    if (header.n_wires as u64) < min_wires {
+        return Err(R1CSError::InconsistentHeader {
+            n_wires: header.n_wires,
+            n_pub_out: header.n_pub_out,
+            n_pub_in: header.n_pub_in,
+            n_prvt_in: header.n_prvt_in,
+        });
+    }
+    Ok(())
+}
+
+/// Find where each constraint starts in a constraints section. If the last
+/// constraint runs past the end of the section, returns how many bytes the
+/// section would need to hold it instead.
+fn constraint_offsets(bytes: &[u8], field_size: u32) -> Result<Vec<usize>, u64> {
+    let term_size = 4 + field_size as u64;
+    let len = bytes.len() as u64;
+    let mut offsets = Vec::new();
+    let mut pos = 0u64;
+    
+    while pos < len {
+        offsets.push(pos as usize);
+        // A, B and C linear combinations, each a term count followed by terms
+        for _ in 0..3 {
+            if pos + 4 > len {
+                return Err(pos + 4);
+            }
+            let start = pos as usize;
+            let n_terms = u32::from_le_bytes([bytes[start], bytes[start + 1], bytes[start + 2], bytes[start + 3]]);
+            pos += 4 + n_terms as u64 * term_size;
+        }
+    }
+    
+    if pos == len {
+        Ok(offsets)
+    } else {
+        Err(pos)
+    }
+}
+
+/// Check that a wire map, if present, has one label per wire and only labels
+/// the header declares
+fn check_wire_map(wire_to_label: &[u64], header: &R1CSHeader) -> Result<(), R1CSError> {
+    if !wire_to_label.is_empty() && wire_to_label.len() as u64 != header.n_wires as u64 {
+        return Err(R1CSError::WireMapSizeMismatch {
+            expected: header.n_wires,
+            got: wire_to_label.len() as u64,
+        });
+    }
+    if let Some((wire, &label)) = wire_to_label.iter().enumerate().find(|(_, &label)| label >= header.n_labels) {
+        return Err(R1CSError::LabelOutOfRange { wire: wire as u32, label, n_labels: header.n_labels });
+    }
+    Ok(())
+}
+
+/// Check that every term of a constraint refers to a wire of the circuit
+fn check_wire_ids<F: PrimeField>(
+    idx: usize,
+    constraint: &R1CSConstraint<F>,
+    n_wires: u32,
+) -> Result<(), R1CSError> {
+    let matrices = [
+        (Matrix::A, &constraint.a_terms),
+        (Matrix::B, &constraint.b_terms),
+        (Matrix::C, &constraint.c_terms),
+    ];
+    for (matrix, terms) in matrices {
+        if let Some(wire) = terms.iter().map(|term| term.wire_id).find(|&wire| wire >= n_wires) {
+            return Err(R1CSError::WireIndexOutOfRange {
+                constraint: idx,
+                matrix,
+                wire,
+                num_wires: n_wires,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Evaluate a linear combination against a wire assignment, or `None` if a
+/// term references a wire outside the assignment
+fn evaluate_terms<F: PrimeField>(terms: &[Term<F>], witness: &[F]) -> Option<F> {
+    terms.iter().try_fold(F::zero(), |acc, term| {
+        witness
+            .get(term.wire_id as usize)
+            .map(|value| acc + term.coefficient * value)
+    })
+}
+
+/// Strip the zero padding from the end of a little-endian integer
+pub(crate) fn trim_le(bytes: &[u8]) -> &[u8] {
+    let len = bytes.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+    &bytes[..len]
+}
+
+/// Warn about wires no constraint uses, listing the first few
+fn print_unconstrained_warning(wires: &[u32]) {
+    const SHOWN: usize = 10;
+    if wires.is_empty() {
+        return;
+    }
+    let mut list = wires.iter().take(SHOWN).map(u32::to_string).collect::<Vec<_>>().join(", ");
+    if wires.len() > SHOWN {
+        list.push_str(&format!(", ... ({} more)", wires.len() - SHOWN));
+    }
+    println!("  Warning: {} wires are not used by any constraint: {}", wires.len(), list);
+}
+
+/// Format a little-endian integer as big-endian hex
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().rev().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Convert a circom field element into the scalar field `F`.
+///
+/// circom writes field elements as `field_size`-byte little-endian integers in
+/// standard (non-Montgomery) form, so the bytes are interpreted as an integer
+/// and reduced into the field. A length that doesn't match `field_size` is
+/// reported as an error rather than silently truncated or padded.
+pub fn bytes_to_fr<F: PrimeField>(bytes: &[u8], field_size: u32) -> Result<F, R1CSError> {
+    if bytes.len() != field_size as usize {
+        return Err(R1CSError::FieldSizeMismatch {
+            expected: field_size,
+            got: bytes.len(),
+        });
+    }
+    Ok(F::from_le_bytes_mod_order(bytes))
+}
+
+/// Simple `x * y = z` circuit for testing when no R1CS file is available.
+///
+/// Wires follow circom's layout `[ONE, z, x, y]`: `z` is the single public
+/// output and `x`, `y` are private inputs, constrained by `x · y = z`.
+pub fn create_hardcoded_r1cs<F: PrimeField>() -> io::Result<R1CS<F>> {
+    info!("Creating hardcoded R1CS for testing purposes...");
+    
+    let term = |wire_id| Term { wire_id, coefficient: F::one() };
+    let constraints = vec![R1CSConstraint {
+        a_terms: vec![term(2)],
+        b_terms: vec![term(3)],
+        c_terms: vec![term(1)],
+    }];
+    
+    let prime_bytes = F::MODULUS.to_bytes_le();
+    let header = R1CSHeader {
+        field_size: prime_bytes.len() as u32,
+        prime_bytes,
+        n_wires: 4,
+        n_pub_out: 1,
+        n_pub_in: 0,
+        n_prvt_in: 2,
+        n_labels: 4,
+        n_constraints: constraints.len() as u32,
+        version: 1,
+        use_custom_gates: false,
+    };
+    
+    Ok(R1CS {
+        header,
+        constraints,
+        wire_to_label: vec![0, 1, 2, 3],
+        custom_gates: Vec::new(),
+        custom_gate_uses: Vec::new(),
+    })
+}
+
+/// Full wire assignment `[1, z, x, y]` for [`create_hardcoded_r1cs`], with
+/// `z = x * y` computed in the field, so it satisfies the circuit's one
+/// constraint.
+pub fn hardcoded_witness<F: PrimeField>(x: u64, y: u64) -> Vec<F> {
+    let (x, y) = (F::from(x), F::from(y));
+    vec![F::one(), x * y, x, y]
+}
@@ -0,0 +1,71 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use log::debug;
+use walkdir::WalkDir;
+
+/// Find every file under `root` whose name matches a glob `pattern`, going
+/// at most `max_depth` directories down (0 searches `root` itself only).
+///
+/// The pattern is matched against the file name alone, with `*` for any run
+/// of characters and `?` for any single one. Matches are returned sorted, so
+/// callers can report all of them when there is more than one. Directories
+/// that can't be read are skipped.
+pub fn find_files(root: &Path, pattern: &str, max_depth: usize) -> io::Result<Vec<PathBuf>> {
+    if !root.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("search root is not a directory: {}", root.display())
+        ));
+    }
+
+    // Files in `root` are at walkdir depth 1
+    let mut matches: Vec<PathBuf> = WalkDir::new(root)
+        .max_depth(max_depth + 1)
+        .into_iter()
+        .filter_map(|entry| match entry {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                debug!("Skipping unreadable entry during search: {}", e);
+                None
+            }
+        })
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| glob_match(pattern, &entry.file_name().to_string_lossy()))
+        .map(|entry| entry.into_path())
+        .collect();
+    matches.sort();
+
+    debug!("Found {} files matching {} under {}", matches.len(), pattern, root.display());
+    Ok(matches)
+}
+
+/// Match a name against a glob pattern with `*` and `?` wildcards
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    // Greedy matching, backtracking to the last `*` on a mismatch
+    let (mut p, mut n) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    star = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
@@ -0,0 +1,135 @@
+use ark_bls12_381::Bls12_381;
+use ark_bn254::Bn254;
+use ark_ec::pairing::Pairing;
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger, Field, One, PrimeField, Zero};
+use ark_groth16::{Proof, VerifyingKey};
+use serde_json::{json, Value};
+
+/// Pairing engines whose artifacts can be written in snarkjs JSON form
+pub trait SnarkjsCurve: Pairing {
+    /// The curve name snarkjs uses in its JSON files
+    const NAME: &'static str;
+}
+
+impl SnarkjsCurve for Bn254 {
+    const NAME: &'static str = "bn128";
+}
+
+impl SnarkjsCurve for Bls12_381 {
+    const NAME: &'static str = "bls12381";
+}
+
+// Field elements are written as base-10 strings of their canonical
+// (non-Montgomery) integer value. This goes through the `BigInt` rather than
+// the field element's `Display`, which prints zero as an empty string.
+fn field_to_string<F: PrimeField>(value: &F) -> String {
+    value.into_bigint().to_string()
+}
+
+/// Encode a base field element: a string for prime fields, and `[c0, c1]`
+/// for quadratic extensions such as G2's Fq2
+fn coordinate_to_json<T: Field>(value: &T) -> Value {
+    let mut elements: Vec<Value> = value
+        .to_base_prime_field_elements()
+        .map(|e| Value::String(field_to_string(&e)))
+        .collect();
+    if elements.len() == 1 {
+        elements.pop().unwrap()
+    } else {
+        Value::Array(elements)
+    }
+}
+
+/// Encode a curve point in snarkjs projective form `[x, y, z]`, using
+/// `["0", "0", "0"]` (all-zero Fq2s in G2) for the point at infinity.
+///
+/// snarkjs itself reads any `z = 0` as infinity, but its Solidity template
+/// copies `x` and `y` straight into the contract, where only `(0, 0)` is
+/// infinity to the EVM precompiles.
+pub fn point_to_json<G: AffineRepr>(point: &G) -> Value {
+    let zero = coordinate_to_json(&G::BaseField::zero());
+    match point.xy() {
+        Some((x, y)) => json!([coordinate_to_json(x), coordinate_to_json(y), coordinate_to_json(&G::BaseField::one())]),
+        None => json!([zero, zero, zero]),
+    }
+}
+
+/// Encode a pairing target field element (Fq12) as snarkjs nests it:
+/// two Fq6 halves of three Fq2 elements each
+fn target_to_json<T: Field>(value: &T) -> Value {
+    let elements: Vec<Value> = value
+        .to_base_prime_field_elements()
+        .map(|e| Value::String(field_to_string(&e)))
+        .collect();
+    let fq2s: Vec<Value> = elements.chunks(2).map(|c| Value::Array(c.to_vec())).collect();
+    Value::Array(fq2s.chunks(3).map(|c| Value::Array(c.to_vec())).collect())
+}
+
+/// Export a verifying key as a snarkjs `verification_key.json`.
+///
+/// `IC` has one point per public value plus one for the ONE wire, and
+/// `nPublic` is derived from it, so the two always agree.
+pub fn vk_to_json<E: SnarkjsCurve>(vk: &VerifyingKey<E>) -> Value {
+    let alphabeta = E::pairing(vk.alpha_g1, vk.beta_g2).0;
+
+    json!({
+        "protocol": "groth16",
+        "curve": E::NAME,
+        "nPublic": vk.gamma_abc_g1.len().saturating_sub(1),
+        "vk_alpha_1": point_to_json(&vk.alpha_g1),
+        "vk_beta_2": point_to_json(&vk.beta_g2),
+        "vk_gamma_2": point_to_json(&vk.gamma_g2),
+        "vk_delta_2": point_to_json(&vk.delta_g2),
+        "vk_alphabeta_12": target_to_json(&alphabeta),
+        "IC": vk.gamma_abc_g1.iter().map(point_to_json).collect::<Vec<_>>(),
+    })
+}
+
+/// Export a proof as a snarkjs `proof.json`
+pub fn proof_to_json<E: SnarkjsCurve>(proof: &Proof<E>) -> Value {
+    json!({
+        "pi_a": point_to_json(&proof.a),
+        "pi_b": point_to_json(&proof.b),
+        "pi_c": point_to_json(&proof.c),
+        "protocol": "groth16",
+        "curve": E::NAME,
+    })
+}
+
+/// Export public inputs as a snarkjs `public.json`
+pub fn public_inputs_to_json<F: PrimeField>(inputs: &[F]) -> Value {
+    Value::Array(inputs.iter().map(|input| Value::String(field_to_string(input))).collect())
+}
+
+/// Byte order of the hex public inputs written by [`public_inputs_to_hex_json`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endianness {
+    /// Least significant byte first, as arkworks serializes field elements
+    Little,
+    /// Most significant byte first, as Solidity's `uint256` and other EVM
+    /// tooling read a 32-byte word
+    Big,
+}
+
+/// Export public inputs as a JSON array of `0x` hex strings, each holding
+/// the field's full byte width (32 bytes for bn254 and bls12-381) in the
+/// given byte order.
+///
+/// snarkjs reads only [`public_inputs_to_json`]'s decimal strings, and
+/// reads any hex as a big-endian number, so little-endian output is for
+/// verifiers that expect raw little-endian words.
+pub fn public_inputs_to_hex_json<F: PrimeField>(inputs: &[F], endianness: Endianness) -> Value {
+    Value::Array(
+        inputs
+            .iter()
+            .map(|input| {
+                let mut bytes = input.into_bigint().to_bytes_le();
+                if endianness == Endianness::Big {
+                    bytes.reverse();
+                }
+                Value::String(format!("0x{}", hex::encode(bytes)))
+            })
+            .collect(),
+    )
+}
@@ -0,0 +1,272 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use ark_bls12_381::{Bls12_381, Fq, Fq2, Fr, G1Affine, G2Affine};
+use ark_ec::AffineRepr;
+use ark_ff::{One, PrimeField, Zero};
+use ark_groth16::{prepare_verifying_key, Groth16, Proof, VerifyingKey};
+use ark_relations::r1cs::SynthesisError;
+use num_bigint::BigUint;
+use serde_json::Value;
+
+/// Errors that can occur while importing snarkjs-format JSON artifacts
+#[derive(Debug)]
+pub enum ImportError {
+    /// Reading an artifact file failed
+    Io(io::Error),
+    /// An artifact file is not valid JSON
+    Json(serde_json::Error),
+    /// A required field is missing or has the wrong JSON shape
+    MissingField(String),
+    /// A coordinate is not a valid decimal field element
+    InvalidFieldElement(String),
+    /// A decimal value is not less than the field modulus. Reducing it would
+    /// let `x` and `x + p` pass as the same public input.
+    NonCanonical(String),
+    /// The artifact targets a curve other than bls12-381
+    UnsupportedCurve(String),
+    /// The decoded point does not lie on the curve
+    NotOnCurve(String),
+    /// The decoded point is on the curve but outside the prime-order subgroup
+    NotInSubgroup(String),
+    /// The G2 point is only valid with its Fq2 coordinates read as `[c1, c0]`
+    SwappedFq2Coordinates(String),
+    /// `nPublic` doesn't match the `IC` array, which holds one point more
+    PublicCountMismatch { n_public: u64, ic: usize },
+    /// arkworks rejected the imported artifacts during verification
+    Verification(SynthesisError),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Io(e) => write!(f, "I/O error: {}", e),
+            ImportError::Json(e) => write!(f, "invalid JSON: {}", e),
+            ImportError::MissingField(name) => write!(f, "missing or malformed field `{}`", name),
+            ImportError::InvalidFieldElement(name) => write!(f, "invalid field element in `{}`", name),
+            ImportError::NonCanonical(name) => write!(f, "field element in `{}` is not less than the field modulus", name),
+            ImportError::UnsupportedCurve(curve) => write!(f, "unsupported curve `{}` (expected bls12381)", curve),
+            ImportError::NotOnCurve(name) => write!(f, "point `{}` is not on the curve", name),
+            ImportError::NotInSubgroup(name) => write!(f, "point `{}` is not in the prime-order subgroup", name),
+            ImportError::SwappedFq2Coordinates(name) => write!(
+                f,
+                "point `{}` has its Fq2 coordinates in [c1, c0] order; snarkjs JSON uses [c0, c1]",
+                name
+            ),
+            ImportError::PublicCountMismatch { n_public, ic } => write!(
+                f,
+                "nPublic is {}, but IC has {} points; it must have nPublic + 1",
+                n_public, ic
+            ),
+            ImportError::Verification(e) => write!(f, "verification error: {}", e),
+        }
+    }
+}
+
+impl Error for ImportError {}
+
+impl From<io::Error> for ImportError {
+    fn from(e: io::Error) -> Self {
+        ImportError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for ImportError {
+    fn from(e: serde_json::Error) -> Self {
+        ImportError::Json(e)
+    }
+}
+
+impl From<SynthesisError> for ImportError {
+    fn from(e: SynthesisError) -> Self {
+        ImportError::Verification(e)
+    }
+}
+
+/// Read and parse a JSON file
+fn read_json<P: AsRef<Path>>(path: P) -> Result<Value, ImportError> {
+    let contents = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+/// Reject artifacts that declare a curve other than bls12-381
+fn check_curve(value: &Value) -> Result<(), ImportError> {
+    if let Some(curve) = value.get("curve").and_then(Value::as_str) {
+        if curve != "bls12381" {
+            return Err(ImportError::UnsupportedCurve(curve.to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// Get a field of a JSON object, reporting its name if it's missing
+fn field<'a>(value: &'a Value, name: &str) -> Result<&'a Value, ImportError> {
+    value.get(name).ok_or_else(|| ImportError::MissingField(name.to_string()))
+}
+
+/// Get the `idx`-th element of a JSON array
+fn element<'a>(value: &'a Value, idx: usize, name: &str) -> Result<&'a Value, ImportError> {
+    value
+        .as_array()
+        .and_then(|items| items.get(idx))
+        .ok_or_else(|| ImportError::MissingField(format!("{}[{}]", name, idx)))
+}
+
+/// Parse a decimal string into a field element, rejecting values not less
+/// than the modulus rather than reducing them
+fn decimal_from_json<F: PrimeField>(value: &Value, name: &str) -> Result<F, ImportError> {
+    let s = value
+        .as_str()
+        .filter(|s| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()))
+        .ok_or_else(|| ImportError::InvalidFieldElement(name.to_string()))?;
+    let n = BigUint::parse_bytes(s.as_bytes(), 10)
+        .ok_or_else(|| ImportError::InvalidFieldElement(name.to_string()))?;
+    if n >= F::MODULUS.into() {
+        return Err(ImportError::NonCanonical(name.to_string()));
+    }
+    Ok(F::from(n))
+}
+
+/// Parse a decimal-string base field element
+fn fq_from_json(value: &Value, name: &str) -> Result<Fq, ImportError> {
+    decimal_from_json(value, name)
+}
+
+/// Parse an Fq2 element given as `[c0, c1]`
+fn fq2_from_json(value: &Value, name: &str) -> Result<Fq2, ImportError> {
+    let c0 = fq_from_json(element(value, 0, name)?, name)?;
+    let c1 = fq_from_json(element(value, 1, name)?, name)?;
+    Ok(Fq2::new(c0, c1))
+}
+
+/// Parse a G1 point from snarkjs projective form `[x, y, z]`, where `z` is 1
+/// for affine points and 0 for the point at infinity
+pub fn g1_from_json(value: &Value, name: &str) -> Result<G1Affine, ImportError> {
+    let z = fq_from_json(element(value, 2, name)?, name)?;
+    if z.is_zero() {
+        return Ok(G1Affine::zero());
+    }
+    if !z.is_one() {
+        return Err(ImportError::InvalidFieldElement(format!("{} (z must be 0 or 1)", name)));
+    }
+
+    let x = fq_from_json(element(value, 0, name)?, name)?;
+    let y = fq_from_json(element(value, 1, name)?, name)?;
+    let point = G1Affine::new_unchecked(x, y);
+
+    if !point.is_on_curve() {
+        return Err(ImportError::NotOnCurve(name.to_string()));
+    }
+    if !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(ImportError::NotInSubgroup(name.to_string()));
+    }
+    Ok(point)
+}
+
+/// Parse a G2 point from snarkjs projective form `[[x0, x1], [y0, y1], [z0, z1]]`.
+///
+/// snarkjs writes Fq2 coordinates as `[c0, c1]` in its JSON files but as
+/// `[c1, c0]` in Solidity calldata, so a point copied from the wrong place is
+/// a common mistake. The decoded point is checked to be on the curve and in
+/// the prime-order subgroup; if it only passes with the coordinates swapped,
+/// a dedicated error is returned instead of silently accepting either order.
+pub fn g2_from_json(value: &Value, name: &str) -> Result<G2Affine, ImportError> {
+    let z = fq2_from_json(element(value, 2, name)?, name)?;
+    if z.is_zero() {
+        return Ok(G2Affine::zero());
+    }
+    if !z.is_one() {
+        return Err(ImportError::InvalidFieldElement(format!("{} (z must be 0 or 1)", name)));
+    }
+
+    let x = fq2_from_json(element(value, 0, name)?, name)?;
+    let y = fq2_from_json(element(value, 1, name)?, name)?;
+    let point = G2Affine::new_unchecked(x, y);
+
+    if !point.is_on_curve() {
+        let swapped = G2Affine::new_unchecked(Fq2::new(x.c1, x.c0), Fq2::new(y.c1, y.c0));
+        if swapped.is_on_curve() && swapped.is_in_correct_subgroup_assuming_on_curve() {
+            return Err(ImportError::SwappedFq2Coordinates(name.to_string()));
+        }
+        return Err(ImportError::NotOnCurve(name.to_string()));
+    }
+    if !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(ImportError::NotInSubgroup(name.to_string()));
+    }
+    Ok(point)
+}
+
+/// Import a snarkjs `verification_key.json` into an arkworks verifying key
+pub fn vk_from_json(value: &Value) -> Result<VerifyingKey<Bls12_381>, ImportError> {
+    check_curve(value)?;
+
+    let ic = field(value, "IC")?
+        .as_array()
+        .ok_or_else(|| ImportError::MissingField("IC".to_string()))?;
+    let gamma_abc_g1 = ic
+        .iter()
+        .enumerate()
+        .map(|(i, point)| g1_from_json(point, &format!("IC[{}]", i)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // `nPublic` is optional, but a key that states it must agree with itself
+    if let Some(n_public) = value.get("nPublic") {
+        let n_public = n_public
+            .as_u64()
+            .ok_or_else(|| ImportError::MissingField("nPublic".to_string()))?;
+        if ic.is_empty() || n_public != ic.len() as u64 - 1 {
+            return Err(ImportError::PublicCountMismatch { n_public, ic: ic.len() });
+        }
+    }
+
+    Ok(VerifyingKey {
+        alpha_g1: g1_from_json(field(value, "vk_alpha_1")?, "vk_alpha_1")?,
+        beta_g2: g2_from_json(field(value, "vk_beta_2")?, "vk_beta_2")?,
+        gamma_g2: g2_from_json(field(value, "vk_gamma_2")?, "vk_gamma_2")?,
+        delta_g2: g2_from_json(field(value, "vk_delta_2")?, "vk_delta_2")?,
+        gamma_abc_g1,
+    })
+}
+
+/// Import a snarkjs `proof.json` into an arkworks proof
+pub fn proof_from_json(value: &Value) -> Result<Proof<Bls12_381>, ImportError> {
+    check_curve(value)?;
+
+    Ok(Proof {
+        a: g1_from_json(field(value, "pi_a")?, "pi_a")?,
+        b: g2_from_json(field(value, "pi_b")?, "pi_b")?,
+        c: g1_from_json(field(value, "pi_c")?, "pi_c")?,
+    })
+}
+
+/// Import a snarkjs `public.json` (an array of decimal strings) as scalar
+/// field elements. Values must be less than the modulus, so that `x` and
+/// `x + p` can't both verify as the same input.
+pub fn public_inputs_from_json<F: PrimeField>(value: &Value) -> Result<Vec<F>, ImportError> {
+    let items = value
+        .as_array()
+        .ok_or_else(|| ImportError::MissingField("public inputs array".to_string()))?;
+
+    items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| decimal_from_json(item, &format!("public[{}]", i)))
+        .collect()
+}
+
+/// Verify a snarkjs Groth16 proof natively, given the paths of its
+/// `verification_key.json`, `public.json` and `proof.json`
+pub fn verify_from_snarkjs_artifacts<P: AsRef<Path>>(
+    vk_json: P,
+    public_json: P,
+    proof_json: P,
+) -> Result<bool, ImportError> {
+    let vk = vk_from_json(&read_json(vk_json)?)?;
+    let public_inputs = public_inputs_from_json::<Fr>(&read_json(public_json)?)?;
+    let proof = proof_from_json(&read_json(proof_json)?)?;
+
+    let pvk = prepare_verifying_key(&vk);
+    Ok(Groth16::<Bls12_381>::verify_proof(&pvk, &proof, &public_inputs)?)
+}
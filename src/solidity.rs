@@ -0,0 +1,207 @@
+use std::fmt::Write;
+use ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G2Affine};
+use ark_ec::AffineRepr;
+use ark_ff::{PrimeField, Zero};
+use ark_groth16::VerifyingKey;
+
+/// Generate a Solidity contract that verifies Groth16 proofs for a verifying
+/// key, like snarkjs' `Verifier.sol`.
+///
+/// Only bn254 is supported, as the EVM only has pairing precompiles for it.
+/// The contract's `verifyProof(pA, pB, pC, pubSignals)` takes the proof in
+/// EVM precompile order, where each G2 coordinate is `[c1, c0]`; this is the
+/// reverse of snarkjs' `proof.json`, and what `snarkjs zkey export
+/// soliditycalldata` produces.
+pub fn export_solidity_verifier(vk: &VerifyingKey<Bn254>) -> String {
+    let n_public = vk.gamma_abc_g1.len().saturating_sub(1);
+    let mut out = String::new();
+
+    out.push_str(HEADER);
+    writeln!(out, "    // Scalar field size").unwrap();
+    writeln!(out, "    uint256 constant r    = {};", Fr::MODULUS).unwrap();
+    writeln!(out, "    // Base field size").unwrap();
+    writeln!(out, "    uint256 constant q   = {};", Fq::MODULUS).unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "    // Verification Key data").unwrap();
+    g1_constants(&mut out, "alpha", &vk.alpha_g1);
+    g2_constants(&mut out, "beta", &vk.beta_g2);
+    g2_constants(&mut out, "gamma", &vk.gamma_g2);
+    g2_constants(&mut out, "delta", &vk.delta_g2);
+    writeln!(out).unwrap();
+    for (i, ic) in vk.gamma_abc_g1.iter().enumerate() {
+        let (x, y) = g1_xy(ic);
+        writeln!(out, "    uint256 constant IC{}x = {};", i, x).unwrap();
+        writeln!(out, "    uint256 constant IC{}y = {};", i, y).unwrap();
+        writeln!(out).unwrap();
+    }
+    out.push_str(MEMORY);
+
+    writeln!(
+        out,
+        "    function verifyProof(uint[2] calldata _pA, uint[2][2] calldata _pB, uint[2] calldata _pC, uint[{}] calldata _pubSignals) public view returns (bool) {{",
+        n_public
+    ).unwrap();
+    out.push_str(FUNCTIONS);
+
+    // vk_x = IC0 + sum(pubSignals[i] * IC[i + 1])
+    for i in 1..=n_public {
+        writeln!(out, "                g1_mulAccC(_pVk, IC{}x, IC{}y, calldataload(add(pubSignals, {})))", i, i, (i - 1) * 32).unwrap();
+    }
+    out.push_str(PAIRING);
+
+    for i in 0..n_public {
+        writeln!(out, "            checkField(calldataload(add(_pubSignals, {})))", i * 32).unwrap();
+    }
+    out.push_str(FOOTER);
+    out
+}
+
+// Affine coordinates as decimal strings, with the point at infinity as
+// (0, 0) as the EVM precompiles encode it
+fn g1_xy(point: &G1Affine) -> (String, String) {
+    let (x, y) = point.xy().map_or((Fq::zero(), Fq::zero()), |(x, y)| (*x, *y));
+    (x.into_bigint().to_string(), y.into_bigint().to_string())
+}
+
+fn g2_xy(point: &G2Affine) -> (Fq2, Fq2) {
+    point.xy().map_or((Fq2::zero(), Fq2::zero()), |(x, y)| (*x, *y))
+}
+
+fn g1_constants(out: &mut String, name: &str, point: &G1Affine) {
+    let (x, y) = g1_xy(point);
+    writeln!(out, "    uint256 constant {}x  = {};", name, x).unwrap();
+    writeln!(out, "    uint256 constant {}y  = {};", name, y).unwrap();
+}
+
+// The precompile takes each Fq2 coordinate imaginary part first, so `x1` is
+// c1 and `x2` is c0
+fn g2_constants(out: &mut String, name: &str, point: &G2Affine) {
+    let (x, y) = g2_xy(point);
+    let coordinates = [("x1", x.c1), ("x2", x.c0), ("y1", y.c1), ("y2", y.c0)];
+    for (suffix, value) in coordinates {
+        writeln!(out, "    uint256 constant {}{} = {};", name, suffix, value.into_bigint()).unwrap();
+    }
+}
+
+const HEADER: &str = "// SPDX-License-Identifier: GPL-3.0
+// Groth16 verifier generated by dogecoin_zkp_generator_qa1
+
+pragma solidity >=0.7.0 <0.9.0;
+
+contract Groth16Verifier {
+";
+
+const MEMORY: &str = "    // Memory data
+    uint16 constant pVk = 0;
+    uint16 constant pPairing = 128;
+
+    uint16 constant pLastMem = 896;
+
+";
+
+const FUNCTIONS: &str = "        assembly {
+            function checkField(v) {
+                if iszero(lt(v, r)) {
+                    mstore(0, 0)
+                    return(0, 0x20)
+                }
+            }
+
+            // G1 function to multiply a G1 value(x,y) to value in an address
+            function g1_mulAccC(pR, x, y, s) {
+                let success
+                let mIn := mload(0x40)
+                mstore(mIn, x)
+                mstore(add(mIn, 32), y)
+                mstore(add(mIn, 64), s)
+
+                success := staticcall(sub(gas(), 2000), 7, mIn, 96, mIn, 64)
+
+                if iszero(success) {
+                    mstore(0, 0)
+                    return(0, 0x20)
+                }
+
+                mstore(add(mIn, 64), mload(pR))
+                mstore(add(mIn, 96), mload(add(pR, 32)))
+
+                success := staticcall(sub(gas(), 2000), 6, mIn, 128, pR, 64)
+
+                if iszero(success) {
+                    mstore(0, 0)
+                    return(0, 0x20)
+                }
+            }
+
+            function checkPairing(pA, pB, pC, pubSignals, pMem) -> isOk {
+                let _pPairing := add(pMem, pPairing)
+                let _pVk := add(pMem, pVk)
+
+                mstore(_pVk, IC0x)
+                mstore(add(_pVk, 32), IC0y)
+
+                // Compute the linear combination vk_x
+";
+
+const PAIRING: &str = "
+                // -A
+                mstore(_pPairing, calldataload(pA))
+                mstore(add(_pPairing, 32), mod(sub(q, calldataload(add(pA, 32))), q))
+
+                // B
+                mstore(add(_pPairing, 64), calldataload(pB))
+                mstore(add(_pPairing, 96), calldataload(add(pB, 32)))
+                mstore(add(_pPairing, 128), calldataload(add(pB, 64)))
+                mstore(add(_pPairing, 160), calldataload(add(pB, 96)))
+
+                // alpha1
+                mstore(add(_pPairing, 192), alphax)
+                mstore(add(_pPairing, 224), alphay)
+
+                // beta2
+                mstore(add(_pPairing, 256), betax1)
+                mstore(add(_pPairing, 288), betax2)
+                mstore(add(_pPairing, 320), betay1)
+                mstore(add(_pPairing, 352), betay2)
+
+                // vk_x
+                mstore(add(_pPairing, 384), mload(add(pMem, pVk)))
+                mstore(add(_pPairing, 416), mload(add(pMem, add(pVk, 32))))
+
+                // gamma2
+                mstore(add(_pPairing, 448), gammax1)
+                mstore(add(_pPairing, 480), gammax2)
+                mstore(add(_pPairing, 512), gammay1)
+                mstore(add(_pPairing, 544), gammay2)
+
+                // C
+                mstore(add(_pPairing, 576), calldataload(pC))
+                mstore(add(_pPairing, 608), calldataload(add(pC, 32)))
+
+                // delta2
+                mstore(add(_pPairing, 640), deltax1)
+                mstore(add(_pPairing, 672), deltax2)
+                mstore(add(_pPairing, 704), deltay1)
+                mstore(add(_pPairing, 736), deltay2)
+
+                let success := staticcall(sub(gas(), 2000), 8, _pPairing, 768, _pPairing, 0x20)
+
+                isOk := and(success, mload(_pPairing))
+            }
+
+            let pMem := mload(0x40)
+            mstore(0x40, add(pMem, pLastMem))
+
+            // Validate that all evaluations ∈ F
+";
+
+const FOOTER: &str = "
+            // Validate all evaluations
+            let isValid := checkPairing(_pA, _pB, _pC, _pubSignals, pMem)
+
+            mstore(0, isValid)
+            return(0, 0x20)
+        }
+    }
+}
+";
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use ark_ff::PrimeField;
+use crate::r1cs::{R1CSConstraint, Term, R1CS};
+
+/// Errors that can occur while solving for a witness
+#[derive(Debug)]
+pub enum SolveError {
+    /// An input is given for a wire the circuit doesn't have
+    InputOutOfRange { wire: usize, num_wires: u32 },
+    /// No constraint pins these wires down to a single value
+    Unresolved(Vec<usize>),
+    /// The inputs, with the solved wires, don't satisfy this constraint
+    Unsatisfied(usize),
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::InputOutOfRange { wire, num_wires } => write!(
+                f,
+                "Input given for wire {}, but the circuit has {} wires",
+                wire, num_wires
+            ),
+            SolveError::Unresolved(wires) => write!(
+                f,
+                "Could not solve for {} wires: {:?}; an input may be missing, or the circuit needs circom's witness calculator",
+                wires.len(), wires
+            ),
+            SolveError::Unsatisfied(idx) => write!(f, "The inputs do not satisfy constraint #{}", idx),
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+impl<F: PrimeField> R1CS<F> {
+    /// Compute a full witness from some of its wire values, typically the
+    /// circuit inputs, keyed by wire index. Wire 0 is always ONE.
+    ///
+    /// Repeatedly walks the constraints and, wherever exactly one wire is
+    /// still unknown and it appears linearly, solves for it, until nothing
+    /// changes. This only works for circuits whose constraints determine
+    /// each wire in turn, as plain circom arithmetic does; wires computed
+    /// outside the constraints (e.g. by `<--` for bit decompositions or
+    /// inverses) can't be recovered, and are returned as unresolved.
+    pub fn solve_witness(&self, inputs: &HashMap<usize, F>) -> Result<Vec<F>, SolveError> {
+        let num_wires = self.num_wires();
+        let mut values: Vec<Option<F>> = vec![None; num_wires as usize];
+        for (&wire, &value) in inputs {
+            match values.get_mut(wire) {
+                Some(slot) => *slot = Some(value),
+                None => return Err(SolveError::InputOutOfRange { wire, num_wires }),
+            }
+        }
+        if let Some(one) = values.first_mut() {
+            *one = Some(F::one());
+        }
+
+        let mut pending: Vec<&R1CSConstraint<F>> = self.constraints().iter().collect();
+        loop {
+            let before = pending.len();
+            pending.retain(|constraint| !solve_constraint(constraint, &mut values));
+            if pending.len() == before {
+                break;
+            }
+        }
+
+        let unresolved: Vec<usize> = values
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| value.is_none())
+            .map(|(wire, _)| wire)
+            .collect();
+        if !unresolved.is_empty() {
+            return Err(SolveError::Unresolved(unresolved));
+        }
+
+        let witness: Vec<F> = values.into_iter().flatten().collect();
+        self.check_witness(&witness).map_err(SolveError::Unsatisfied)?;
+        Ok(witness)
+    }
+}
+
+/// A linear combination split into its value over the known wires and the
+/// coefficient of the single unknown wire
+struct Partial<F> {
+    known: F,
+    unknown: F,
+}
+
+// Try to solve a constraint for its one unknown wire, returning true once the
+// constraint has no unknown wires left
+fn solve_constraint<F: PrimeField>(constraint: &R1CSConstraint<F>, values: &mut [Option<F>]) -> bool {
+    let mut unknown = None;
+    let all_terms = constraint.a_terms.iter().chain(&constraint.b_terms).chain(&constraint.c_terms);
+    for term in all_terms {
+        let wire = term.wire_id as usize;
+        if values.get(wire).copied().flatten().is_none() {
+            match unknown {
+                None => unknown = Some(wire),
+                Some(other) if other == wire => {}
+                // Two or more unknowns: wait for other constraints to fix them
+                Some(_) => return false,
+            }
+        }
+    }
+    let wire = match unknown {
+        Some(wire) if wire < values.len() => wire,
+        Some(_) => return false,
+        None => return true,
+    };
+
+    let a = partial(&constraint.a_terms, wire, values);
+    let b = partial(&constraint.b_terms, wire, values);
+    let c = partial(&constraint.c_terms, wire, values);
+
+    // (a + ka·w)(b + kb·w) = c + kc·w is only linear in w if ka or kb is zero,
+    // and then it's w·(ka·b + kb·a - kc) = c - a·b
+    if !a.unknown.is_zero() && !b.unknown.is_zero() {
+        return false;
+    }
+    let denominator = a.unknown * b.known + b.unknown * a.known - c.unknown;
+    match denominator.inverse() {
+        Some(inverse) => {
+            values[wire] = Some((c.known - a.known * b.known) * inverse);
+            true
+        }
+        None => false,
+    }
+}
+
+fn partial<F: PrimeField>(terms: &[Term<F>], wire: usize, values: &[Option<F>]) -> Partial<F> {
+    let mut result = Partial { known: F::zero(), unknown: F::zero() };
+    for term in terms {
+        let id = term.wire_id as usize;
+        if id == wire {
+            result.unknown += term.coefficient;
+        } else if let Some(Some(value)) = values.get(id) {
+            result.known += term.coefficient * value;
+        }
+    }
+    result
+}
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use log::info;
+use crate::r1cs::R1CSError;
+
+/// One line of a circom `.sym` file
+#[derive(Debug, Clone)]
+pub struct Symbol {
+    pub label_id: u64,
+    /// Wire index, or `None` if circom optimized the signal away (`-1`)
+    pub wire_id: Option<u32>,
+    pub component_id: u64,
+    pub name: String,
+}
+
+/// Signal names from a circom `.sym` file, used to label wires
+#[derive(Debug, Clone, Default)]
+pub struct SymbolTable {
+    symbols: Vec<Symbol>,
+    by_wire: HashMap<u32, usize>,
+    by_label: HashMap<u64, usize>,
+    by_name: HashMap<String, usize>,
+}
+
+impl SymbolTable {
+    /// Read and parse a `.sym` file, one `labelId,varIdx,componentId,signalName`
+    /// entry per line
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self, R1CSError> {
+        info!("Reading symbol file from: {}", path.as_ref().display());
+        
+        let contents = fs::read_to_string(&path)?;
+        let mut table = Self::default();
+        
+        for (idx, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            
+            // The signal name itself may contain commas, so split at most 4 ways
+            let mut fields = line.splitn(4, ',');
+            let malformed = || R1CSError::MalformedSymbolLine(idx + 1);
+            let mut next = || fields.next().map(str::trim).ok_or_else(malformed);
+            
+            let label_id = next()?.parse().map_err(|_| malformed())?;
+            let var_idx: i64 = next()?.parse().map_err(|_| malformed())?;
+            let component_id = next()?.parse().map_err(|_| malformed())?;
+            let name = next()?.to_string();
+            
+            let wire_id = if var_idx < 0 {
+                None
+            } else {
+                Some(u32::try_from(var_idx).map_err(|_| malformed())?)
+            };
+            table.push(Symbol { label_id, wire_id, component_id, name });
+        }
+        
+        info!("Loaded {} symbols", table.len());
+        Ok(table)
+    }
+    
+    fn push(&mut self, symbol: Symbol) {
+        // Several signals can share a wire; keep the first name, which circom
+        // writes for the outermost component
+        if let Some(wire_id) = symbol.wire_id {
+            self.by_wire.entry(wire_id).or_insert(self.symbols.len());
+        }
+        self.by_label.insert(symbol.label_id, self.symbols.len());
+        self.by_name.insert(symbol.name.clone(), self.symbols.len());
+        self.symbols.push(symbol);
+    }
+    
+    /// Get the signal name for a wire
+    pub fn name(&self, wire_id: u32) -> Option<&str> {
+        self.by_wire.get(&wire_id).map(|&idx| self.symbols[idx].name.as_str())
+    }
+    
+    /// Get the signal name for a label id, as an R1CS wire map gives them
+    pub fn label_name(&self, label_id: u64) -> Option<&str> {
+        self.by_label.get(&label_id).map(|&idx| self.symbols[idx].name.as_str())
+    }
+    
+    /// Look up a signal by its full name (e.g. `main.in[0]`)
+    pub fn get(&self, name: &str) -> Option<&Symbol> {
+        self.by_name.get(name).map(|&idx| &self.symbols[idx])
+    }
+    
+    /// Get all symbols, in file order
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+    
+    /// Get the number of symbols
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+    
+    /// Check whether the table has no symbols
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+}
@@ -0,0 +1,397 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::path::Path;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use log::info;
+use num_bigint::BigUint;
+use ark_bls12_381::Fr;
+use ark_ff::{BigInteger, PrimeField};
+use serde_json::Value;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+use crate::circuit::CircuitError;
+use crate::r1cs::{bytes_to_fr, check_field_size, field_bytes, trim_le, R1CSError, R1CS};
+use crate::snarkjs_export::Endianness;
+use crate::sym::SymbolTable;
+
+/// Witness values loaded from a snarkjs `.wtns` file, decoded into the
+/// scalar field `F` (bls12-381's `Fr` by default).
+///
+/// The values include the private inputs, so they are zeroized on drop.
+#[derive(Debug, Clone)]
+pub struct Witness<F: PrimeField = Fr> {
+    pub field_size: u32,
+    pub prime_bytes: Vec<u8>,
+    pub values: Vec<F>,
+}
+
+impl<F: PrimeField> Witness<F> {
+    /// Read and parse a snarkjs `.wtns` file.
+    ///
+    /// Like the R1CS parser, this trusts none of the file's sizes: the field
+    /// size must be sane, and each section must fit in the file and be
+    /// consumed exactly. The values are only allocated for once their section
+    /// is known to hold exactly the declared count, and in one go, so no
+    /// reallocation frees a copy of them that was never zeroized.
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self, R1CSError> {
+        info!("Reading witness file from: {}", path.as_ref().display());
+
+        let mut file = io::BufReader::new(File::open(&path)?);
+        let file_len = file.get_ref().metadata()?.len();
+
+        // Read magic bytes "wtns"
+        let mut magic = [0u8; 4];
+        file.read_exact(&mut magic)?;
+
+        if &magic != b"wtns" {
+            return Err(R1CSError::BadWitnessMagic);
+        }
+
+        // Read version (snarkjs writes version 2, older tools version 1)
+        let version = file.read_u32::<LittleEndian>()?;
+        if version != 1 && version != 2 {
+            return Err(R1CSError::UnsupportedVersion(version));
+        }
+
+        let num_sections = file.read_u32::<LittleEndian>()?;
+
+        let mut field_size = 0;
+        let mut prime_bytes = Vec::new();
+        let mut n_witness = 0;
+        // Zeroized if reading fails part way through
+        let mut values = Zeroizing::new(Vec::new());
+
+        for _ in 0..num_sections {
+            let section_type = file.read_u32::<LittleEndian>()?;
+            let section_size = file.read_u64::<LittleEndian>()?;
+            let offset = file.stream_position()?;
+            
+            // Make sure the whole section is actually present in the file
+            let available = file_len.saturating_sub(offset);
+            if section_size > available {
+                return Err(R1CSError::TruncatedSection {
+                    section_type,
+                    expected: section_size,
+                    got: available,
+                });
+            }
+
+            // Reads past the section's end fail instead of running into the next one
+            let mut section = (&mut file).take(section_size);
+            match section_type {
+                1 => { // Header section: field size, prime, number of witness values
+                    field_size = section.read_u32::<LittleEndian>()?;
+                    check_field_size(field_size)?;
+                    prime_bytes = vec![0u8; field_size as usize];
+                    section.read_exact(&mut prime_bytes)?;
+                    n_witness = section.read_u32::<LittleEndian>()?;
+                }
+                2 => { // Witness values section
+                    if field_size == 0 {
+                        return Err(R1CSError::MissingHeader);
+                    }
+                    // The header's count is only trusted once the section,
+                    // which fits in the file, holds exactly that many values
+                    let expected = n_witness as u64 * field_size as u64;
+                    if expected != section_size {
+                        return Err(R1CSError::SectionSizeMismatch {
+                            section_type,
+                            declared: section_size,
+                            consumed: expected,
+                        });
+                    }
+                    let mut value_bytes = vec![0u8; field_size as usize];
+                    values.zeroize();
+                    values.reserve_exact((section_size / field_size as u64) as usize);
+                    for _ in 0..n_witness {
+                        section.read_exact(&mut value_bytes)?;
+                        values.push(bytes_to_fr(&value_bytes, field_size)?);
+                    }
+                    value_bytes.zeroize();
+                }
+                _ => {
+                    file.seek(SeekFrom::Start(offset + section_size))?;
+                    continue;
+                }
+            }
+            
+            let consumed = file.stream_position()? - offset;
+            if consumed != section_size {
+                return Err(R1CSError::SectionSizeMismatch {
+                    section_type,
+                    declared: section_size,
+                    consumed,
+                });
+            }
+        }
+
+        info!("Loaded {} witness values", values.len());
+
+        Ok(Self {
+            field_size,
+            prime_bytes,
+            values: std::mem::take(&mut *values),
+        })
+    }
+
+    /// Write the witness as a snarkjs `.wtns` file
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<(), R1CSError> {
+        let mut writer = io::BufWriter::new(File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+    
+    /// Write the witness in the layout snarkjs writes: version 2, a header
+    /// section with the field size, prime and value count, then the values
+    /// section, every number little-endian in `field_size` bytes. Reading the
+    /// output back gives the same witness.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> Result<(), R1CSError> {
+        let field_size = self.field_size;
+        
+        writer.write_all(b"wtns")?;
+        writer.write_u32::<LittleEndian>(2)?;
+        writer.write_u32::<LittleEndian>(2)?;
+        
+        writer.write_u32::<LittleEndian>(1)?;
+        writer.write_u64::<LittleEndian>(4 + field_size as u64 + 4)?;
+        writer.write_u32::<LittleEndian>(field_size)?;
+        writer.write_all(&field_bytes(&self.prime_bytes, field_size))?;
+        writer.write_u32::<LittleEndian>(self.values.len() as u32)?;
+        
+        writer.write_u32::<LittleEndian>(2)?;
+        writer.write_u64::<LittleEndian>(self.values.len() as u64 * field_size as u64)?;
+        for value in &self.values {
+            // The values include the private inputs
+            let mut bytes = field_bytes(&value.into_bigint().to_bytes_le(), field_size);
+            let written = writer.write_all(&bytes);
+            bytes.zeroize();
+            written?;
+        }
+        Ok(())
+    }
+    
+    /// Get the number of witness values
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Check whether the witness has no values
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+    
+    /// Check that the witness file's prime is the R1CS prime. Values
+    /// computed over another curve would decode without error, but into
+    /// meaningless field elements.
+    pub fn assert_prime_matches(&self, r1cs: &R1CS<F>) -> Result<(), R1CSError> {
+        let (ours, theirs) = (trim_le(&self.prime_bytes), trim_le(r1cs.prime_field_modulus()));
+        if ours != theirs {
+            return Err(R1CSError::WitnessCurveMismatch {
+                r1cs: theirs.to_vec(),
+                witness: ours.to_vec(),
+            });
+        }
+        Ok(())
+    }
+    
+    /// Check that the witness can be `r1cs`'s wire assignment: for its
+    /// prime, one value per wire, and 1 for the ONE wire. Terms on wire 0
+    /// are constants, which the circuit multiplies by arkworks' ONE rather
+    /// than this value, so any other value would make the parsed
+    /// constraints and the proved ones disagree.
+    pub fn assert_fits(&self, r1cs: &R1CS<F>) -> Result<(), CircuitError> {
+        self.assert_prime_matches(r1cs)?;
+        if self.len() != r1cs.num_wires() as usize {
+            return Err(CircuitError::WitnessLengthMismatch {
+                expected: r1cs.num_wires(),
+                got: self.len(),
+            });
+        }
+        if self.values.first().is_some_and(|value| !value.is_one()) {
+            return Err(CircuitError::OneWireNotOne);
+        }
+        Ok(())
+    }
+    
+    /// Get the public output values: circom puts them on wires
+    /// `1..=n_pub_out`, right after ONE
+    pub fn public_outputs(&self, r1cs: &R1CS<F>) -> Vec<F> {
+        self.wires(1, r1cs.num_public_outputs())
+    }
+    
+    /// Get the public input values: the `n_pub_in` wires after the outputs
+    pub fn public_inputs(&self, r1cs: &R1CS<F>) -> Vec<F> {
+        self.wires(1 + r1cs.num_public_outputs() as usize, r1cs.num_public_inputs())
+    }
+    
+    // Up to `count` values starting at wire `start`; a witness too short for
+    // the circuit yields fewer
+    fn wires(&self, start: usize, count: u32) -> Vec<F> {
+        self.values.iter().skip(start).take(count as usize).copied().collect()
+    }
+}
+
+/// Format field elements as decimal strings, as snarkjs writes them
+pub fn to_decimal_strings<F: PrimeField>(values: &[F]) -> Vec<String> {
+    values.iter().map(|value| value.into_bigint().to_string()).collect()
+}
+
+// Zero every value in place, keeping the witness length
+impl<F: PrimeField> Zeroize for Witness<F> {
+    fn zeroize(&mut self) {
+        self.values.iter_mut().for_each(Zeroize::zeroize);
+    }
+}
+
+impl<F: PrimeField> Drop for Witness<F> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<F: PrimeField> ZeroizeOnDrop for Witness<F> {}
+
+/// Load public inputs from a JSON array, as in snarkjs `public.json`.
+///
+/// Entries may be decimal strings or `0x`-prefixed hex strings (plain JSON
+/// numbers are accepted too); values must be less than the field modulus.
+pub fn load_public_inputs<F: PrimeField, P: AsRef<Path>>(path: P) -> Result<Vec<F>, R1CSError> {
+    load_public_inputs_with_endianness(path, Endianness::Big)
+}
+
+/// Load public inputs like [`load_public_inputs`], reading `0x` hex entries
+/// in the given byte order, e.g. little-endian as written by
+/// [`public_inputs_to_hex_json`](crate::snarkjs_export::public_inputs_to_hex_json).
+/// Decimal entries are unaffected.
+pub fn load_public_inputs_with_endianness<F: PrimeField, P: AsRef<Path>>(
+    path: P,
+    endianness: Endianness,
+) -> Result<Vec<F>, R1CSError> {
+    info!("Reading public inputs from: {}", path.as_ref().display());
+    
+    let json: Value = serde_json::from_str(&fs::read_to_string(&path)?)?;
+    let items = match json {
+        Value::Array(items) => items,
+        other => {
+            return Err(R1CSError::InvalidPublicInput {
+                index: 0,
+                value: other.to_string(),
+            })
+        }
+    };
+    
+    items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let text = match item {
+                Value::String(s) => s.clone(),
+                Value::Number(n) => n.to_string(),
+                other => other.to_string(),
+            };
+            parse_field_element_with_endianness(text.trim(), endianness).ok_or(R1CSError::InvalidPublicInput {
+                index,
+                value: text,
+            })
+        })
+        .collect()
+}
+
+/// Load circuit inputs from a circom `input.json`, keyed by wire index.
+///
+/// Each top-level key names a signal of the main component; arrays expand
+/// to indexed signals (`in[0]`, `in[1][2]`, ...) and nested objects to
+/// dotted ones. The symbol file maps the full names (`main.in[0]`) to
+/// wires. Signals circom optimized away have no wire and are skipped.
+pub fn load_circuit_inputs<F: PrimeField, P: AsRef<Path>>(
+    path: P,
+    symbols: &SymbolTable,
+) -> Result<HashMap<usize, F>, R1CSError> {
+    info!("Reading circuit inputs from: {}", path.as_ref().display());
+    
+    let json: Value = serde_json::from_str(&fs::read_to_string(&path)?)?;
+    let mut leaves = Vec::new();
+    flatten_input("main".to_string(), &json, &mut leaves);
+    
+    let mut inputs = HashMap::new();
+    for (signal, value) in leaves {
+        let symbol = symbols.get(&signal).ok_or_else(|| R1CSError::UnknownSignal(signal.clone()))?;
+        let text = match value {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        let element = parse_field_element(text.trim())
+            .ok_or(R1CSError::InvalidInputValue { signal, value: text })?;
+        if let Some(wire_id) = symbol.wire_id {
+            inputs.insert(wire_id as usize, element);
+        }
+    }
+    
+    info!("Loaded {} circuit inputs", inputs.len());
+    Ok(inputs)
+}
+
+// Flatten nested input arrays and objects into (signal name, value) pairs
+fn flatten_input<'a>(name: String, value: &'a Value, leaves: &mut Vec<(String, &'a Value)>) {
+    match value {
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                flatten_input(format!("{}[{}]", name, i), item, leaves);
+            }
+        }
+        Value::Object(fields) => {
+            for (key, item) in fields {
+                flatten_input(format!("{}.{}", name, key), item, leaves);
+            }
+        }
+        _ => leaves.push((name, value)),
+    }
+}
+
+/// Parse a decimal or `0x` hex string into a canonical field element,
+/// rejecting values not less than the modulus
+pub fn parse_field_element<F: PrimeField>(text: &str) -> Option<F> {
+    parse_field_element_with_endianness(text, Endianness::Big)
+}
+
+/// Parse a field element like [`parse_field_element`], reading `0x` hex in
+/// the given byte order. Little-endian hex must be whole bytes, as there is
+/// no telling which end a lone digit belongs to.
+pub fn parse_field_element_with_endianness<F: PrimeField>(text: &str, endianness: Endianness) -> Option<F> {
+    let digits = match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(digits) => digits,
+        None => return parse_decimal(text),
+    };
+    if endianness == Endianness::Little && digits.len() % 2 == 1 {
+        return None;
+    }
+    
+    // Left-pad to whole bytes, then drop leading zero bytes
+    let padded = if digits.len() % 2 == 1 { format!("0{}", digits) } else { digits.to_string() };
+    let mut be_bytes = hex::decode(padded).ok()?;
+    if endianness == Endianness::Little {
+        be_bytes.reverse();
+    }
+    let start = be_bytes.iter().position(|&b| b != 0).unwrap_or(be_bytes.len());
+    let be_bytes = &be_bytes[start..];
+    
+    // Reject values that would otherwise be silently reduced
+    let modulus = F::MODULUS.to_bytes_be();
+    let modulus = &modulus[modulus.iter().position(|&b| b != 0).unwrap_or(0)..];
+    if be_bytes.len() > modulus.len() || (be_bytes.len() == modulus.len() && be_bytes >= modulus) {
+        return None;
+    }
+    
+    Some(F::from_be_bytes_mod_order(be_bytes))
+}
+
+// Parse a decimal field element, rejecting values not less than the modulus
+// rather than reducing them like `F::from_str`
+fn parse_decimal<F: PrimeField>(text: &str) -> Option<F> {
+    if text.is_empty() || !text.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let value = BigUint::parse_bytes(text.as_bytes(), 10)?;
+    (value < F::MODULUS.into()).then(|| F::from(value))
+}
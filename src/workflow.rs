@@ -0,0 +1,483 @@
+//! The Groth16 workflows the `qa1` command line runs, for library users:
+//! picking the curve for an R1CS file, loading the circuit and witness,
+//! resolving public inputs, and setting up, proving and self-checking.
+//!
+//! Nothing here prints. Progress goes to a [`Hooks`] implementation and
+//! failures come back as [`WorkflowError`], so the caller decides what to
+//! show.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use ark_ec::pairing::Pairing;
+use ark_ff::PrimeField;
+use ark_groth16::{Proof, ProvingKey};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem, SynthesisError};
+use ark_std::rand::{rngs::StdRng, CryptoRng, RngCore, SeedableRng};
+use crate::circuit::{CircuitError, CircuitFromR1CS};
+use crate::groth16::{self, SynthesisDiagnosis, VerifyError};
+use crate::r1cs::{KnownCurve, R1CSError, R1CSHeader, ReadOptions, R1CS};
+use crate::snarkjs_export::Endianness;
+use crate::solve::SolveError;
+use crate::sym::SymbolTable;
+use crate::witness::{self, Witness};
+
+/// Errors from the workflows in this module
+#[derive(Debug)]
+pub enum WorkflowError {
+    /// Reading or validating the circuit, witness or inputs failed
+    R1CS(R1CSError),
+    /// The circuit and witness don't fit together (see [`CircuitFromR1CS::new`])
+    Circuit(CircuitError),
+    Io(io::Error),
+    /// The R1CS prime isn't this curve's scalar field modulus
+    CurveMismatch(KnownCurve),
+    /// The R1CS prime matches none of the [`KnownCurve`]s
+    UnsupportedPrime,
+    /// The circuit has more constraints than the setup limit
+    TooManyConstraints { num_constraints: u32, max_constraints: u32 },
+    /// The witness does not satisfy this constraint
+    Unsatisfied(usize),
+    /// Proving needs a witness, but none was given
+    NoWitness,
+    /// Solving a witness from circuit inputs failed
+    Solve(SolveError),
+    /// A [`PublicInput`] is invalid, or a public wire has no value
+    InvalidPublicInput(String),
+    /// An expected output was given, but the circuit has no public output
+    NoPublicOutput,
+    /// The circuit's first public output isn't the expected value (both in decimal)
+    OutputMismatch { actual: String, expected: String },
+    /// arkworks failed in this phase, most likely for `diagnosis`
+    Synthesis { phase: Phase, error: SynthesisError, diagnosis: SynthesisDiagnosis },
+    /// A proof couldn't be checked, e.g. for the wrong number of public inputs
+    Verify(VerifyError),
+    /// The parsed constraints, arkworks and Groth16 disagree about a witness,
+    /// which is a bug in how the circuit is translated to arkworks (see [`selfcheck`])
+    TranslationBug(String),
+}
+
+impl fmt::Display for WorkflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorkflowError::R1CS(e) => write!(f, "{}", e),
+            WorkflowError::Circuit(e) => write!(f, "{}", e),
+            WorkflowError::Io(e) => write!(f, "{}", e),
+            WorkflowError::CurveMismatch(curve) => write!(f, "The R1CS prime is not the {} scalar field modulus", curve),
+            WorkflowError::UnsupportedPrime => write!(f, "The R1CS prime matches neither bn254 nor bls12-381"),
+            WorkflowError::TooManyConstraints { num_constraints, max_constraints } => write!(
+                f,
+                "The circuit has {} constraints, more than the setup limit of {}",
+                num_constraints, max_constraints
+            ),
+            WorkflowError::Unsatisfied(idx) => write!(f, "Witness does not satisfy constraint #{}", idx),
+            WorkflowError::NoWitness => write!(f, "Proving needs a witness"),
+            WorkflowError::Solve(e) => write!(f, "{}", e),
+            WorkflowError::InvalidPublicInput(message) => write!(f, "{}", message),
+            WorkflowError::NoPublicOutput => write!(f, "The circuit has no public output to check"),
+            WorkflowError::OutputMismatch { actual, expected } => write!(
+                f,
+                "Public output is {}, but {} was expected",
+                actual, expected
+            ),
+            WorkflowError::Synthesis { phase, error, .. } => write!(f, "Groth16 {} failed: {}", phase, error),
+            WorkflowError::Verify(e) => write!(f, "{}", e),
+            WorkflowError::TranslationBug(message) => write!(f, "self-check failed: {}", message),
+        }
+    }
+}
+
+impl Error for WorkflowError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            WorkflowError::R1CS(e) => Some(e),
+            WorkflowError::Circuit(e) => Some(e),
+            WorkflowError::Io(e) => Some(e),
+            WorkflowError::Solve(e) => Some(e),
+            WorkflowError::Synthesis { error, .. } => Some(error),
+            WorkflowError::Verify(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<R1CSError> for WorkflowError {
+    fn from(e: R1CSError) -> Self {
+        WorkflowError::R1CS(e)
+    }
+}
+
+impl From<CircuitError> for WorkflowError {
+    fn from(e: CircuitError) -> Self {
+        WorkflowError::Circuit(e)
+    }
+}
+
+impl From<io::Error> for WorkflowError {
+    fn from(e: io::Error) -> Self {
+        WorkflowError::Io(e)
+    }
+}
+
+impl From<SolveError> for WorkflowError {
+    fn from(e: SolveError) -> Self {
+        WorkflowError::Solve(e)
+    }
+}
+
+impl From<VerifyError> for WorkflowError {
+    fn from(e: VerifyError) -> Self {
+        WorkflowError::Verify(e)
+    }
+}
+
+impl From<WorkflowError> for io::Error {
+    fn from(e: WorkflowError) -> Self {
+        let kind = match e {
+            WorkflowError::R1CS(e) => return e.into(),
+            WorkflowError::Circuit(e) => return e.into(),
+            WorkflowError::Io(e) => return e,
+            WorkflowError::CurveMismatch(_)
+            | WorkflowError::TooManyConstraints { .. }
+            | WorkflowError::NoWitness
+            | WorkflowError::InvalidPublicInput(_)
+            | WorkflowError::NoPublicOutput
+            | WorkflowError::OutputMismatch { .. } => io::ErrorKind::InvalidInput,
+            WorkflowError::UnsupportedPrime | WorkflowError::Unsatisfied(_) | WorkflowError::Solve(_) => io::ErrorKind::InvalidData,
+            _ => io::ErrorKind::Other,
+        };
+        io::Error::new(kind, e)
+    }
+}
+
+/// A step of a workflow that can take minutes, run through [`Hooks::phase`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Setup,
+    Prove,
+    Verify,
+}
+
+impl Phase {
+    /// Lowercase name, e.g. for a timings table
+    pub fn name(self) -> &'static str {
+        match self {
+            Phase::Setup => "setup",
+            Phase::Prove => "prove",
+            Phase::Verify => "verify",
+        }
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A check a workflow passed, or what it found, told to [`Hooks::event`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event<'a, F: PrimeField> {
+    /// The circuit's first public output is the expected value
+    OutputMatches(&'a F),
+    /// The witness satisfies all the circuit's constraints
+    WitnessSatisfied { num_constraints: usize },
+    /// What the parsed constraints (the first one violated, if any) and
+    /// arkworks' constraint system made of a witness, see [`selfcheck`]
+    Evaluated { native: Result<(), usize>, arkworks: bool },
+}
+
+/// Follows a workflow as it runs, e.g. to show progress or time each
+/// phase. The methods do nothing by default, and `()` implements the trait
+/// for callers that don't need to follow along.
+pub trait Hooks<F: PrimeField> {
+    /// Run one phase by calling `run`, returning its result
+    fn phase<T, E>(&mut self, phase: Phase, run: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+        let _ = phase;
+        run()
+    }
+
+    /// Hear about a check that passed or a finding
+    fn event(&mut self, event: Event<'_, F>) {
+        let _ = event;
+    }
+}
+
+impl<F: PrimeField> Hooks<F> for () {}
+
+/// Pick the curve for an R1CS file from its header: `requested`, as long as
+/// the file's prime is its scalar field modulus, or else the curve whose
+/// modulus the prime is
+pub fn select_curve<P: AsRef<Path>>(
+    r1cs_path: P,
+    requested: Option<KnownCurve>,
+    options: &ReadOptions,
+) -> Result<KnownCurve, WorkflowError> {
+    // The header's prime tells us which curve the circuit was compiled for
+    let detected = R1CSHeader::read_with_options(r1cs_path, options)?.detect_curve();
+    match (requested, detected) {
+        (Some(curve), detected) if detected != Some(curve) => Err(WorkflowError::CurveMismatch(curve)),
+        (Some(curve), _) | (None, Some(curve)) => Ok(curve),
+        (None, None) => Err(WorkflowError::UnsupportedPrime),
+    }
+}
+
+/// Parse an R1CS file into the scalar field `F` with `options`, and check
+/// that its prime is `F`'s modulus: coefficients reduced into the wrong
+/// field would make a meaningless proof
+pub fn load_r1cs<F: PrimeField, P: AsRef<Path>>(r1cs_path: P, options: &ReadOptions) -> Result<R1CS<F>, WorkflowError> {
+    let r1cs = R1CS::<F>::read_with_options(r1cs_path, options)?;
+    r1cs.assert_modulus_matches::<F>()?;
+    Ok(r1cs)
+}
+
+/// Read a witness for `r1cs`, checking that it was computed over the same
+/// prime, i.e. isn't for another curve
+pub fn load_witness<F: PrimeField, P: AsRef<Path>>(wtns_path: P, r1cs: &R1CS<F>) -> Result<Witness<F>, WorkflowError> {
+    let witness = Witness::<F>::read(wtns_path)?;
+    witness.assert_prime_matches(r1cs)?;
+    Ok(witness)
+}
+
+/// Compute a witness for `r1cs` from a circom `input.json`, whose signals
+/// `symbols` maps to wires (see [`R1CS::solve_witness`])
+pub fn solve_witness<F: PrimeField, P: AsRef<Path>>(
+    r1cs: &R1CS<F>,
+    input_path: P,
+    symbols: &SymbolTable,
+) -> Result<Witness<F>, WorkflowError> {
+    let inputs = witness::load_circuit_inputs::<F, _>(input_path, symbols)?;
+    Ok(Witness {
+        field_size: r1cs.header().field_size,
+        prime_bytes: r1cs.header().prime_bytes.clone(),
+        values: r1cs.solve_witness(&inputs)?,
+    })
+}
+
+/// Check that `r1cs` has at most `max_constraints` constraints. Setup
+/// memory grows with the constraint count, so huge circuits have to be
+/// asked for explicitly.
+pub fn check_setup_limit<F: PrimeField>(r1cs: &R1CS<F>, max_constraints: u32) -> Result<(), WorkflowError> {
+    let num_constraints = r1cs.num_constraints();
+    if num_constraints > max_constraints {
+        return Err(WorkflowError::TooManyConstraints { num_constraints, max_constraints });
+    }
+    Ok(())
+}
+
+/// Where a verifier's public inputs come from: a snarkjs `public.json`
+/// file, or the value of one public wire
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PublicInput {
+    File(PathBuf),
+    /// Public wire `wire` (1 for the first public value) as a decimal or
+    /// `0x` hex string
+    Value { wire: usize, value: String },
+}
+
+/// Collect `num_public` public inputs from at most one `public.json`, then
+/// apply the [`PublicInput::Value`]s over it. Without a file every public
+/// wire must be given. Hex values are read in the `endianness` byte order.
+pub fn resolve_public_inputs<F: PrimeField>(
+    inputs: &[PublicInput],
+    num_public: usize,
+    endianness: Endianness,
+) -> Result<Vec<F>, WorkflowError> {
+    let invalid = WorkflowError::InvalidPublicInput;
+
+    let files: Vec<&PathBuf> = inputs.iter().filter_map(|input| match input {
+        PublicInput::File(path) => Some(path),
+        PublicInput::Value { .. } => None,
+    }).collect();
+    let mut values: Vec<Option<F>> = match files.as_slice() {
+        [] => vec![None; num_public],
+        [path] => witness::load_public_inputs_with_endianness::<F, _>(path, endianness)?.into_iter().map(Some).collect(),
+        _ => return Err(invalid("at most one public inputs file can be given".to_string())),
+    };
+
+    for input in inputs {
+        if let PublicInput::Value { wire, value } = input {
+            if *wire == 0 || *wire > num_public {
+                return Err(invalid(format!("public input {}={}: public wires are 1 to {}", wire, value, num_public)));
+            }
+            let parsed = witness::parse_field_element_with_endianness::<F>(value, endianness)
+                .ok_or_else(|| invalid(format!("public input {}={}: not a decimal or 0x hex field element", wire, value)))?;
+            match values.get_mut(wire - 1) {
+                Some(slot) => *slot = Some(parsed),
+                None => return Err(invalid(format!(
+                    "public input {}={}: the public inputs file only has {} values", wire, value, values.len()
+                ))),
+            }
+        }
+    }
+
+    let missing: Vec<usize> = values.iter().enumerate().filter(|(_, v)| v.is_none()).map(|(i, _)| i + 1).collect();
+    if !missing.is_empty() {
+        return Err(invalid(format!("no value given for public wires {:?}", missing)));
+    }
+    Ok(values.into_iter().flatten().collect())
+}
+
+/// Check the circuit's first public output, the first of `public_inputs`,
+/// against `expected`, to catch a witness that satisfies the constraints
+/// but computes the wrong answer
+pub fn check_expected_output<F: PrimeField>(r1cs: &R1CS<F>, public_inputs: &[F], expected: &F) -> Result<(), WorkflowError> {
+    // Public values start with the outputs
+    let actual = match public_inputs.first() {
+        Some(actual) if r1cs.num_public_outputs() > 0 => actual,
+        _ => return Err(WorkflowError::NoPublicOutput),
+    };
+    if actual != expected {
+        return Err(WorkflowError::OutputMismatch {
+            actual: actual.into_bigint().to_string(),
+            expected: expected.into_bigint().to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Run the Groth16 setup for `r1cs`, refusing circuits Groth16 can't prove
+/// or with more than `max_constraints` constraints
+pub fn setup<E: Pairing, R: RngCore + CryptoRng, H: Hooks<E::ScalarField>>(
+    r1cs: &R1CS<E::ScalarField>,
+    max_constraints: u32,
+    rng: &mut R,
+    hooks: &mut H,
+) -> Result<ProvingKey<E>, WorkflowError> {
+    r1cs.assert_groth16_compatible()?;
+    check_setup_limit(r1cs, max_constraints)?;
+
+    // Setup only needs the constraints, not real witness values
+    let circuit = CircuitFromR1CS::new_with_dummy(r1cs)?;
+    hooks.phase(Phase::Setup, || groth16::setup::<E, _>(circuit, rng))
+        .map_err(|e| synthesis_error(Phase::Setup, e, r1cs, None))
+}
+
+/// Prove with a saved proving key that `witness` satisfies `r1cs`,
+/// returning the proof and its public inputs. The witness is checked
+/// natively first, so a bad one is reported by constraint
+/// ([`WorkflowError::Unsatisfied`]) rather than from inside the prover.
+pub fn prove<E: Pairing, H: Hooks<E::ScalarField>>(
+    r1cs: &R1CS<E::ScalarField>,
+    witness: Witness<E::ScalarField>,
+    pk: &ProvingKey<E>,
+    hooks: &mut H,
+) -> Result<(Proof<E>, Vec<E::ScalarField>), WorkflowError> {
+    r1cs.assert_groth16_compatible()?;
+    prove_circuit(pk, CircuitFromR1CS::new(r1cs, witness)?, hooks)
+}
+
+/// The keys, proof and public inputs from [`run`]
+pub struct RunOutput<E: Pairing> {
+    pub pk: ProvingKey<E>,
+    pub proof: Proof<E>,
+    pub public_inputs: Vec<E::ScalarField>,
+}
+
+impl<E: Pairing> RunOutput<E> {
+    /// Verify the proof against the new verifying key
+    pub fn verify<H: Hooks<E::ScalarField>>(&self, hooks: &mut H) -> Result<bool, WorkflowError> {
+        Ok(hooks.phase(Phase::Verify, || groth16::verify(&self.pk.vk, &self.public_inputs, &self.proof))?)
+    }
+}
+
+/// Set up and prove `r1cs` in one go, as the command line does without a
+/// subcommand. A proof needs real values, so a missing witness is
+/// [`WorkflowError::NoWitness`] before any setup work; use [`setup`] for
+/// keys alone. With `expected_output`, a witness whose first public output
+/// is anything else stops the run before the expensive setup.
+pub fn run<E: Pairing, R: RngCore + CryptoRng, H: Hooks<E::ScalarField>>(
+    r1cs: &R1CS<E::ScalarField>,
+    witness: Option<Witness<E::ScalarField>>,
+    max_constraints: u32,
+    expected_output: Option<&E::ScalarField>,
+    rng: &mut R,
+    hooks: &mut H,
+) -> Result<RunOutput<E>, WorkflowError> {
+    let witness = witness.ok_or(WorkflowError::NoWitness)?;
+    r1cs.assert_groth16_compatible()?;
+    check_setup_limit(r1cs, max_constraints)?;
+
+    let circuit = CircuitFromR1CS::new(r1cs, witness.clone())?;
+    if let Some(expected) = expected_output {
+        check_expected_output(r1cs, &circuit.get_public_inputs(), expected)?;
+        hooks.event(Event::OutputMatches(expected));
+    }
+
+    let pk = hooks.phase(Phase::Setup, || groth16::setup::<E, _>(circuit, rng))
+        .map_err(|e| synthesis_error(Phase::Setup, e, r1cs, Some(&witness.values)))?;
+
+    // The proving circuit shares the parsed R1CS with the setup circuit
+    let (proof, public_inputs) = prove_circuit(&pk, CircuitFromR1CS::new(r1cs, witness)?, hooks)?;
+    Ok(RunOutput { pk, proof, public_inputs })
+}
+
+/// Check that the parsed constraints evaluated natively, arkworks'
+/// constraint system and Groth16 agree about a witness: both evaluators
+/// accept it and a fresh proof of it verifies. A witness both evaluators
+/// reject is [`WorkflowError::Unsatisfied`]; any disagreement is a
+/// [`WorkflowError::TranslationBug`] in how the circuit is translated to
+/// arkworks, not in the user's data.
+pub fn selfcheck<E: Pairing, R: RngCore + CryptoRng, H: Hooks<E::ScalarField>>(
+    r1cs: &R1CS<E::ScalarField>,
+    witness: Witness<E::ScalarField>,
+    max_constraints: u32,
+    rng: &mut R,
+    hooks: &mut H,
+) -> Result<(), WorkflowError> {
+    r1cs.assert_groth16_compatible()?;
+    let translation_bug = |message: &str| WorkflowError::TranslationBug(message.to_string());
+
+    // 1. The parsed constraints, evaluated natively
+    let circuit = CircuitFromR1CS::new(r1cs, witness.clone())?;
+    let native = r1cs.check_witness(circuit.witness_values());
+
+    // 2. The same witness in arkworks' constraint system
+    let cs = ConstraintSystem::<E::ScalarField>::new_ref();
+    CircuitFromR1CS::new(r1cs, witness)?
+        .generate_constraints(cs.clone())
+        .map_err(io::Error::other)?;
+    let arkworks = cs.is_satisfied().map_err(io::Error::other)?;
+    hooks.event(Event::Evaluated { native, arkworks });
+
+    match (native, arkworks) {
+        (Ok(()), true) => {}
+        (Err(idx), false) => return Err(WorkflowError::Unsatisfied(idx)),
+        (Ok(()), false) => return Err(translation_bug("arkworks rejects a witness that satisfies every parsed constraint")),
+        (Err(_), true) => return Err(translation_bug("arkworks accepts a witness that violates a parsed constraint")),
+    }
+
+    // 3. A proof of the witness must verify
+    let pk = setup::<E, _, _>(r1cs, max_constraints, rng, hooks)?;
+    let (proof, public_inputs) = prove_circuit(&pk, circuit, hooks)?;
+    match hooks.phase(Phase::Verify, || groth16::verify(&pk.vk, &public_inputs, &proof)) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(translation_bug("the proof of a satisfying witness does not verify")),
+        Err(e) => Err(translation_bug(&format!("verifying the proof of a satisfying witness failed: {}", e))),
+    }
+}
+
+// Check the circuit's witness natively, then prove it with fresh blinding
+fn prove_circuit<E: Pairing, H: Hooks<E::ScalarField>>(
+    pk: &ProvingKey<E>,
+    circuit: CircuitFromR1CS<'_, E::ScalarField>,
+    hooks: &mut H,
+) -> Result<(Proof<E>, Vec<E::ScalarField>), WorkflowError> {
+    let r1cs = circuit.r1cs();
+    r1cs.check_witness(circuit.witness_values()).map_err(WorkflowError::Unsatisfied)?;
+    hooks.event(Event::WitnessSatisfied { num_constraints: r1cs.constraints().len() });
+
+    // Proof blinding must be fresh, or proofs could leak the witness
+    let public_inputs = circuit.get_public_inputs();
+    let mut rng = StdRng::from_entropy();
+    let proof = hooks.phase(Phase::Prove, || groth16::prove(pk, circuit, &mut rng))
+        .map_err(|e| synthesis_error(Phase::Prove, e, r1cs, None))?;
+    Ok((proof, public_inputs))
+}
+
+// Wrap an arkworks error with its likely cause in the circuit and witness
+fn synthesis_error<F: PrimeField>(phase: Phase, error: SynthesisError, r1cs: &R1CS<F>, witness: Option<&[F]>) -> WorkflowError {
+    let diagnosis = groth16::diagnose_synthesis_error(&error, r1cs, witness);
+    WorkflowError::Synthesis { phase, error, diagnosis }
+}
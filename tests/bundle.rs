@@ -0,0 +1,98 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use dogecoin_zkp_generator_qa1::bundle::Bundle;
+
+mod common;
+use common::{fixture, path_str, qa1, scratch_dir};
+
+// `bundle_mult` is what circom writes for `out = x * y` over bn254
+// (`circuit.r1cs` is `mult.r1cs`), with `input.json` setting x = 5, y = 7
+
+// A copy of the committed bundle, as the workflow writes its proof there
+fn bundle_copy(test: &str) -> PathBuf {
+    let dir = scratch_dir(test);
+    for name in ["circuit.r1cs", "circuit.sym", "input.json"] {
+        fs::copy(fixture("bundle_mult").join(name), dir.join(name)).unwrap();
+    }
+    dir
+}
+
+fn read_json(path: &Path) -> serde_json::Value {
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn committed_bundle_is_discovered() {
+    let dir = fixture("bundle_mult");
+    let bundle = Bundle::discover(&dir).unwrap();
+    assert_eq!(bundle.r1cs, dir.join("circuit.r1cs"));
+    assert_eq!(bundle.sym, Some(dir.join("circuit.sym")));
+    assert_eq!(bundle.input, Some(dir.join("input.json")));
+    assert_eq!(bundle.witness, None);
+}
+
+// input.json -> solved witness -> setup -> proof -> verify, in one command
+#[test]
+fn proves_from_input_json() {
+    let dir = bundle_copy("bundle-input");
+    let output = qa1(&["--bundle", path_str(&dir), "--setup-seed", "206"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.contains("Solved all 4 wires"), "{}", stdout);
+    assert!(stdout.contains("Proof verified successfully"), "{}", stdout);
+    
+    assert_eq!(read_json(&dir.join("public.json")), serde_json::json!(["35"]));
+    let proof = read_json(&dir.join("proof.json"));
+    assert_eq!(proof["protocol"], "groth16");
+    assert_eq!(proof["curve"], "bn128");
+}
+
+// The witness solved from input.json is the one circom computes
+#[test]
+fn solved_witness_matches_circom() {
+    let dir = bundle_copy("bundle-out-dir");
+    let out_dir = dir.join("out");
+    let output = qa1(&["--bundle", path_str(&dir), "--setup-seed", "206", "--out-dir", path_str(&out_dir)]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+    assert_eq!(fs::read(out_dir.join("witness.wtns")).unwrap(), fs::read(fixture("mult.wtns")).unwrap());
+    assert_eq!(read_json(&out_dir.join("public.json")), serde_json::json!(["35"]));
+}
+
+// A bundle with circom's witness.wtns uses it instead of solving
+#[test]
+fn proves_from_witness_wtns() {
+    let dir = bundle_copy("bundle-wtns");
+    fs::remove_file(dir.join("input.json")).unwrap();
+    fs::copy(fixture("mult.wtns"), dir.join("witness.wtns")).unwrap();
+    
+    let output = qa1(&["--bundle", path_str(&dir), "--setup-seed", "206"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", stdout);
+    assert!(!stdout.contains("Solving witness"), "{}", stdout);
+    assert_eq!(read_json(&dir.join("public.json")), serde_json::json!(["35"]));
+}
+
+#[test]
+fn wrong_input_fails() {
+    let dir = bundle_copy("bundle-unknown-signal");
+    fs::write(dir.join("input.json"), r#"{"x": "5", "z": "7"}"#).unwrap();
+    let output = qa1(&["--bundle", path_str(&dir), "--setup-seed", "206"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(2), "{}", stdout);
+    assert!(stdout.contains("main.z"), "{}", stdout);
+    assert!(!dir.join("proof.json").exists());
+}
+
+// Without the .sym file input.json can't be solved, and there are no
+// sample values to fall back on
+#[test]
+fn input_json_without_sym_fails() {
+    let dir = bundle_copy("bundle-no-sym");
+    fs::remove_file(dir.join("circuit.sym")).unwrap();
+    let output = qa1(&["--bundle", path_str(&dir), "--setup-seed", "206"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(2), "{}", stdout);
+    assert!(stdout.contains("needs the circuit's .sym file"), "{}", stdout);
+    assert!(!stdout.contains("Running Groth16 setup"), "{}", stdout);
+    assert!(!dir.join("proof.json").exists());
+}
@@ -0,0 +1,44 @@
+use std::io::Cursor;
+use ark_bn254::Fr;
+use dogecoin_zkp_generator_qa1::R1CS;
+
+mod common;
+use common::{field_element, linear_combination, r1cs_bytes};
+
+// The circuit has one constraint over the wires [1, out, x]:
+// A = 1·x + 1·ONE + 2·x, unsorted and with x twice; B = ONE; C = out.
+const ONE: u32 = 0;
+const OUT: u32 = 1;
+const X: u32 = 2;
+
+fn constraint() -> Vec<u8> {
+    let mut bytes = linear_combination(&[(X, field_element(1)), (ONE, field_element(1)), (X, field_element(2))]);
+    bytes.extend(linear_combination(&[(ONE, field_element(1))]));
+    bytes.extend(linear_combination(&[(OUT, field_element(1))]));
+    bytes
+}
+
+fn parse() -> R1CS<Fr> {
+    R1CS::from_reader(Cursor::new(r1cs_bytes(&constraint()))).unwrap()
+}
+
+#[test]
+fn terms_are_sorted_and_coalesced() {
+    let r1cs = parse();
+    let a = &r1cs.constraints()[0].a_terms;
+    let wires: Vec<u32> = a.iter().map(|term| term.wire_id).collect();
+    assert_eq!(wires, [ONE, X]);
+    assert_eq!(a[0].coefficient, Fr::from(1u64));
+    assert_eq!(a[1].coefficient, Fr::from(3u64));
+    assert_eq!(r1cs.constraints()[0].to_string(), "(1·x0 + 3·x2) · (1·x0) = 1·x1");
+}
+
+// (1 + 3x) · 1 = out, so x = 3 gives out = 10, counting each x term once
+#[test]
+fn duplicate_terms_count_once_each_in_check_witness() {
+    let r1cs = parse();
+    let witness = |out: u64| [Fr::from(1u64), Fr::from(out), Fr::from(3u64)];
+    assert_eq!(r1cs.check_witness(&witness(10)), Ok(()));
+    assert_eq!(r1cs.check_witness(&witness(7)), Err(0));
+    assert_eq!(r1cs.check_witness(&witness(16)), Err(0));
+}
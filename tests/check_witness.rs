@@ -0,0 +1,108 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use ark_bn254::Fr;
+use dogecoin_zkp_generator_qa1::Witness;
+
+mod common;
+use common::{fixture, scratch_dir};
+
+// The fixture circuit is `out = x * y` over bn254, with wires
+// [1, out, x, y] and the witness x = 5, y = 7, out = 35.
+
+fn check_circuit(r1cs: &Path, wtns: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_dogecoin_zkp_generator_qa1"))
+        .args(["check", "--r1cs", r1cs.to_str().unwrap()])
+        .args(["--wtns", wtns.to_str().unwrap()])
+        .output()
+        .unwrap()
+}
+
+fn check(wtns: &Path) -> Output {
+    check_circuit(&fixture("mult.r1cs"), wtns)
+}
+
+#[test]
+fn satisfying_witness_passes() {
+    let output = check(&fixture("mult.wtns"));
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Witness satisfies all 1 constraints"));
+}
+
+// The values section starts at byte 76 of `mult.wtns` (after the magic,
+// version, section count and the 40-byte header section), one 32-byte
+// little-endian value per wire
+#[test]
+fn failing_constraint_is_printed_with_its_values() {
+    let mut bytes = fs::read(fixture("mult.wtns")).unwrap();
+    let out = 76 + 32;
+    assert_eq!(bytes[out], 35);
+    bytes[out] = 36;
+    let wtns = scratch_dir("check-witness").join("wrong.wtns");
+    fs::write(&wtns, bytes).unwrap();
+
+    let output = check(&wtns);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("does not satisfy constraint #0: (1·x2) · (1·x3) = 1·x1"), "{}", stdout);
+    for line in ["A·w = 5", "B·w = 7", "C·w = 36", "(A·w)(B·w) = 35"] {
+        assert!(stdout.contains(line), "missing {:?} in {}", line, stdout);
+    }
+}
+
+#[test]
+fn witness_for_another_circuit_is_an_error() {
+    let output = check(&fixture("mult_bls12_381.wtns"));
+    assert_eq!(output.status.code(), Some(2));
+}
+
+// Checking needs no Groth16, so circuits only a Groth16 setup rejects are
+// still checked: `mult_custom_gates.r1cs` is `mult.r1cs` with the custom
+// gates flag set, and `empty.r1cs` has three wires and no constraints
+#[test]
+fn circuits_groth16_rejects_are_checked() {
+    let output = check_circuit(&fixture("mult_custom_gates.r1cs"), &fixture("mult.wtns"));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0), "{}", stdout);
+    assert!(stdout.contains("Witness satisfies all 1 constraints"), "{}", stdout);
+
+    let mut witness = Witness::<Fr>::read(fixture("mult.wtns")).unwrap();
+    witness.values.truncate(3);
+    let wtns = scratch_dir("check-witness-empty").join("empty.wtns");
+    witness.write(&wtns).unwrap();
+    let output = check_circuit(&fixture("empty.r1cs"), &wtns);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0), "{}", stdout);
+    assert!(stdout.contains("Witness satisfies all 0 constraints"), "{}", stdout);
+}
+
+#[test]
+fn witness_shape_is_still_checked() {
+    let mut witness = Witness::<Fr>::read(fixture("mult.wtns")).unwrap();
+    witness.values[0] = Fr::from(2u64);
+    let wtns = scratch_dir("check-witness-one").join("one.wtns");
+    witness.write(&wtns).unwrap();
+    let output = check(&wtns);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(2), "{}", stdout);
+    assert!(stdout.contains("Witness wire 0 must be 1"), "{}", stdout);
+
+    let output = check_circuit(&fixture("empty.r1cs"), &fixture("mult.wtns"));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(2), "{}", stdout);
+    assert!(stdout.contains("Witness has 4 values, but the circuit has 3 wires"), "{}", stdout);
+}
+
+// Proving needs real values, so the full workflow without a witness stops
+// before the Groth16 setup instead of failing the proof afterwards
+#[test]
+fn proving_without_a_witness_is_an_error() {
+    let output = Command::new(env!("CARGO_BIN_EXE_dogecoin_zkp_generator_qa1"))
+        .args(["--r1cs", fixture("mult.r1cs").to_str().unwrap(), "--setup-seed", "1"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(2), "{}", stdout);
+    assert!(stdout.contains("Proving needs a witness: pass --wtns"), "{}", stdout);
+    assert!(!stdout.contains("Running Groth16 setup"), "{}", stdout);
+}
@@ -0,0 +1,56 @@
+use ark_bn254::Fr;
+use dogecoin_zkp_generator_qa1::r1cs::Term;
+use dogecoin_zkp_generator_qa1::{CircuitError, CircuitFromR1CS, Witness, R1CS};
+
+mod common;
+use common::fixture;
+
+// `mult.r1cs` is `out = x * y` over bn254 with wires [1, out, x, y], and
+// `mult.wtns` the witness [1, 35, 5, 7]
+
+fn witness() -> Witness<Fr> {
+    Witness::<Fr>::read(fixture("mult.wtns")).unwrap()
+}
+
+#[test]
+fn matching_witness_builds() {
+    let r1cs = R1CS::<Fr>::read(fixture("mult.r1cs")).unwrap();
+    let circuit = CircuitFromR1CS::new(&r1cs, witness()).unwrap();
+    assert_eq!(circuit.witness_values(), witness().values);
+    assert_eq!(circuit.get_public_inputs(), [Fr::from(35u64)]);
+
+    let dummy = CircuitFromR1CS::new_with_dummy(&r1cs).unwrap();
+    assert_eq!(dummy.witness_values().len(), 4);
+}
+
+#[test]
+fn missing_and_extra_values_are_rejected() {
+    let r1cs = R1CS::<Fr>::read(fixture("mult.r1cs")).unwrap();
+    for len in [3, 5] {
+        let mut short = witness();
+        short.values.resize(len, Fr::from(0u64));
+        match CircuitFromR1CS::new(&r1cs, short) {
+            Err(CircuitError::WitnessLengthMismatch { expected, got }) => assert_eq!((expected, got), (4, len)),
+            Err(e) => panic!("expected WitnessLengthMismatch, got {}", e),
+            Ok(_) => panic!("a witness of {} values was accepted", len),
+        }
+    }
+}
+
+// A circuit edited in code can reference wires the header doesn't declare,
+// which arkworks would only report as AssignmentMissing
+#[test]
+fn constraints_on_missing_wires_are_rejected() {
+    let mut r1cs = R1CS::<Fr>::read(fixture("mult.r1cs")).unwrap();
+    r1cs.constraints_mut()[0].c_terms.push(Term { wire_id: 4, coefficient: Fr::from(1u64) });
+
+    let expected = |result: Result<CircuitFromR1CS<'_, Fr>, CircuitError>| match result {
+        Err(CircuitError::WireOutOfRange { constraint, wire, num_wires }) => {
+            assert_eq!((constraint, wire, num_wires), (0, 4, 4));
+        }
+        Err(e) => panic!("expected WireOutOfRange, got {}", e),
+        Ok(_) => panic!("a constraint on wire 4 was accepted"),
+    };
+    expected(CircuitFromR1CS::new(&r1cs, witness()));
+    expected(CircuitFromR1CS::new_with_dummy(&r1cs));
+}
@@ -0,0 +1,99 @@
+// Helpers shared by the integration tests. Each test binary compiles its
+// own copy of this module and uses only some of them.
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+// A committed file or directory under tests/fixtures
+pub fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name)
+}
+
+// A fresh, empty directory for one test's files, unique to this process
+pub fn scratch_dir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("qa1-{}-{}", test, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// Run the qa1 binary, whatever its exit status
+pub fn qa1(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_dogecoin_zkp_generator_qa1"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+// Run the qa1 binary, failing the test unless it succeeds
+pub fn qa1_ok(args: &[&str]) -> Output {
+    let output = qa1(args);
+    assert!(
+        output.status.success(),
+        "qa1 {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output
+}
+
+pub fn path_str(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
+
+// bn254's scalar field modulus, big-endian
+pub const BN254_R: &str = "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+
+// BN254_R as the little-endian bytes .r1cs and .wtns headers store
+pub fn prime_le() -> Vec<u8> {
+    let mut bytes: Vec<u8> = (0..BN254_R.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&BN254_R[i..i + 2], 16).unwrap())
+        .collect();
+    bytes.reverse();
+    bytes
+}
+
+// A small value as a 32-byte little-endian field element
+pub fn field_element(value: u64) -> Vec<u8> {
+    let mut bytes = value.to_le_bytes().to_vec();
+    bytes.resize(32, 0);
+    bytes
+}
+
+pub fn section(out: &mut Vec<u8>, section_type: u32, body: &[u8]) {
+    out.extend_from_slice(&section_type.to_le_bytes());
+    out.extend_from_slice(&(body.len() as u64).to_le_bytes());
+    out.extend_from_slice(body);
+}
+
+// One side of a constraint, with each coefficient already encoded
+pub fn linear_combination(terms: &[(u32, Vec<u8>)]) -> Vec<u8> {
+    let mut bytes = (terms.len() as u32).to_le_bytes().to_vec();
+    for (wire, coefficient) in terms {
+        bytes.extend_from_slice(&wire.to_le_bytes());
+        bytes.extend_from_slice(coefficient);
+    }
+    bytes
+}
+
+// A bn254 .r1cs file over the wires [1, out, x], with out public, x private
+// and a single constraint whose A, B and C sides are `constraint`
+pub fn r1cs_bytes(constraint: &[u8]) -> Vec<u8> {
+    let mut header = 32u32.to_le_bytes().to_vec();
+    header.extend_from_slice(&prime_le());
+    for count in [3u32, 1, 0, 1] {
+        header.extend_from_slice(&count.to_le_bytes());
+    }
+    header.extend_from_slice(&3u64.to_le_bytes());
+    header.extend_from_slice(&1u32.to_le_bytes());
+
+    let mut out = b"r1cs".to_vec();
+    out.extend_from_slice(&1u32.to_le_bytes());
+    out.extend_from_slice(&2u32.to_le_bytes());
+    section(&mut out, 1, &header);
+    section(&mut out, 2, constraint);
+    out
+}
@@ -0,0 +1,86 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+mod common;
+use common::{fixture, path_str, scratch_dir};
+
+// `mult.r1cs` is `out = x * y` over bn254, and `mult.wtns` its witness
+
+// Run qa1 from `dir`, where it looks for qa1.toml
+fn qa1_in(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_dogecoin_zkp_generator_qa1"))
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn command_line_overrides_qa1_toml() {
+    let dir = scratch_dir("config-curve");
+    fs::write(dir.join("qa1.toml"), "curve = \"bls12-381\"\n").unwrap();
+    let r1cs = fixture("mult.r1cs");
+
+    // The bn254 fixture doesn't match the configured curve...
+    let output = qa1_in(&dir, &["info", "--r1cs", path_str(&r1cs)]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(2), "{}", stdout);
+    assert!(stdout.contains("not the bls12-381 scalar field modulus"), "{}", stdout);
+
+    // ...unless the command line says otherwise
+    let output = qa1_in(&dir, &["info", "--r1cs", path_str(&r1cs), "--curve", "bn254"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+}
+
+// Setup needs an explicit RNG, which the config file can provide
+fn workflow(dir: &Path, out: &str, extra: &[&str]) -> Vec<u8> {
+    let (r1cs, wtns, out_dir) = (fixture("mult.r1cs"), fixture("mult.wtns"), dir.join(out));
+    let mut args = vec![
+        "--r1cs", path_str(&r1cs),
+        "--wtns", path_str(&wtns),
+        "--out-dir", path_str(&out_dir),
+    ];
+    args.extend_from_slice(extra);
+    let output = qa1_in(dir, &args);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+    fs::read(out_dir.join("verifying_key.bin")).unwrap()
+}
+
+#[test]
+fn config_flag_supplies_the_setup_seed() {
+    let dir = scratch_dir("config-seed");
+    let config = dir.join("defaults.toml");
+    fs::write(&config, "setup_seed = 343\nthreads = 2\n").unwrap();
+
+    let first = workflow(&dir, "first", &["--config", path_str(&config)]);
+    let second = workflow(&dir, "second", &["--config", path_str(&config)]);
+    assert_eq!(first, second);
+
+    let seeded = workflow(&dir, "seeded", &["--setup-seed", "343"]);
+    assert_eq!(first, seeded);
+    let other = workflow(&dir, "other", &["--config", path_str(&config), "--setup-seed", "1"]);
+    assert_ne!(first, other);
+    let entropy = workflow(&dir, "entropy", &["--config", path_str(&config), "--setup-entropy"]);
+    assert_ne!(first, entropy);
+}
+
+#[test]
+fn bad_config_files_are_rejected() {
+    let dir = scratch_dir("config-bad");
+    for (text, message) in [
+        ("curve = \"bn254\"\nthread = 4\n", "unknown field `thread`"),
+        ("threads = 0\n", "threads must be at least 1"),
+        ("curve = \"secp256k1\"\n", "unknown variant"),
+    ] {
+        fs::write(dir.join("qa1.toml"), text).unwrap();
+        let output = qa1_in(&dir, &["info", "--r1cs", path_str(&fixture("mult.r1cs"))]);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert_eq!(output.status.code(), Some(2), "{}", stdout);
+        assert!(stdout.contains("invalid config file") && stdout.contains(message), "{}", stdout);
+    }
+
+    let output = qa1_in(&dir, &["info", "--r1cs", path_str(&fixture("mult.r1cs")), "--config", "missing.toml"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("could not read config file missing.toml"));
+}
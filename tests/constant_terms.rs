@@ -0,0 +1,81 @@
+use ark_bn254::{Bn254, Fr};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use dogecoin_zkp_generator_qa1::r1cs::{R1CSConstraint, Term};
+use dogecoin_zkp_generator_qa1::{prove, setup, verify, CircuitError, CircuitFromR1CS, Witness, R1CS};
+
+mod common;
+use common::fixture;
+
+// `mult.r1cs` is `out = x * y` over bn254 with wires [1, out, x, y], and
+// `mult.wtns` the witness [1, 35, 5, 7]
+
+fn term(wire_id: u32, coefficient: i64) -> Term<Fr> {
+    let magnitude = Fr::from(coefficient.unsigned_abs());
+    Term { wire_id, coefficient: if coefficient < 0 { -magnitude } else { magnitude } }
+}
+
+fn constraint(a: Vec<Term<Fr>>, b: Vec<Term<Fr>>, c: Vec<Term<Fr>>) -> R1CSConstraint<Fr> {
+    R1CSConstraint { a_terms: a, b_terms: b, c_terms: c }
+}
+
+// The fixture with constraints whose sides are only terms on wire 0, as
+// circom emits for constants. `c_out` is the constant side of x * y = c_out.
+fn with_constant_constraints(c_out: i64) -> R1CS<Fr> {
+    let mut r1cs = R1CS::<Fr>::read(fixture("mult.r1cs")).unwrap();
+    r1cs.constraints_mut().extend([
+        // x * y = 35
+        constraint(vec![term(2, 1)], vec![term(3, 1)], vec![term(0, c_out)]),
+        // (2 + 3) * 7 = 7x, with ONE split over two terms on A
+        constraint(vec![term(0, 2), term(0, 3)], vec![term(0, 7)], vec![term(2, 7)]),
+        // 0 * 0 = out - 35: a linear constraint with a negative constant
+        constraint(vec![], vec![], vec![term(1, 1), term(0, -35)]),
+        // 6 * 6 = 36, constant on every side
+        constraint(vec![term(0, 6)], vec![term(0, 6)], vec![term(0, 36)]),
+    ]);
+    let n_constraints = r1cs.constraints().len() as u32;
+    r1cs.header_mut().n_constraints = n_constraints;
+    r1cs
+}
+
+fn witness() -> Witness<Fr> {
+    Witness::<Fr>::read(fixture("mult.wtns")).unwrap()
+}
+
+fn arkworks_satisfied(r1cs: &R1CS<Fr>, witness: Witness<Fr>) -> bool {
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    CircuitFromR1CS::new(r1cs, witness).unwrap().generate_constraints(cs.clone()).unwrap();
+    cs.is_satisfied().unwrap()
+}
+
+#[test]
+fn constant_sides_are_enforced() {
+    let r1cs = with_constant_constraints(35);
+    assert_eq!(r1cs.check_witness(&witness().values), Ok(()));
+    assert!(arkworks_satisfied(&r1cs, witness()));
+
+    let wrong = with_constant_constraints(36);
+    assert_eq!(wrong.check_witness(&witness().values), Err(1));
+    assert!(!arkworks_satisfied(&wrong, witness()));
+}
+
+#[test]
+fn constant_constraints_prove_and_verify() {
+    let mut rng = StdRng::seed_from_u64(329);
+    let r1cs = with_constant_constraints(35);
+    let pk = setup::<Bn254, _>(CircuitFromR1CS::new_with_dummy(&r1cs).unwrap(), &mut rng).unwrap();
+    let circuit = CircuitFromR1CS::new(&r1cs, witness()).unwrap();
+    let public_inputs = circuit.get_public_inputs();
+    let proof = prove(&pk, circuit, &mut rng).unwrap();
+    assert!(verify(&pk.vk, &public_inputs, &proof).unwrap());
+}
+
+// Constants are multiplied by arkworks' ONE, not the witness' wire 0, so a
+// witness claiming another value for it is rejected up front
+#[test]
+fn witness_with_wire_0_not_one_is_rejected() {
+    let r1cs = with_constant_constraints(35);
+    let mut bad = witness();
+    bad.values[0] = Fr::from(2u64);
+    assert!(matches!(CircuitFromR1CS::new(&r1cs, bad), Err(CircuitError::OneWireNotOne)));
+}
@@ -0,0 +1,61 @@
+use ark_bn254::Fr;
+use dogecoin_zkp_generator_qa1::r1cs::R1CSConstraint;
+use dogecoin_zkp_generator_qa1::R1CS;
+
+mod common;
+use common::fixture;
+
+// `mult.r1cs` is `out = x * y` over bn254: one constraint, with A = x,
+// B = y and C = out; `mult_split.r1cs` has its constraints split over
+// several sections
+
+fn mult() -> R1CS<Fr> {
+    R1CS::<Fr>::read(fixture("mult.r1cs")).unwrap()
+}
+
+#[test]
+fn index_and_as_ref_agree_with_constraints() {
+    for name in ["mult.r1cs", "mult_split.r1cs", "empty.r1cs"] {
+        let r1cs = R1CS::<Fr>::read(fixture(name)).unwrap();
+        let slice: &[R1CSConstraint<Fr>] = r1cs.as_ref();
+        assert_eq!(slice.len(), r1cs.num_constraints() as usize, "{}", name);
+        assert_eq!(slice.len(), r1cs.constraints().len(), "{}", name);
+        for idx in 0..slice.len() {
+            assert_eq!(r1cs[idx], slice[idx], "{}", name);
+            assert_eq!(r1cs.constraint(idx), Some(&slice[idx]), "{}", name);
+        }
+    }
+}
+
+#[test]
+fn index_gives_the_parsed_terms() {
+    let r1cs = mult();
+    assert_eq!(r1cs[0].a_terms[0].wire_id, 2);
+    assert_eq!(r1cs[0].b_terms[0].wire_id, 3);
+    assert_eq!(r1cs[0].c_terms[0].wire_id, 1);
+    assert_eq!(r1cs.num_terms(0), Some((1, 1, 1)));
+}
+
+#[test]
+fn checked_access_past_the_end_is_none() {
+    let r1cs = mult();
+    let len = r1cs.as_ref().len();
+    assert!(r1cs.constraint(len).is_none());
+    assert!(r1cs.num_terms(len).is_none());
+    assert!(r1cs.constraint(usize::MAX).is_none());
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn index_past_the_end_panics() {
+    let r1cs = mult();
+    let len = r1cs.as_ref().len();
+    let _ = &r1cs[len];
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn index_into_an_empty_circuit_panics() {
+    let r1cs = R1CS::<Fr>::read(fixture("empty.r1cs")).unwrap();
+    let _ = &r1cs[0];
+}
@@ -0,0 +1,72 @@
+use ark_bn254::{Bn254, Fr};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use dogecoin_zkp_generator_qa1::r1cs::{R1CSConstraint, Term};
+use dogecoin_zkp_generator_qa1::{prove, setup, verify, CircuitFromR1CS, Witness, R1CS};
+
+mod common;
+use common::fixture;
+
+// `mult.r1cs` is `out = x * y` over bn254 with wires [1, out, x, y], and
+// `mult.wtns` the witness [1, 35, 5, 7]
+
+fn term(wire_id: u32, coefficient: u64) -> Term<Fr> {
+    Term { wire_id, coefficient: Fr::from(coefficient) }
+}
+
+// Scale the existing constraint to 3x * y = 3out and add the constraint
+// (x + 2) * y = out + 2y, then fix up the header's count
+fn transformed() -> R1CS<Fr> {
+    let mut r1cs = R1CS::<Fr>::read(fixture("mult.r1cs")).unwrap();
+    let constraint = &mut r1cs.constraints_mut()[0];
+    for term in constraint.a_terms.iter_mut().chain(constraint.c_terms.iter_mut()) {
+        term.coefficient *= Fr::from(3u64);
+    }
+    r1cs.constraints_mut().push(R1CSConstraint {
+        a_terms: vec![term(0, 2), term(2, 1)],
+        b_terms: vec![term(3, 1)],
+        c_terms: vec![term(1, 1), term(3, 2)],
+    });
+    
+    let n_constraints = r1cs.constraints().len() as u32;
+    r1cs.header_mut().n_constraints = n_constraints;
+    r1cs
+}
+
+fn witness(values: [u64; 4]) -> Witness<Fr> {
+    let mut witness = Witness::<Fr>::read(fixture("mult.wtns")).unwrap();
+    witness.values = values.map(Fr::from).to_vec();
+    witness
+}
+
+#[test]
+fn header_matches_the_edited_constraints() {
+    let r1cs = transformed();
+    assert_eq!(r1cs.num_constraints(), 2);
+    assert_eq!(r1cs.constraints().len(), 2);
+    assert_eq!(r1cs.num_public_values(), 1);
+    assert_eq!(r1cs.check_witness(&witness([1, 35, 5, 7]).values), Ok(()));
+    assert_eq!(r1cs.check_witness(&witness([1, 36, 5, 7]).values), Err(0));
+}
+
+#[test]
+fn edited_circuit_proves_and_verifies() {
+    let r1cs = transformed();
+    let mut rng = StdRng::seed_from_u64(205);
+    let pk = setup::<Bn254, _>(CircuitFromR1CS::new_with_dummy(&r1cs).unwrap(), &mut rng).unwrap();
+    let circuit = CircuitFromR1CS::new(&r1cs, witness([1, 35, 5, 7])).unwrap();
+    let proof = prove(&pk, circuit, &mut rng).unwrap();
+    
+    assert!(verify(&pk.vk, &[Fr::from(35u64)], &proof).unwrap());
+    assert!(!verify(&pk.vk, &[Fr::from(36u64)], &proof).unwrap());
+}
+
+// The recomputed header is what a written file declares
+#[test]
+fn edited_circuit_round_trips() {
+    let r1cs = transformed();
+    let mut bytes = Vec::new();
+    r1cs.write_to(&mut bytes).unwrap();
+    let read = R1CS::<Fr>::from_reader(std::io::Cursor::new(bytes)).unwrap();
+    assert_eq!(read.header(), r1cs.header());
+    assert!(read == r1cs);
+}
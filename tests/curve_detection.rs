@@ -0,0 +1,50 @@
+use std::fs;
+use num_bigint::BigUint;
+use dogecoin_zkp_generator_qa1::r1cs::R1CSHeader;
+use dogecoin_zkp_generator_qa1::{KnownCurve, R1CS};
+
+mod common;
+use common::fixture;
+
+// The scalar field moduli circom writes as the R1CS prime, in decimal
+const BN254_R: &str = "21888242871839275222246405745257275088548364400416034343698204186575808495617";
+const BLS12_381_R: &str = "52435875175126190479447740508185965837690552500527637822603658699938581184513";
+
+fn decimal(value: &str) -> BigUint {
+    value.parse().unwrap()
+}
+
+#[test]
+fn known_curve_moduli() {
+    assert_eq!(KnownCurve::Bn254.modulus(), decimal(BN254_R));
+    assert_eq!(KnownCurve::Bls12_381.modulus(), decimal(BLS12_381_R));
+}
+
+#[test]
+fn bn254_fixture_is_detected() {
+    let r1cs = R1CS::<ark_bn254::Fr>::read(fixture("mult.r1cs")).unwrap();
+    assert_eq!(r1cs.modulus_biguint(), decimal(BN254_R));
+    assert_eq!(r1cs.detect_curve(), Some(KnownCurve::Bn254));
+}
+
+#[test]
+fn bls12_381_fixture_is_detected() {
+    let r1cs = R1CS::<ark_bls12_381::Fr>::read(fixture("mult_bls12_381.r1cs")).unwrap();
+    assert_eq!(r1cs.modulus_biguint(), decimal(BLS12_381_R));
+    assert_eq!(r1cs.detect_curve(), Some(KnownCurve::Bls12_381));
+}
+
+#[test]
+fn other_primes_are_not_detected() {
+    // The prime follows the preamble, section header and field size
+    let mut bytes = fs::read(fixture("mult.r1cs")).unwrap();
+    bytes[28] ^= 2;
+    let path = std::env::temp_dir().join(format!("qa1-other-prime-{}.r1cs", std::process::id()));
+    fs::write(&path, bytes).unwrap();
+
+    let header = R1CSHeader::read(&path).unwrap();
+    assert_eq!(header.modulus_biguint(), decimal(BN254_R) + 2u32);
+    assert_eq!(header.detect_curve(), None);
+
+    fs::remove_file(&path).unwrap();
+}
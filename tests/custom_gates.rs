@@ -0,0 +1,82 @@
+use ark_bn254::Fr;
+use dogecoin_zkp_generator_qa1::{CircuitError, CircuitFromR1CS, R1CSError, R1CS};
+
+mod common;
+use common::{fixture, path_str, qa1, scratch_dir};
+
+// `mult_custom_gates.r1cs` is `mult_v2.r1cs` (`out = x * y`, version 2)
+// with the header's custom gates flag set, but without custom gate sections
+
+#[test]
+fn header_flag_is_parsed() {
+    let r1cs = R1CS::<Fr>::read(fixture("mult_custom_gates.r1cs")).unwrap();
+    assert!(r1cs.header().use_custom_gates);
+    assert!(r1cs.uses_custom_gates());
+    assert!(matches!(r1cs.assert_groth16_compatible(), Err(R1CSError::CustomGatesUnsupported)));
+
+    let plain = R1CS::<Fr>::read(fixture("mult_v2.r1cs")).unwrap();
+    assert!(!plain.uses_custom_gates());
+    assert!(plain.assert_groth16_compatible().is_ok());
+}
+
+#[test]
+fn setup_circuit_is_rejected() {
+    let r1cs = R1CS::<Fr>::read(fixture("mult_custom_gates.r1cs")).unwrap();
+    assert!(matches!(
+        CircuitFromR1CS::new_with_dummy(&r1cs),
+        Err(CircuitError::R1CS(R1CSError::CustomGatesUnsupported))
+    ));
+}
+
+// Setup refuses the file before a structurally wrong proving key could be written
+#[test]
+fn setup_fails_with_a_recompile_hint() {
+    let r1cs = fixture("mult_custom_gates.r1cs");
+    let dir = scratch_dir("custom-gates-setup");
+    let output = qa1(&[
+        "setup",
+        "--r1cs", path_str(&r1cs),
+        "--pk", path_str(&dir.join("pk.bin")),
+        "--vk", path_str(&dir.join("vk.bin")),
+        "--setup-seed", "340",
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(2), "{}", stdout);
+    assert!(stdout.contains("--O1 or --O2"), "{}", stdout);
+    assert!(!dir.join("pk.bin").exists());
+}
+
+#[test]
+fn prove_and_selfcheck_are_rejected() {
+    let r1cs = fixture("mult_custom_gates.r1cs");
+    let wtns = fixture("mult.wtns");
+    let dir = scratch_dir("custom-gates-prove");
+    let outputs = [
+        qa1(&[
+            "prove",
+            "--r1cs", path_str(&r1cs),
+            "--pk", path_str(&dir.join("pk.bin")),
+            "--wtns", path_str(&wtns),
+            "--proof", path_str(&dir.join("proof.bin")),
+            "--public", path_str(&dir.join("public.json")),
+        ]),
+        qa1(&["selfcheck", "--r1cs", path_str(&r1cs), "--wtns", path_str(&wtns), "--setup-seed", "340"]),
+        qa1(&["--r1cs", path_str(&r1cs), "--wtns", path_str(&wtns), "--setup-seed", "340"]),
+    ];
+    for output in outputs {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert_eq!(output.status.code(), Some(2), "{}", stdout);
+        assert!(stdout.contains("custom gates"), "{}", stdout);
+    }
+    assert!(!dir.join("proof.bin").exists());
+}
+
+// Commands that only inspect the circuit don't build a Groth16 circuit
+#[test]
+fn inspecting_commands_accept_it() {
+    let r1cs = fixture("mult_custom_gates.r1cs");
+    for command in ["info", "stats", "dump-json"] {
+        let output = qa1(&[command, "--r1cs", path_str(&r1cs)]);
+        assert!(output.status.success(), "{}: {}", command, String::from_utf8_lossy(&output.stdout));
+    }
+}
@@ -0,0 +1,84 @@
+use std::fs;
+use std::path::Path;
+
+mod common;
+use common::{field_element, linear_combination, path_str, prime_le, qa1, r1cs_bytes, scratch_dir, section};
+
+// The circuit has one constraint over the wires [1, out, x], where each side
+// is either empty or a single term: A = x, B = x, C = out.
+const OUT: u32 = 1;
+const X: u32 = 2;
+
+fn side(wire: Option<u32>) -> Vec<u8> {
+    match wire {
+        Some(wire) => linear_combination(&[(wire, field_element(1))]),
+        None => linear_combination(&[]),
+    }
+}
+
+fn write_r1cs(path: &Path, a: Option<u32>, b: Option<u32>, c: Option<u32>) {
+    let mut constraint = side(a);
+    constraint.extend(side(b));
+    constraint.extend(side(c));
+    fs::write(path, r1cs_bytes(&constraint)).unwrap();
+}
+
+fn write_wtns(path: &Path, values: &[u64]) {
+    let mut header = 32u32.to_le_bytes().to_vec();
+    header.extend_from_slice(&prime_le());
+    header.extend_from_slice(&(values.len() as u32).to_le_bytes());
+    let body: Vec<u8> = values.iter().flat_map(|&v| field_element(v)).collect();
+
+    let mut out = b"wtns".to_vec();
+    out.extend_from_slice(&2u32.to_le_bytes());
+    out.extend_from_slice(&2u32.to_le_bytes());
+    section(&mut out, 1, &header);
+    section(&mut out, 2, &body);
+    fs::write(path, out).unwrap();
+}
+
+// Run the whole workflow, returning whether the witness satisfied the
+// constraint; if it did, the proof must also verify
+fn satisfied(dir: &Path, a: bool, b: bool, c: bool, out: u64) -> bool {
+    let r1cs = dir.join("circuit.r1cs");
+    let wtns = dir.join("witness.wtns");
+    write_r1cs(&r1cs, a.then_some(X), b.then_some(X), c.then_some(OUT));
+    write_wtns(&wtns, &[1, out, 3]);
+
+    let output = qa1(&["--r1cs", path_str(&r1cs), "--wtns", path_str(&wtns), "--setup-seed", "1"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if output.status.success() {
+        assert!(stdout.contains("Proof verified successfully"), "{}", stdout);
+        true
+    } else {
+        assert!(stdout.contains("does not satisfy constraint #0"), "{}", stdout);
+        false
+    }
+}
+
+// circom reads every empty side as zero, so with x = 3 the constraint
+// A·B = C holds exactly when (A ? 3 : 0)·(B ? 3 : 0) = (C ? out : 0)
+#[test]
+fn empty_sides_are_zero_in_every_combination() {
+    let dir = scratch_dir("empty-lc");
+
+    for a in [false, true] {
+        for b in [false, true] {
+            for c in [false, true] {
+                let product = if a && b { 9 } else { 0 };
+                let case = format!("A {}, B {}, C {}",
+                                   if a { "= x" } else { "empty" },
+                                   if b { "= x" } else { "empty" },
+                                   if c { "= out" } else { "empty" });
+                if c {
+                    assert!(satisfied(&dir, a, b, c, product), "{}: out = {} should satisfy", case, product);
+                    assert!(!satisfied(&dir, a, b, c, product + 1), "{}: out = {} should not", case, product + 1);
+                } else {
+                    assert_eq!(satisfied(&dir, a, b, c, 0), product == 0, "{}", case);
+                }
+            }
+        }
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
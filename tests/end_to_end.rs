@@ -0,0 +1,55 @@
+use ark_bn254::{Bn254, Fr};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use dogecoin_zkp_generator_qa1::artifacts::{load_proof, load_verifying_key, save_proof, save_verifying_key};
+use dogecoin_zkp_generator_qa1::r1cs::{create_hardcoded_r1cs, hardcoded_witness};
+use dogecoin_zkp_generator_qa1::{prove, setup, verify, CircuitFromR1CS, Witness, R1CS};
+
+mod common;
+use common::{fixture, scratch_dir};
+
+// `mult.r1cs` is `out = x * y` over bn254, with wires [1, out, x, y] and
+// the witness x = 5, y = 7, out = 35
+
+// The same steps as main: parse, check the witness, set up, prove, save
+// the verifying key and proof, then verify what was loaded back
+fn prove_and_verify(test: &str, r1cs: &R1CS<Fr>, witness: Witness<Fr>) {
+    let mut rng = StdRng::seed_from_u64(338);
+    assert!(r1cs.check_witness(&witness.values).is_ok());
+
+    let circuit = CircuitFromR1CS::new(r1cs, witness).unwrap();
+    let public_inputs = circuit.get_public_inputs();
+    assert_eq!(public_inputs, [Fr::from(35u64)]);
+
+    let pk = setup::<Bn254, _>(CircuitFromR1CS::new_with_dummy(r1cs).unwrap(), &mut rng).unwrap();
+    let proof = prove(&pk, circuit, &mut rng).unwrap();
+
+    let dir = scratch_dir(test);
+    save_verifying_key(&pk.vk, dir.join("verifying_key.bin")).unwrap();
+    save_proof(&proof, dir.join("proof.bin")).unwrap();
+    let vk = load_verifying_key::<Bn254, _>(dir.join("verifying_key.bin")).unwrap();
+    let proof = load_proof::<Bn254, _>(dir.join("proof.bin")).unwrap();
+    assert!(verify(&vk, &public_inputs, &proof).unwrap());
+
+    // Changing the one public input must make the same proof fail
+    let mut tampered = public_inputs.clone();
+    tampered[0] += Fr::from(1u64);
+    assert!(!verify(&vk, &tampered, &proof).unwrap());
+}
+
+#[test]
+fn fixture_circuit_end_to_end() {
+    let r1cs = R1CS::<Fr>::read(fixture("mult.r1cs")).unwrap();
+    let witness = Witness::<Fr>::read(fixture("mult.wtns")).unwrap();
+    prove_and_verify("end-to-end-fixture", &r1cs, witness);
+}
+
+#[test]
+fn hardcoded_circuit_end_to_end() {
+    let r1cs = create_hardcoded_r1cs::<Fr>().unwrap();
+    let witness = Witness {
+        field_size: r1cs.header().field_size,
+        prime_bytes: r1cs.prime_field_modulus().to_vec(),
+        values: hardcoded_witness(5, 7),
+    };
+    prove_and_verify("end-to-end-hardcoded", &r1cs, witness);
+}
@@ -0,0 +1,35 @@
+use std::process::{Command, Output};
+
+mod common;
+use common::fixture;
+
+// The fixture circuit is `out = x * y` over bn254, with the witness
+// x = 5, y = 7, out = 35
+
+fn run_expecting(expected: &str) -> Output {
+    let (r1cs, wtns) = (fixture("mult.r1cs"), fixture("mult.wtns"));
+    Command::new(env!("CARGO_BIN_EXE_dogecoin_zkp_generator_qa1"))
+        .args(["--r1cs", r1cs.to_str().unwrap(), "--wtns", wtns.to_str().unwrap()])
+        .args(["--setup-seed", "319", "--expect-output", expected])
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn matching_output_proves() {
+    let output = run_expecting("35");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.contains("matches the expected value 35"), "{}", stdout);
+    assert!(stdout.contains("Proof verified successfully"), "{}", stdout);
+}
+
+// The witness already shows the wrong answer, so setup never runs
+#[test]
+fn wrong_output_stops_before_setup() {
+    let output = run_expecting("36");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(2), "{}", stdout);
+    assert!(stdout.contains("Public output is 35, but --expect-output expected 36"), "{}", stdout);
+    assert!(!stdout.contains("Running Groth16 setup"), "{}", stdout);
+}
@@ -0,0 +1,59 @@
+use std::fs;
+use ark_bn254::Fr;
+use ark_ff::PrimeField;
+use num_bigint::BigUint;
+use dogecoin_zkp_generator_qa1::witness::{load_public_inputs, parse_field_element};
+use dogecoin_zkp_generator_qa1::R1CSError;
+
+mod common;
+use common::{fixture, path_str, qa1, scratch_dir};
+
+// The fixture circuit is `out = x * y` over bn254, with the witness
+// x = 5, y = 7, out = 35
+
+// The bn254 scalar field modulus plus `offset`, in decimal
+fn modulus_plus(offset: u32) -> String {
+    (BigUint::from(Fr::MODULUS) + offset).to_string()
+}
+
+#[test]
+fn decimal_values_must_be_canonical() {
+    let p_minus_1 = (BigUint::from(Fr::MODULUS) - 1u32).to_string();
+    assert_eq!(parse_field_element::<Fr>(&p_minus_1), Some(-Fr::from(1u64)));
+    assert_eq!(parse_field_element::<Fr>(&modulus_plus(0)), None);
+    assert_eq!(parse_field_element::<Fr>(&modulus_plus(1)), None);
+    assert_eq!(parse_field_element::<Fr>("35"), Some(Fr::from(35u64)));
+    assert_eq!(parse_field_element::<Fr>("0"), Some(Fr::from(0u64)));
+    for malformed in ["", "-1", "+35", "3 5"] {
+        assert_eq!(parse_field_element::<Fr>(malformed), None, "{:?}", malformed);
+    }
+}
+
+#[test]
+fn public_json_rejects_the_modulus() {
+    let path = scratch_dir("field-elements-public").join("public.json");
+    for value in [modulus_plus(0), modulus_plus(1)] {
+        fs::write(&path, format!(r#"["{}"]"#, value)).unwrap();
+        assert!(matches!(
+            load_public_inputs::<Fr, _>(&path),
+            Err(R1CSError::InvalidPublicInput { index: 0, .. })
+        ));
+    }
+}
+
+// `p + 35` would equal the output 35 if it were reduced
+#[test]
+fn expect_output_rejects_non_canonical_values() {
+    let r1cs = fixture("mult.r1cs");
+    let wtns = fixture("mult.wtns");
+    let output = qa1(&[
+        "--r1cs", path_str(&r1cs),
+        "--wtns", path_str(&wtns),
+        "--setup-seed", "330",
+        "--no-verify",
+        "--expect-output", &modulus_plus(35),
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(2), "{}", stdout);
+    assert!(stdout.contains("is not a decimal or 0x-hex field element"), "{}", stdout);
+}
@@ -0,0 +1,36 @@
+use std::fs;
+use dogecoin_zkp_generator_qa1::r1cs::R1CSHeader;
+use dogecoin_zkp_generator_qa1::R1CSError;
+
+mod common;
+use common::{fixture, scratch_dir};
+
+// In both fixtures the header section comes first, so its field size is at
+// byte 24
+
+// field_size counts bytes, so a 254- or 255-bit prime is 32 of them
+#[test]
+fn standard_curves_have_32_byte_fields() {
+    for name in ["mult.r1cs", "mult_bls12_381.r1cs"] {
+        let header = R1CSHeader::read(fixture(name)).unwrap();
+        assert_eq!(header.field_size, 32, "{}", name);
+        assert_eq!(header.prime_bytes.len(), 32, "{}", name);
+    }
+}
+
+#[test]
+fn field_size_must_be_whole_words() {
+    let dir = scratch_dir("field-size");
+    for field_size in [0u32, 4, 33, 36] {
+        let mut bytes = fs::read(fixture("mult.r1cs")).unwrap();
+        bytes[24..28].copy_from_slice(&field_size.to_le_bytes());
+        let path = dir.join(format!("field-size-{}.r1cs", field_size));
+        fs::write(&path, bytes).unwrap();
+
+        match R1CSHeader::read(&path) {
+            Err(R1CSError::InvalidFieldSize(size)) => assert_eq!(size, field_size),
+            other => panic!("field size {}: expected InvalidFieldSize, got {:?}", field_size, other),
+        }
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
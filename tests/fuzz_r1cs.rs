@@ -0,0 +1,72 @@
+use std::io::Cursor;
+use ark_bn254::Fr;
+use ark_std::rand::rngs::StdRng;
+use ark_std::rand::{Rng, RngCore, SeedableRng};
+use dogecoin_zkp_generator_qa1::{R1CSError, R1CS};
+
+mod common;
+use common::fixture;
+
+// The fixture circuit is `out = x * y` over bn254. Its constraints section
+// body starts at byte 0x64, with the term count of the first A linear
+// combination.
+const FIRST_TERM_COUNT: usize = 0x64;
+
+fn fixture_bytes() -> Vec<u8> {
+    std::fs::read(fixture("mult.r1cs")).unwrap()
+}
+
+// Parsing must return, Ok or Err, without panicking or running out of memory
+fn parse(bytes: &[u8]) -> Result<R1CS<Fr>, R1CSError> {
+    R1CS::<Fr>::from_reader(Cursor::new(bytes))
+}
+
+#[test]
+fn huge_term_count_is_rejected() {
+    let mut bytes = fixture_bytes();
+    bytes[FIRST_TERM_COUNT..FIRST_TERM_COUNT + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+
+    match parse(&bytes) {
+        Err(R1CSError::ImplausibleTermCount { terms, num_wires }) => {
+            assert_eq!(terms, u32::MAX);
+            assert_eq!(num_wires, 4);
+        }
+        other => panic!("expected ImplausibleTermCount, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn random_bytes_never_panic() {
+    let mut rng = StdRng::seed_from_u64(296);
+    for _ in 0..2000 {
+        let mut bytes = vec![0u8; rng.gen_range(0..512)];
+        rng.fill_bytes(&mut bytes);
+        // A valid preamble gets the garbage past the first check
+        if rng.gen_bool(0.5) && bytes.len() >= 12 {
+            bytes[..12].copy_from_slice(&fixture_bytes()[..12]);
+        }
+        let _ = parse(&bytes);
+    }
+}
+
+#[test]
+fn mutated_fixture_never_panics() {
+    let original = fixture_bytes();
+    let mut rng = StdRng::seed_from_u64(296);
+    for _ in 0..5000 {
+        let mut bytes = original.clone();
+        for _ in 0..rng.gen_range(1..4) {
+            let pos = rng.gen_range(0..bytes.len());
+            if rng.gen_bool(0.5) && pos + 4 <= bytes.len() {
+                // Counts and sizes are u32s; corrupt one whole
+                bytes[pos..pos + 4].copy_from_slice(&rng.next_u32().to_le_bytes());
+            } else {
+                bytes[pos] = rng.gen();
+            }
+        }
+        if rng.gen_bool(0.1) {
+            bytes.truncate(rng.gen_range(0..bytes.len()));
+        }
+        let _ = parse(&bytes);
+    }
+}
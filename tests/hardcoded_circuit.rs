@@ -0,0 +1,59 @@
+use std::io::Cursor;
+use std::process::Command;
+use ark_bn254::{Bn254, Fr};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use dogecoin_zkp_generator_qa1::r1cs::{create_hardcoded_r1cs, hardcoded_witness};
+use dogecoin_zkp_generator_qa1::{prove, setup, verify, CircuitFromR1CS, Witness, R1CS};
+
+// The built-in `x * y = z` circuit, with wires [1, z, x, y]
+
+#[test]
+fn witness_is_in_circom_wire_order() {
+    let values = hardcoded_witness::<Fr>(5, 7);
+    assert_eq!(values, [Fr::from(1u64), Fr::from(35u64), Fr::from(5u64), Fr::from(7u64)]);
+
+    // z is reduced in the field rather than overflowing a u64
+    let values = hardcoded_witness::<Fr>(u64::MAX, u64::MAX);
+    assert_eq!(values[1], Fr::from(u64::MAX) * Fr::from(u64::MAX));
+}
+
+// parse -> witness -> check -> prove -> verify without any files
+#[test]
+fn parse_check_prove_verify() {
+    let mut bytes = Vec::new();
+    create_hardcoded_r1cs::<Fr>().unwrap().write_to(&mut bytes).unwrap();
+    let r1cs = R1CS::<Fr>::from_reader(Cursor::new(bytes)).unwrap();
+
+    let values = hardcoded_witness::<Fr>(5, 7);
+    assert_eq!(r1cs.check_witness(&values), Ok(()));
+    let mut wrong = values.clone();
+    wrong[1] += Fr::from(1u64);
+    assert_eq!(r1cs.check_witness(&wrong), Err(0));
+
+    let mut rng = StdRng::seed_from_u64(331);
+    let pk = setup::<Bn254, _>(CircuitFromR1CS::new_with_dummy(&r1cs).unwrap(), &mut rng).unwrap();
+    let witness = Witness {
+        field_size: r1cs.header().field_size,
+        prime_bytes: r1cs.header().prime_bytes.clone(),
+        values,
+    };
+    let circuit = CircuitFromR1CS::new(&r1cs, witness).unwrap();
+    assert_eq!(circuit.get_public_inputs(), [Fr::from(35u64)]);
+    let proof = prove(&pk, circuit, &mut rng).unwrap();
+    assert!(verify(&pk.vk, &[Fr::from(35u64)], &proof).unwrap());
+    assert!(!verify(&pk.vk, &[Fr::from(36u64)], &proof).unwrap());
+}
+
+#[test]
+fn demo_subcommand_proves_and_verifies() {
+    for curve in ["bn254", "bls12-381"] {
+        let output = Command::new(env!("CARGO_BIN_EXE_dogecoin_zkp_generator_qa1"))
+            .args(["demo", "--x", "3", "--y", "11", "--curve", curve, "--setup-seed", "1"])
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(output.status.success(), "{}: {}", curve, stdout);
+        assert!(stdout.contains("Witness [1, z, x, y] = [1, 33, 3, 11]"), "{}", stdout);
+        assert!(stdout.contains("Proof verified successfully"), "{}", stdout);
+    }
+}
@@ -0,0 +1,67 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use ark_bn254::Fr;
+use dogecoin_zkp_generator_qa1::r1cs::Term;
+use dogecoin_zkp_generator_qa1::R1CS;
+
+mod common;
+use common::{fixture, scratch_dir};
+
+// `mult.r1cs` is `out = x * y` over bn254, one constraint on wires 0 to 3
+
+fn info(path: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_dogecoin_zkp_generator_qa1"))
+        .args(["info", "--r1cs", path.to_str().unwrap()])
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn valid_file_prints_its_summary() {
+    let output = info(&fixture("mult.r1cs"));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.contains("Total wires: 4"), "{}", stdout);
+    assert!(stdout.contains("Constraints: 1"), "{}", stdout);
+    assert!(stdout.contains("are valid"), "{}", stdout);
+    assert!(!stdout.contains("Running Groth16 setup"), "{}", stdout);
+}
+
+// A circuit Groth16 can't prove is still a valid R1CS file
+#[test]
+fn custom_gates_are_only_a_warning() {
+    let output = info(&fixture("mult_custom_gates.r1cs"));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.contains("Constraints: 1"), "{}", stdout);
+    assert!(stdout.contains("uses custom gates"), "{}", stdout);
+}
+
+#[test]
+fn out_of_range_wire_fails() {
+    let mut r1cs = R1CS::<Fr>::read(fixture("mult.r1cs")).unwrap();
+    r1cs.constraints_mut()[0].c_terms.push(Term { wire_id: 9, coefficient: Fr::from(1u64) });
+    let path = scratch_dir("info-wire-range").join("bad.r1cs");
+    r1cs.write(&path).unwrap();
+    
+    let output = info(&path);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(2), "{}", stdout);
+    assert!(stdout.contains("wire 9 in C"), "{}", stdout);
+}
+
+#[test]
+fn constraint_count_mismatch_fails() {
+    // The header section starts at byte 24, and its constraint count follows
+    // the field size, the prime, four wire counts and the label count
+    let mut bytes = fs::read(fixture("mult.r1cs")).unwrap();
+    bytes[84..88].copy_from_slice(&2u32.to_le_bytes());
+    let path = scratch_dir("info-constraint-count").join("bad.r1cs");
+    fs::write(&path, bytes).unwrap();
+    
+    let output = info(&path);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(2), "{}", stdout);
+    assert!(!stdout.contains("are valid"), "{}", stdout);
+}
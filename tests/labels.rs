@@ -0,0 +1,77 @@
+use std::fs;
+use std::io::Cursor;
+use ark_bn254::Fr;
+use dogecoin_zkp_generator_qa1::r1cs::R1CSError;
+use dogecoin_zkp_generator_qa1::sym::SymbolTable;
+use dogecoin_zkp_generator_qa1::R1CS;
+
+mod common;
+use common::{fixture, scratch_dir};
+
+// `mult.r1cs` is `out = x * y`, wires [1, out, x, y] labelled 0 to 3
+
+#[test]
+fn labels_come_from_the_header_and_wire_map() {
+    let r1cs = R1CS::<Fr>::read(fixture("mult.r1cs")).unwrap();
+    assert_eq!(r1cs.num_labels(), 4);
+    assert_eq!(r1cs.info().num_labels, 4);
+    assert_eq!((0..4).map(|wire| r1cs.label(wire)).collect::<Vec<_>>(), [Some(0), Some(1), Some(2), Some(3)]);
+    assert_eq!(r1cs.label(4), None);
+}
+
+// Names resolve through the wire map's labels, not the `.sym` wire column
+#[test]
+fn wire_names_resolve_through_labels() {
+    let mut r1cs = R1CS::<Fr>::read(fixture("mult.r1cs")).unwrap();
+    r1cs.header_mut().n_labels = 5;
+    let mut bytes = Vec::new();
+    r1cs.write_to(&mut bytes).unwrap();
+    let mut patched = R1CS::<Fr>::from_reader(Cursor::new(bytes)).unwrap();
+    assert_eq!(patched.num_labels(), 5);
+
+    // Label 4 is an alias of `x` that circom kept on the same wire
+    let sym = scratch_dir("labels").join("mult.sym");
+    fs::write(&sym, "1,1,0,main.out\n2,2,0,main.x\n3,3,0,main.y\n4,2,1,main.sub.a\n").unwrap();
+    let symbols = SymbolTable::read(&sym).unwrap();
+    assert_eq!(symbols.label_name(4), Some("main.sub.a"));
+    assert_eq!(symbols.label_name(0), None);
+
+    assert_eq!(patched.wire_name(1, &symbols), Some("main.out"));
+    assert_eq!(patched.wire_name(2, &symbols), Some("main.x"));
+    assert_eq!(patched.wire_name(0, &symbols), None);
+
+    // Pointing the wire map at the alias renames the wire
+    let mut bytes = Vec::new();
+    patched.write_to(&mut bytes).unwrap();
+    patched = R1CS::<Fr>::from_reader(Cursor::new(patch_label(bytes, 2, 4))).unwrap();
+    assert_eq!(patched.wire_name(2, &symbols), Some("main.sub.a"));
+}
+
+#[test]
+fn labels_past_the_header_count_are_rejected() {
+    let mut r1cs = R1CS::<Fr>::read(fixture("mult.r1cs")).unwrap();
+    r1cs.header_mut().n_labels = 3;
+    let mut bytes = Vec::new();
+    r1cs.write_to(&mut bytes).unwrap();
+
+    match R1CS::<Fr>::from_reader(Cursor::new(bytes)) {
+        Err(R1CSError::LabelOutOfRange { wire, label, n_labels }) => assert_eq!((wire, label, n_labels), (3, 3, 3)),
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("a label past the header's count parsed"),
+    }
+}
+
+// Overwrite one wire's entry in the wire map section (type 3)
+fn patch_label(mut bytes: Vec<u8>, wire: usize, label: u64) -> Vec<u8> {
+    let mut pos = 12;
+    loop {
+        let section_type = u32::from_le_bytes(bytes[pos..pos + 4].try_into().unwrap());
+        let size = u64::from_le_bytes(bytes[pos + 4..pos + 12].try_into().unwrap()) as usize;
+        if section_type == 3 {
+            let entry = pos + 12 + wire * 8;
+            bytes[entry..entry + 8].copy_from_slice(&label.to_le_bytes());
+            return bytes;
+        }
+        pos += 12 + size;
+    }
+}
@@ -0,0 +1,49 @@
+use std::cell::RefCell;
+use ark_bn254::{Bn254, Fr};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use dogecoin_zkp_generator_qa1::r1cs::Term;
+use dogecoin_zkp_generator_qa1::{prove, setup, verify, CircuitError, CircuitFromR1CS, LazyCircuitFromR1CS, R1CS};
+
+mod common;
+use common::fixture;
+
+// `mult.r1cs` is `out = x * y` over bn254 with wires [1, out, x, y]
+
+// Wire values for 5 * 7 = 35, recording which wires were asked for
+fn witness_fn(calls: &RefCell<Vec<usize>>) -> Box<dyn Fn(usize) -> Fr + '_> {
+    Box::new(move |wire| {
+        calls.borrow_mut().push(wire);
+        Fr::from([1u64, 35, 5, 7][wire])
+    })
+}
+
+#[test]
+fn lazy_witness_proves_in_wire_order() {
+    let mut rng = StdRng::seed_from_u64(339);
+    let r1cs = R1CS::<Fr>::read(fixture("mult.r1cs")).unwrap();
+    let pk = setup::<Bn254, _>(CircuitFromR1CS::new_with_dummy(&r1cs).unwrap(), &mut rng).unwrap();
+
+    let calls = RefCell::new(Vec::new());
+    let circuit = LazyCircuitFromR1CS::new(&r1cs, witness_fn(&calls)).unwrap();
+    let proof = prove(&pk, circuit, &mut rng).unwrap();
+    assert_eq!(*calls.borrow(), [1, 2, 3]);
+
+    let circuit = LazyCircuitFromR1CS::new(&r1cs, witness_fn(&calls)).unwrap();
+    let public_inputs = circuit.get_public_inputs();
+    assert_eq!(public_inputs, [Fr::from(35u64)]);
+    assert!(verify(&pk.vk, &public_inputs, &proof).unwrap());
+}
+
+// The circuit is checked up front even though the values can't be
+#[test]
+fn lazy_circuit_checks_wires() {
+    let mut r1cs = R1CS::<Fr>::read(fixture("mult.r1cs")).unwrap();
+    r1cs.constraints_mut()[0].c_terms.push(Term { wire_id: 4, coefficient: Fr::from(1u64) });
+
+    let calls = RefCell::new(Vec::new());
+    assert!(matches!(
+        LazyCircuitFromR1CS::new(&r1cs, witness_fn(&calls)),
+        Err(CircuitError::WireOutOfRange { constraint: 0, wire: 4, num_wires: 4 })
+    ));
+    assert!(calls.borrow().is_empty());
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "mmap")]
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use ark_ff::PrimeField;
+use flate2::write::GzEncoder;
+use dogecoin_zkp_generator_qa1::r1cs::ReadOptions;
+use dogecoin_zkp_generator_qa1::{R1CSError, R1CS};
+
+mod common;
+use common::{fixture, scratch_dir};
+
+fn assert_same_as_read<F: PrimeField>(path: &Path, options: &ReadOptions) {
+    let mapped = R1CS::<F>::read_mmap(path, options).unwrap();
+    let read = R1CS::<F>::read_with_options(path, options).unwrap();
+    assert!(mapped == read, "{}", path.display());
+    assert_eq!(mapped.header(), read.header(), "{}", path.display());
+}
+
+#[test]
+fn parses_fixtures_like_read() {
+    for name in ["mult.r1cs", "mult_v2.r1cs", "mult_split.r1cs", "mult_custom_gates.r1cs", "empty.r1cs"] {
+        assert_same_as_read::<ark_bn254::Fr>(&fixture(name), &ReadOptions::default());
+    }
+    assert_same_as_read::<ark_bls12_381::Fr>(&fixture("mult_bls12_381.r1cs"), &ReadOptions::default());
+    assert_same_as_read::<ark_bls12_381::Fq>(&fixture("mult_fq48.r1cs"), &ReadOptions::default());
+}
+
+#[test]
+fn honours_read_options() {
+    let path = fixture("mult.r1cs");
+    let parallel = ReadOptions { parallel: true, ..ReadOptions::default() };
+    assert_same_as_read::<ark_bn254::Fr>(&path, &parallel);
+    let public_count = ReadOptions { public_count: Some(2), ..ReadOptions::default() };
+    assert_same_as_read::<ark_bn254::Fr>(&path, &public_count);
+    assert_eq!(R1CS::<ark_bn254::Fr>::read_mmap(&path, &public_count).unwrap().num_public_values(), 2);
+}
+
+// Compressed files can't be parsed from the map and are read normally
+#[test]
+fn falls_back_for_compressed_files() {
+    let path = scratch_dir("mmap-gzip").join("mult.r1cs.gz");
+    let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&fs::read(fixture("mult.r1cs")).unwrap()).unwrap();
+    fs::write(&path, encoder.finish().unwrap()).unwrap();
+    
+    let mapped = R1CS::<ark_bn254::Fr>::read_mmap(&path, &ReadOptions::default()).unwrap();
+    assert!(mapped == R1CS::<ark_bn254::Fr>::read(fixture("mult.r1cs")).unwrap());
+}
+
+#[test]
+fn reports_the_same_errors() {
+    let path = scratch_dir("mmap-truncated").join("short.r1cs");
+    fs::write(&path, b"r1c").unwrap();
+    assert!(matches!(
+        R1CS::<ark_bn254::Fr>::read_mmap(&path, &ReadOptions::default()),
+        Err(R1CSError::TruncatedPreamble(3))
+    ));
+}
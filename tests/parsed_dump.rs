@@ -0,0 +1,111 @@
+use std::fs;
+use ark_bn254::Fr;
+use dogecoin_zkp_generator_qa1::r1cs::{parsed_path, ReadOptions};
+use dogecoin_zkp_generator_qa1::{R1CSError, R1CS};
+
+mod common;
+use common::{fixture, scratch_dir};
+
+// `mult.r1cs` is `out = x * y` over bn254 with a wire map; `mult_v2.r1cs`
+// is the same circuit with a version 2 header
+
+#[test]
+fn dump_round_trips() {
+    let dir = scratch_dir("parsed-round-trip");
+    for name in ["mult.r1cs", "mult_v2.r1cs"] {
+        let source = fixture(name);
+        let r1cs = R1CS::<Fr>::read(&source).unwrap();
+        let dump = dir.join(format!("{}.parsed", name));
+        r1cs.save_parsed(&dump, &source).unwrap();
+        assert!(R1CS::<Fr>::load_parsed(&dump, &source).unwrap() == r1cs, "{}", name);
+    }
+}
+
+#[test]
+fn changed_source_makes_the_dump_stale() {
+    let dir = scratch_dir("parsed-stale");
+    let source = dir.join("mult.r1cs");
+    fs::copy(fixture("mult.r1cs"), &source).unwrap();
+    let dump = dir.join("mult.parsed");
+    R1CS::<Fr>::read(&source).unwrap().save_parsed(&dump, &source).unwrap();
+
+    fs::copy(fixture("mult_v2.r1cs"), &source).unwrap();
+    assert!(matches!(R1CS::<Fr>::load_parsed(&dump, &source), Err(R1CSError::StaleParsedDump)));
+    assert!(matches!(R1CS::<Fr>::load_parsed(&source, &source), Err(R1CSError::BadParsedMagic)));
+}
+
+#[test]
+fn cached_reads_go_through_the_dump() {
+    let dir = scratch_dir("parsed-cache");
+    let source = dir.join("mult.r1cs");
+    fs::copy(fixture("mult.r1cs"), &source).unwrap();
+    let options = ReadOptions { cache: true, ..ReadOptions::default() };
+
+    let parsed = R1CS::<Fr>::read_with_options(&source, &options).unwrap();
+    assert!(parsed == R1CS::<Fr>::read(&source).unwrap());
+    assert!(R1CS::<Fr>::load_parsed(parsed_path(&source), &source).unwrap() == parsed);
+
+    // The dump keeps the header's counts; overrides apply on top of it
+    let overridden = ReadOptions { public_count: Some(2), ..options.clone() };
+    assert_eq!(R1CS::<Fr>::read_with_options(&source, &overridden).unwrap().num_public_values(), 2);
+    assert_eq!(R1CS::<Fr>::load_parsed(parsed_path(&source), &source).unwrap().num_public_values(), 1);
+
+    // A stale dump is replaced
+    fs::copy(fixture("mult_v2.r1cs"), &source).unwrap();
+    let reparsed = R1CS::<Fr>::read_with_options(&source, &options).unwrap();
+    assert_eq!(reparsed.header().version, 2);
+    assert_eq!(R1CS::<Fr>::load_parsed(parsed_path(&source), &source).unwrap().header().version, 2);
+}
+
+// Lengths in a corrupt dump are not trusted to size anything: a huge
+// constraint or term count runs out of bytes instead of allocating
+#[test]
+fn huge_counts_in_a_corrupt_dump_are_errors() {
+    let dir = scratch_dir("parsed-huge-counts");
+    let source = fixture("mult.r1cs");
+    let dump = dir.join("mult.parsed");
+    R1CS::<Fr>::read(&source).unwrap().save_parsed(&dump, &source).unwrap();
+    let bytes = fs::read(&dump).unwrap();
+
+    // bincode with fixed-width integers: the 40-byte key (magic, format,
+    // SHA-256), then the header with its 32-byte prime, whose n_constraints
+    // is at 108; the constraint count is at 117 and the first term count
+    // right after it
+    assert_eq!(u32::from_le_bytes(bytes[108..112].try_into().unwrap()), 1);
+    assert_eq!(u64::from_le_bytes(bytes[117..125].try_into().unwrap()), 1);
+    assert_eq!(u64::from_le_bytes(bytes[125..133].try_into().unwrap()), 1);
+    for offset in [117, 125] {
+        let mut corrupt = bytes.clone();
+        corrupt[offset..offset + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+        let path = dir.join(format!("corrupt-{}.parsed", offset));
+        fs::write(&path, corrupt).unwrap();
+        assert!(matches!(R1CS::<Fr>::load_parsed(&path, &source), Err(R1CSError::Io(_))), "{}", offset);
+    }
+}
+
+// A dump is checked like the .r1cs file it came from, so a label the
+// header doesn't declare is rejected rather than loaded
+#[test]
+fn out_of_range_label_in_a_dump_is_rejected() {
+    let dir = scratch_dir("parsed-label-range");
+    let source = fixture("mult.r1cs");
+    let r1cs = R1CS::<Fr>::read(&source).unwrap();
+    let dump = dir.join("mult.parsed");
+    r1cs.save_parsed(&dump, &source).unwrap();
+    let mut bytes = fs::read(&dump).unwrap();
+
+    // Find the wire map by its encoding: its length, then each label
+    let labels = r1cs.wire_to_label();
+    let mut wire_map = (labels.len() as u64).to_le_bytes().to_vec();
+    wire_map.extend(labels.iter().flat_map(|label| label.to_le_bytes()));
+    let start = bytes.windows(wire_map.len()).position(|window| window == wire_map).unwrap();
+    let last = start + wire_map.len() - 8;
+    let n_labels = r1cs.header().n_labels;
+    bytes[last..last + 8].copy_from_slice(&n_labels.to_le_bytes());
+    fs::write(&dump, bytes).unwrap();
+
+    match R1CS::<Fr>::load_parsed(&dump, &source) {
+        Err(R1CSError::LabelOutOfRange { wire, label, .. }) => assert_eq!((wire as usize, label), (labels.len() - 1, n_labels)),
+        other => panic!("expected LabelOutOfRange, got {:?}", other.map(|_| ())),
+    }
+}
@@ -0,0 +1,54 @@
+use std::fs;
+use std::io::{Cursor, Read};
+use ark_bn254::Fr;
+use dogecoin_zkp_generator_qa1::{R1CSError, R1CS};
+
+mod common;
+use common::fixture;
+
+#[test]
+fn probe_reports_version_and_section_count() {
+    let (version, sections) = R1CS::<Fr>::probe(fs::File::open(fixture("mult.r1cs")).unwrap()).unwrap();
+    assert_eq!(version, 1);
+    assert_eq!(sections, 3);
+    
+    let (version, _) = R1CS::<Fr>::probe(fs::File::open(fixture("mult_v2.r1cs")).unwrap()).unwrap();
+    assert_eq!(version, 2);
+}
+
+#[test]
+fn probe_reads_only_the_preamble() {
+    let bytes = fs::read(fixture("mult.r1cs")).unwrap();
+    let mut reader = Cursor::new(&bytes);
+    R1CS::<Fr>::probe(&mut reader).unwrap();
+    assert_eq!(reader.position(), 12);
+    
+    // Anything past the preamble may be garbage
+    let mut rest = Vec::new();
+    reader.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest.len(), bytes.len() - 12);
+    assert!(R1CS::<Fr>::probe(&bytes[..12]).is_ok());
+}
+
+#[test]
+fn probe_rejects_a_truncated_file_cleanly() {
+    let err = R1CS::<Fr>::probe(&b"r1c"[..]).unwrap_err();
+    assert!(matches!(err, R1CSError::TruncatedPreamble(3)), "{:?}", err);
+    assert!(err.to_string().contains("only 3 bytes"), "{}", err);
+    
+    // Parsing the whole file reports the same error
+    assert!(matches!(
+        R1CS::<Fr>::from_reader(Cursor::new(b"r1c")),
+        Err(R1CSError::TruncatedPreamble(3))
+    ));
+}
+
+#[test]
+fn probe_rejects_bad_magic_and_versions() {
+    assert!(matches!(R1CS::<Fr>::probe(&b"wtns\x02\0\0\0\x02\0\0\0"[..]), Err(R1CSError::BadMagic)));
+    assert!(matches!(R1CS::<Fr>::probe(&b"nope"[..]), Err(R1CSError::BadMagic)));
+    assert!(matches!(
+        R1CS::<Fr>::probe(&b"r1cs\x03\0\0\0\x03\0\0\0"[..]),
+        Err(R1CSError::UnsupportedVersion(3))
+    ));
+}
@@ -0,0 +1,94 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Output;
+use ark_bn254::Fr;
+use serde_json::{json, Value};
+use dogecoin_zkp_generator_qa1::r1cs::{R1CSError, ReadOptions};
+use dogecoin_zkp_generator_qa1::R1CS;
+
+mod common;
+use common::{fixture, path_str, qa1, scratch_dir};
+
+// `mult.r1cs` is `out = x * y` with wires [1, out, x, y]: one public output
+// and two private inputs. The witness has x = 5, y = 7.
+
+// A copy of the fixture whose header forgets that `out` is public
+fn mislabeled(dir: &Path) -> PathBuf {
+    let mut r1cs = R1CS::<Fr>::read(fixture("mult.r1cs")).unwrap();
+    r1cs.header_mut().n_pub_out = 0;
+    let path = dir.join("mislabeled.r1cs");
+    r1cs.write(&path).unwrap();
+    path
+}
+
+#[test]
+fn override_recounts_public_wires() {
+    let mut r1cs = R1CS::<Fr>::read(fixture("mult.r1cs")).unwrap();
+    r1cs.override_public_count(3).unwrap();
+    assert_eq!((r1cs.num_public_outputs(), r1cs.num_public_inputs(), r1cs.num_private_inputs()), (1, 2, 0));
+
+    r1cs.override_public_count(0).unwrap();
+    assert_eq!((r1cs.num_public_outputs(), r1cs.num_public_inputs(), r1cs.num_private_inputs()), (0, 0, 0));
+
+    assert!(matches!(
+        r1cs.override_public_count(4),
+        Err(R1CSError::PublicCountOutOfRange { requested: 4, num_wires: 4 })
+    ));
+}
+
+#[test]
+fn read_options_apply_the_override() {
+    let dir = scratch_dir("public-count-read");
+    let path = mislabeled(&dir);
+    assert_eq!(R1CS::<Fr>::read(&path).unwrap().num_public_values(), 0);
+
+    let options = ReadOptions { public_count: Some(1), ..ReadOptions::default() };
+    let r1cs = R1CS::<Fr>::read_with_options(&path, &options).unwrap();
+    assert_eq!((r1cs.num_public_outputs(), r1cs.num_public_inputs()), (0, 1));
+}
+
+fn prove(r1cs: &Path, out_dir: &Path, extra: &[&str]) -> (Output, Value) {
+    let wtns = fixture("mult.wtns");
+    let mut args = vec![
+        "--r1cs", path_str(r1cs),
+        "--wtns", path_str(&wtns),
+        "--out-dir", path_str(out_dir),
+        "--setup-seed", "337",
+    ];
+    args.extend_from_slice(extra);
+    let output = qa1(&args);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let public = serde_json::from_str(&fs::read_to_string(out_dir.join("public.json")).unwrap()).unwrap();
+    (output, public)
+}
+
+#[test]
+fn workflow_exposes_the_overridden_wires() {
+    let dir = scratch_dir("public-count-workflow");
+    let path = mislabeled(&dir);
+
+    let (_, public) = prove(&path, &dir.join("header"), &[]);
+    assert_eq!(public, json!([]));
+
+    let (output, public) = prove(&path, &dir.join("override"), &["--public-count", "1"]);
+    assert_eq!(public, json!(["35"]));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("--public-count 1 overrides the 0 public values"), "{}", stdout);
+
+    // Agreeing with the header is allowed and stays quiet
+    let (output, _) = prove(&fixture("mult.r1cs"), &dir.join("agree"), &["--public-count", "1"]);
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("overrides"));
+}
+
+#[test]
+fn override_past_the_wire_count_fails() {
+    let dir = scratch_dir("public-count-range");
+    let output = qa1(&[
+        "--r1cs", path_str(&fixture("mult.r1cs")),
+        "--wtns", path_str(&fixture("mult.wtns")),
+        "--out-dir", path_str(&dir),
+        "--setup-seed", "337",
+        "--public-count", "9",
+    ]);
+    assert_eq!(output.status.code(), Some(2));
+}
@@ -0,0 +1,79 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use ark_bn254::Fr;
+use serde_json::{json, Value};
+use dogecoin_zkp_generator_qa1::snarkjs_export::{public_inputs_to_hex_json, public_inputs_to_json, Endianness};
+use dogecoin_zkp_generator_qa1::witness::{load_public_inputs, load_public_inputs_with_endianness};
+
+mod common;
+use common::{fixture, path_str, qa1, scratch_dir};
+
+// The fixture circuit is `out = x * y` over bn254, with the witness
+// x = 5, y = 7, out = 35 (0x23)
+
+fn word(last_byte: &str, endianness: Endianness) -> String {
+    let zeros = "00".repeat(31);
+    match endianness {
+        Endianness::Little => format!("0x{}{}", last_byte, zeros),
+        Endianness::Big => format!("0x{}{}", zeros, last_byte),
+    }
+}
+
+#[test]
+fn hex_words_in_both_byte_orders() {
+    let inputs = [Fr::from(35u64), Fr::from(0u64)];
+    assert_eq!(public_inputs_to_json(&inputs), json!(["35", "0"]));
+    for endianness in [Endianness::Little, Endianness::Big] {
+        let encoded = public_inputs_to_hex_json(&inputs, endianness);
+        assert_eq!(encoded, json!([word("23", endianness), word("00", endianness)]));
+
+        let path = scratch_dir(&format!("endianness-{:?}", endianness)).join("public.json");
+        fs::write(&path, encoded.to_string()).unwrap();
+        assert_eq!(load_public_inputs_with_endianness::<Fr, _>(&path, endianness).unwrap(), inputs);
+    }
+
+    // Big-endian words are plain hex numbers, little-endian ones aren't
+    let path = scratch_dir("endianness-default").join("public.json");
+    fs::write(&path, public_inputs_to_hex_json(&inputs, Endianness::Big).to_string()).unwrap();
+    assert_eq!(load_public_inputs::<Fr, _>(&path).unwrap(), inputs);
+    fs::write(&path, public_inputs_to_hex_json(&inputs, Endianness::Little).to_string()).unwrap();
+    assert_ne!(load_public_inputs::<Fr, _>(&path).unwrap()[0], inputs[0]);
+}
+
+// The workflow writes public.json in the chosen byte order, and reading it
+// back needs the same --endianness
+fn prove_with(endianness: &str) -> (PathBuf, Value) {
+    let out_dir = scratch_dir(&format!("endianness-workflow-{}", endianness));
+    let output = qa1(&[
+        "--r1cs", path_str(&fixture("mult.r1cs")),
+        "--wtns", path_str(&fixture("mult.wtns")),
+        "--out-dir", path_str(&out_dir),
+        "--setup-seed", "330",
+        "--endianness", endianness,
+    ]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let public: Value = serde_json::from_str(&fs::read_to_string(out_dir.join("public.json")).unwrap()).unwrap();
+    (out_dir, public)
+}
+
+fn verify_only(out_dir: &Path, extra: &[&str]) -> Option<i32> {
+    let mut args = vec!["--verify-only", "--out-dir", path_str(out_dir)];
+    args.extend_from_slice(extra);
+    qa1(&args).status.code()
+}
+
+#[test]
+fn workflow_writes_and_reads_little_endian() {
+    let (out_dir, public) = prove_with("le");
+    assert_eq!(public, json!([word("23", Endianness::Little)]));
+    assert_eq!(verify_only(&out_dir, &["--endianness", "le"]), Some(0));
+    assert_eq!(verify_only(&out_dir, &[]), Some(1));
+}
+
+#[test]
+fn workflow_writes_and_reads_big_endian() {
+    let (out_dir, public) = prove_with("be");
+    assert_eq!(public, json!([word("23", Endianness::Big)]));
+    assert_eq!(verify_only(&out_dir, &["--endianness", "be"]), Some(0));
+    assert_eq!(verify_only(&out_dir, &[]), Some(0));
+}
@@ -0,0 +1,47 @@
+use ark_bn254::Fr;
+use dogecoin_zkp_generator_qa1::public_hash::{hash_public_inputs, HashFunction};
+
+// Known answers computed independently over the 32-byte little-endian
+// encoding of each input
+fn hex_hash(inputs: &[Fr], function: HashFunction) -> String {
+    hex::encode(hash_public_inputs(inputs, function))
+}
+
+#[test]
+fn no_inputs_hash_the_empty_string() {
+    assert_eq!(
+        hex_hash(&[], HashFunction::Sha256),
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+    assert_eq!(
+        hex_hash(&[], HashFunction::Keccak256),
+        "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+    );
+}
+
+#[test]
+fn single_input_known_answer() {
+    let inputs = [Fr::from(35u64)];
+    assert_eq!(
+        hex_hash(&inputs, HashFunction::Sha256),
+        "cb1b385900e78ef9265ec5c6e44e97eb1d5c3411d2b4667b606876a9c89e5403"
+    );
+    assert_eq!(
+        hex_hash(&inputs, HashFunction::Keccak256),
+        "58d63c505e87cda75eda1363a044cea6f600bb9b3c8c35eedfcdc1c4393c4e82"
+    );
+}
+
+// -1 is hashed as the reduced value r - 1, not as a negative number
+#[test]
+fn inputs_are_hashed_in_order_and_reduced() {
+    let inputs = [Fr::from(1u64), Fr::from(2u64), -Fr::from(1u64)];
+    assert_eq!(
+        hex_hash(&inputs, HashFunction::Sha256),
+        "1988412e2acf21bc319348dad7d533696ab745ebf1626d797287f96aaffa2997"
+    );
+    assert_eq!(
+        hex_hash(&inputs, HashFunction::Keccak256),
+        "b113597c4067cbe49ca74cd62f24966f73380414b5de514b981fb2bf7c8e91ab"
+    );
+}
@@ -0,0 +1,47 @@
+use std::fs;
+use std::path::Path;
+use std::process::Output;
+
+mod common;
+use common::{fixture, qa1};
+
+// `mult.r1cs` is a version 1 file; `mult_v2.r1cs` is the same circuit as
+// version 2, whose header adds the custom gates flag (unset) and four bytes
+// of a field this parser doesn't know about.
+
+fn info(path: &Path) -> Output {
+    qa1(&["info", "--r1cs", path.to_str().unwrap()])
+}
+
+fn assert_parses(name: &str) {
+    let output = info(&fixture(name));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}: {}", name, stdout);
+    assert!(stdout.contains("Total wires: 4"), "{}: {}", name, stdout);
+    assert!(stdout.contains("Constraints: 1"), "{}: {}", name, stdout);
+}
+
+#[test]
+fn version_1_parses() {
+    assert_parses("mult.r1cs");
+}
+
+#[test]
+fn version_2_parses() {
+    assert_parses("mult_v2.r1cs");
+}
+
+#[test]
+fn version_3_is_rejected() {
+    let mut bytes = fs::read(fixture("mult.r1cs")).unwrap();
+    bytes[4..8].copy_from_slice(&3u32.to_le_bytes());
+    let path = std::env::temp_dir().join(format!("qa1-version-3-{}.r1cs", std::process::id()));
+    fs::write(&path, bytes).unwrap();
+
+    let output = info(&path);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success(), "{}", stdout);
+    assert!(stdout.contains("Unsupported R1CS version: 3"), "{}", stdout);
+
+    fs::remove_file(&path).unwrap();
+}
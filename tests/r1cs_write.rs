@@ -0,0 +1,77 @@
+use std::fs;
+use std::io::Cursor;
+use ark_ff::PrimeField;
+use ark_bn254::Fr;
+use dogecoin_zkp_generator_qa1::r1cs::Term;
+use dogecoin_zkp_generator_qa1::R1CS;
+
+mod common;
+use common::{fixture, scratch_dir};
+
+// The fixtures are all `out = x * y`: `mult.r1cs` over bn254,
+// `mult_bls12_381.r1cs` over bls12-381, and `mult_v2.r1cs` a version 2
+// file with four header bytes this parser doesn't know about.
+
+fn to_bytes<F: PrimeField>(r1cs: &R1CS<F>) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    r1cs.write_to(&mut bytes).unwrap();
+    bytes
+}
+
+// read -> write -> read gives back the same circuit
+fn assert_round_trips<F: PrimeField>(name: &str) -> (R1CS<F>, R1CS<F>) {
+    let original = R1CS::<F>::read(fixture(name)).unwrap();
+    let path = scratch_dir(&format!("write-{}", name)).join(name);
+    original.write(&path).unwrap();
+    let reread = R1CS::<F>::read(&path).unwrap();
+
+    assert!(reread == original, "{} changed in the round trip", name);
+    assert_eq!(reread.to_json(), original.to_json(), "{}", name);
+    (original, reread)
+}
+
+#[test]
+fn bn254_round_trips_byte_for_byte() {
+    let (original, _) = assert_round_trips::<Fr>("mult.r1cs");
+    assert_eq!(to_bytes(&original), fs::read(fixture("mult.r1cs")).unwrap());
+}
+
+#[test]
+fn bls12_381_round_trips_byte_for_byte() {
+    let (original, _) = assert_round_trips::<ark_bls12_381::Fr>("mult_bls12_381.r1cs");
+    assert_eq!(to_bytes(&original), fs::read(fixture("mult_bls12_381.r1cs")).unwrap());
+}
+
+// The unknown header bytes are dropped, so the first write is smaller than
+// the fixture, but writing again changes nothing
+#[test]
+fn version_2_round_trips() {
+    let (original, reread) = assert_round_trips::<Fr>("mult_v2.r1cs");
+    assert_eq!(reread.header().version, 2);
+    assert_eq!(to_bytes(&reread), to_bytes(&original));
+}
+
+#[test]
+fn written_bytes_parse_from_a_reader() {
+    let original = R1CS::<Fr>::read(fixture("mult.r1cs")).unwrap();
+    let parsed = R1CS::<Fr>::from_reader(Cursor::new(to_bytes(&original))).unwrap();
+    assert!(parsed == original);
+}
+
+// Equality ignores the order of terms and how a wire's coefficient is
+// split between them, but not the coefficients themselves
+#[test]
+fn equality_ignores_term_order() {
+    let term = |wire_id, coefficient: u64| Term { wire_id, coefficient: Fr::from(coefficient) };
+    let original = R1CS::<Fr>::read(fixture("mult.r1cs")).unwrap();
+
+    let mut ours = original.clone();
+    ours.constraints_mut()[0].a_terms = vec![term(0, 5), term(2, 1)];
+    let mut theirs = original.clone();
+    theirs.constraints_mut()[0].a_terms = vec![term(2, 3), term(0, 5), Term { wire_id: 2, coefficient: -Fr::from(2u64) }];
+    assert!(ours == theirs);
+
+    theirs.constraints_mut()[0].a_terms = vec![term(2, 1), term(0, 4)];
+    assert!(ours != theirs);
+    assert!(ours != original);
+}
@@ -0,0 +1,40 @@
+use ark_bls12_381::{Bls12_381, Fr};
+use ark_serialize::CanonicalSerialize;
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use dogecoin_zkp_generator_qa1::{prove, rerandomize_proof, setup, verify, CircuitFromR1CS, Witness, R1CS};
+
+mod common;
+use common::fixture;
+
+// `out = x * y` over bls12-381, with a witness for 3 * 11 = 33
+
+fn to_bytes<T: CanonicalSerialize>(value: &T) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    value.serialize_compressed(&mut bytes).unwrap();
+    bytes
+}
+
+#[test]
+fn rerandomized_proof_differs_but_verifies() {
+    let mut rng = StdRng::seed_from_u64(328);
+    let r1cs = R1CS::<Fr>::read(fixture("mult_bls12_381.r1cs")).unwrap();
+    let witness = Witness::<Fr>::read(fixture("mult_bls12_381.wtns")).unwrap();
+    let public_inputs = witness.values[1..=r1cs.num_public_values() as usize].to_vec();
+
+    let pk = setup::<Bls12_381, _>(CircuitFromR1CS::new_with_dummy(&r1cs).unwrap(), &mut rng).unwrap();
+    let proof = prove(&pk, CircuitFromR1CS::new(&r1cs, witness).unwrap(), &mut rng).unwrap();
+    assert!(verify(&pk.vk, &public_inputs, &proof).unwrap());
+
+    let first = rerandomize_proof(&proof, &pk.vk, &mut rng);
+    let second = rerandomize_proof(&first, &pk.vk, &mut rng);
+    for rerandomized in [&first, &second] {
+        assert_ne!(to_bytes(rerandomized), to_bytes(&proof));
+        assert!(verify(&pk.vk, &public_inputs, rerandomized).unwrap());
+    }
+    assert_ne!(to_bytes(&first), to_bytes(&second));
+
+    // Rerandomizing doesn't make the proof valid for another statement
+    let mut wrong_inputs = public_inputs.clone();
+    wrong_inputs[0] += Fr::from(1u64);
+    assert!(!verify(&pk.vk, &wrong_inputs, &first).unwrap());
+}
@@ -0,0 +1,127 @@
+use std::fs;
+use std::path::Path;
+use std::process::Output;
+
+mod common;
+use common::{fixture, path_str, qa1, qa1_ok, scratch_dir};
+
+// The fixture circuit is `out = x * y` over bn254, with wires
+// [1, out, x, y] and the witness x = 5, y = 7, out = 35.
+
+// setup -> save keys -> prove with the reloaded proving key -> save proof
+// -> verify with the reloaded verifying key
+fn setup_and_prove(dir: &Path) {
+    let r1cs = fixture("mult.r1cs");
+    let wtns = fixture("mult.wtns");
+
+    qa1_ok(&[
+        "setup",
+        "--r1cs", path_str(&r1cs),
+        "--pk", path_str(&dir.join("pk.bin")),
+        "--vk", path_str(&dir.join("vk.bin")),
+        "--setup-seed", "42",
+    ]);
+    qa1_ok(&[
+        "prove",
+        "--r1cs", path_str(&r1cs),
+        "--pk", path_str(&dir.join("pk.bin")),
+        "--wtns", path_str(&wtns),
+        "--proof", path_str(&dir.join("proof.bin")),
+        "--public", path_str(&dir.join("public.json")),
+    ]);
+}
+
+fn verify(dir: &Path, public: &Path, extra: &[&str]) -> Output {
+    let (vk, proof) = (dir.join("vk.bin"), dir.join("proof.bin"));
+    let mut args = vec![
+        "verify",
+        "--vk", path_str(&vk),
+        "--public", path_str(public),
+        "--proof", path_str(&proof),
+    ];
+    args.extend_from_slice(extra);
+    qa1(&args)
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn reloaded_keys_verify_reloaded_proof() {
+    let dir = scratch_dir("roundtrip");
+    setup_and_prove(&dir);
+
+    let public: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(dir.join("public.json")).unwrap()).unwrap();
+    assert_eq!(public, serde_json::json!(["35"]));
+
+    let output = verify(&dir, &dir.join("public.json"), &[]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).contains("Proof verified successfully"), "{}", stdout(&output));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn reloaded_proof_rejects_wrong_public_input() {
+    let dir = scratch_dir("roundtrip-wrong-input");
+    setup_and_prove(&dir);
+
+    let wrong = dir.join("wrong.json");
+    fs::write(&wrong, r#"["36"]"#).unwrap();
+    let output = verify(&dir, &wrong, &[]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains("Proof verification failed"), "{}", stdout(&output));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// Exit status 0 means verified, 1 rejected and 2 not checked at all, and
+// --json reports the same on stdout
+#[test]
+fn json_result_and_exit_codes() {
+    let dir = scratch_dir("roundtrip-json");
+    setup_and_prove(&dir);
+    let report = |output: &Output| -> serde_json::Value { serde_json::from_str(&stdout(output)).unwrap() };
+
+    let output = verify(&dir, &dir.join("public.json"), &["--json"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(report(&output), serde_json::json!({"verified": true, "public_inputs": ["35"], "error": null}));
+
+    let wrong = dir.join("wrong.json");
+    fs::write(&wrong, r#"["36"]"#).unwrap();
+    let output = verify(&dir, &wrong, &["--json"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(report(&output), serde_json::json!({"verified": false, "public_inputs": ["36"], "error": null}));
+
+    fs::remove_file(dir.join("proof.bin")).unwrap();
+    let output = verify(&dir, &dir.join("public.json"), &["--json"]);
+    assert_eq!(output.status.code(), Some(2));
+    let report = report(&output);
+    assert_eq!(report["verified"], false);
+    assert!(report["error"].is_string(), "{}", report);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// Setup and proving on a one-thread pool still produce a valid proof
+#[test]
+fn single_thread_proof_verifies() {
+    let dir = scratch_dir("roundtrip-threads");
+    let output = qa1_ok(&[
+        "--r1cs", path_str(&fixture("mult.r1cs")),
+        "--wtns", path_str(&fixture("mult.wtns")),
+        "--setup-seed", "42",
+        "--threads", "1",
+        "--out-dir", path_str(&dir),
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Proof verified successfully"), "{}", stdout);
+
+    let output = qa1_ok(&["--verify-only", "--out-dir", path_str(&dir), "--curve", "bn254", "--threads", "1"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Proof verified successfully"), "{}", stdout);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
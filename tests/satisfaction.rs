@@ -0,0 +1,74 @@
+use ark_bn254::Fr;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
+use dogecoin_zkp_generator_qa1::r1cs::{R1CSConstraint, Term};
+use dogecoin_zkp_generator_qa1::{CircuitFromR1CS, Witness, R1CS};
+
+mod common;
+use common::fixture;
+
+// `mult.r1cs` is `out = x * y` over bn254 with wires [1, out, x, y], and
+// `mult.wtns` the witness [1, 35, 5, 7]
+
+fn mult() -> R1CS<Fr> {
+    R1CS::<Fr>::read(fixture("mult.r1cs")).unwrap()
+}
+
+fn values(values: [u64; 4]) -> Vec<Fr> {
+    values.map(Fr::from).to_vec()
+}
+
+fn term(wire_id: u32) -> Term<Fr> {
+    Term { wire_id, coefficient: Fr::from(1u64) }
+}
+
+// What arkworks makes of the same witness, through `generate_constraints`
+fn arkworks_satisfied(r1cs: &R1CS<Fr>, values: Vec<Fr>) -> bool {
+    let mut witness = Witness::<Fr>::read(fixture("mult.wtns")).unwrap();
+    witness.values = values;
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    CircuitFromR1CS::new(r1cs, witness).unwrap().generate_constraints(cs.clone()).unwrap();
+    cs.is_satisfied().unwrap()
+}
+
+#[test]
+fn satisfying_witness_passes() {
+    let r1cs = mult();
+    let witness = Witness::<Fr>::read(fixture("mult.wtns")).unwrap();
+    assert_eq!(r1cs.check_witness(&witness.values), Ok(()));
+}
+
+#[test]
+fn broken_witness_fails_at_its_constraint() {
+    let mut r1cs = mult();
+    assert_eq!(r1cs.check_witness(&values([1, 35, 5, 8])), Err(0));
+
+    // With a second constraint y * 1 = y, only the first one breaks
+    r1cs.constraints_mut().push(R1CSConstraint { a_terms: vec![term(3)], b_terms: vec![term(0)], c_terms: vec![term(3)] });
+    r1cs.header_mut().n_constraints = 2;
+    assert_eq!(r1cs.check_witness(&values([1, 35, 5, 7])), Ok(()));
+    assert_eq!(r1cs.check_witness(&values([1, 36, 5, 7])), Err(0));
+
+    // ... and a broken second constraint is reported as such
+    r1cs.constraints_mut()[1].c_terms = vec![term(2)];
+    assert_eq!(r1cs.check_witness(&values([1, 35, 5, 7])), Err(1));
+}
+
+// A witness too short for a constraint's wires can't satisfy it
+#[test]
+fn short_witness_fails() {
+    assert_eq!(mult().check_witness(&values([1, 35, 5, 7])[..3]), Err(0));
+}
+
+// An empty B is the zero linear combination, as in circom (see synth-289),
+// not the ONE wire: x * () = out holds only for out = 0. check_witness and
+// generate_constraints must agree on this.
+#[test]
+fn empty_b_matches_generate_constraints() {
+    let mut r1cs = mult();
+    r1cs.constraints_mut()[0].b_terms.clear();
+
+    for (witness, satisfied) in [([1, 0, 5, 7], true), ([1, 5, 5, 7], false), ([1, 35, 5, 7], false)] {
+        assert_eq!(r1cs.check_witness(&values(witness)).is_ok(), satisfied, "{:?}", witness);
+        assert_eq!(arkworks_satisfied(&r1cs, values(witness)), satisfied, "{:?}", witness);
+    }
+}
@@ -0,0 +1,56 @@
+use ark_bn254::Fr;
+use ark_relations::r1cs::SynthesisError;
+use dogecoin_zkp_generator_qa1::{diagnose_synthesis_error, SynthesisDiagnosis, Witness, R1CS};
+
+mod common;
+use common::fixture;
+
+// `mult.r1cs` is `out = x * y` over bn254 with wires [1, out, x, y], and
+// `mult.wtns` the witness [1, 35, 5, 7]
+
+fn mult() -> (R1CS<Fr>, Vec<Fr>) {
+    let r1cs = R1CS::<Fr>::read(fixture("mult.r1cs")).unwrap();
+    let witness = Witness::<Fr>::read(fixture("mult.wtns")).unwrap();
+    (r1cs, witness.values.clone())
+}
+
+#[test]
+fn bad_witness_points_at_the_constraint() {
+    let (r1cs, mut witness) = mult();
+    witness[1] = Fr::from(36u64);
+    let diagnosis = diagnose_synthesis_error(&SynthesisError::Unsatisfiable, &r1cs, Some(&witness));
+    assert_eq!(diagnosis, SynthesisDiagnosis::UnsatisfiedConstraint(0));
+    assert!(diagnosis.to_string().contains("constraint #0"));
+
+    // Without a real witness there is nothing to cross-reference
+    assert_eq!(diagnose_synthesis_error(&SynthesisError::Unsatisfiable, &r1cs, None), SynthesisDiagnosis::Unknown);
+}
+
+#[test]
+fn short_witness_names_the_missing_wire() {
+    let (r1cs, witness) = mult();
+    let diagnosis = diagnose_synthesis_error(&SynthesisError::AssignmentMissing, &r1cs, Some(&witness[..3]));
+    assert_eq!(diagnosis, SynthesisDiagnosis::MissingAssignment { constraint: 0, wire: 3, witness_len: 3 });
+
+    // A witness that satisfies everything leaves the error unexplained
+    assert_eq!(diagnose_synthesis_error(&SynthesisError::AssignmentMissing, &r1cs, Some(&witness)), SynthesisDiagnosis::Unknown);
+}
+
+#[test]
+fn degree_errors_report_the_domain_size() {
+    let (r1cs, _) = mult();
+    // One constraint plus ONE and the public output need a domain of 4
+    let diagnosis = diagnose_synthesis_error(&SynthesisError::PolynomialDegreeTooLarge, &r1cs, None);
+    assert_eq!(diagnosis, SynthesisDiagnosis::DomainTooLarge { log_size: 2, max_log_size: 28 });
+}
+
+#[test]
+fn unconstrained_variables_are_listed() {
+    let (mut r1cs, _) = mult();
+    assert_eq!(diagnose_synthesis_error(&SynthesisError::UnconstrainedVariable, &r1cs, None), SynthesisDiagnosis::Unknown);
+
+    r1cs.header_mut().n_wires = 5;
+    let diagnosis = diagnose_synthesis_error(&SynthesisError::UnconstrainedVariable, &r1cs, None);
+    assert_eq!(diagnosis, SynthesisDiagnosis::UnconstrainedWires(vec![4]));
+    assert!(diagnosis.to_string().contains("1 wire(s) appear in no constraint (first: 4)"));
+}
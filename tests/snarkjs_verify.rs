@@ -0,0 +1,156 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use ark_bls12_381::Bls12_381;
+use dogecoin_zkp_generator_qa1::snarkjs_import::{self, ImportError};
+use dogecoin_zkp_generator_qa1::artifacts;
+
+mod common;
+use common::{fixture, path_str, qa1_ok, scratch_dir};
+
+// The fixture circuit is `out = x * y` over bls12-381, with wires
+// [1, out, x, y] and the witness x = 5, y = 7, out = 35. The
+// `snarkjs_bls12_381` directory holds JSON artifacts for it in snarkjs'
+// layout, written by this crate rather than snarkjs (see its README.md), so
+// they pin the importer against our own exporter only.
+
+// Prove the fixture, saving arkworks binaries and snarkjs JSON to `dir`
+fn prove(dir: &Path) {
+    qa1_ok(&[
+        "--r1cs", path_str(&fixture("mult_bls12_381.r1cs")),
+        "--wtns", path_str(&fixture("mult_bls12_381.wtns")),
+        "--setup-seed", "42",
+        "--out-dir", path_str(dir),
+    ]);
+}
+
+fn verify_snarkjs(dir: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_dogecoin_zkp_generator_qa1"))
+        .args(["verify-snarkjs"])
+        .args(["--vk", path_str(&dir.join("verification_key.json"))])
+        .args(["--proof", path_str(&dir.join("proof.json"))])
+        .args(["--public", path_str(&dir.join("public.json"))])
+        .output()
+        .unwrap()
+}
+
+fn read_json(path: &Path) -> serde_json::Value {
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+// The JSON importers rebuild exactly the key and proof saved as binaries
+#[test]
+fn imported_json_matches_binary_artifacts() {
+    let dir = scratch_dir("snarkjs-import");
+    prove(&dir);
+
+    let vk = snarkjs_import::vk_from_json(&read_json(&dir.join("verification_key.json"))).unwrap();
+    let proof = snarkjs_import::proof_from_json(&read_json(&dir.join("proof.json"))).unwrap();
+    assert_eq!(vk, artifacts::load_verifying_key::<Bls12_381, _>(dir.join("verifying_key.bin")).unwrap());
+    assert_eq!(proof, artifacts::load_proof::<Bls12_381, _>(dir.join("proof.bin")).unwrap());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn json_artifacts_verify_without_setup() {
+    let dir = scratch_dir("snarkjs-verify");
+    prove(&dir);
+
+    let output = verify_snarkjs(&dir);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert!(stdout.contains("Proof verified successfully"), "{}", stdout);
+
+    // A different public output must not verify
+    fs::write(dir.join("public.json"), r#"["36"]"#).unwrap();
+    let output = verify_snarkjs(&dir);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout.contains("Proof verification failed"), "{}", stdout);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// The bls12-381 scalar field modulus
+const R: &str = "52435875175126190479447740508185965837690552500527637822603658699938581184513";
+
+fn committed() -> PathBuf {
+    fixture("snarkjs_bls12_381")
+}
+
+fn verify_committed(dir: &Path) -> Result<bool, ImportError> {
+    snarkjs_import::verify_from_snarkjs_artifacts(
+        dir.join("verification_key.json"),
+        dir.join("public.json"),
+        dir.join("proof.json"),
+    )
+}
+
+// A copy of the committed artifacts that a test can tamper with
+fn committed_copy(test: &str) -> PathBuf {
+    let dir = scratch_dir(test);
+    for name in ["verification_key.json", "proof.json", "public.json"] {
+        fs::copy(committed().join(name), dir.join(name)).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn committed_artifacts_verify() {
+    assert!(verify_committed(&committed()).unwrap());
+
+    let output = verify_snarkjs(&committed());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.contains("Proof verified successfully"), "{}", stdout);
+}
+
+#[test]
+fn tampered_committed_proof_does_not_verify() {
+    let dir = committed_copy("snarkjs-tampered");
+    // Both are valid curve points, so the proof still imports
+    let mut proof = read_json(&dir.join("proof.json"));
+    proof["pi_c"] = proof["pi_a"].clone();
+    fs::write(dir.join("proof.json"), proof.to_string()).unwrap();
+
+    assert!(!verify_committed(&dir).unwrap());
+    let output = verify_snarkjs(&dir);
+    assert_eq!(output.status.code(), Some(1), "{}", String::from_utf8_lossy(&output.stdout));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// `35 + r` reduces to the proven output 35, but must not verify as it
+#[test]
+fn non_canonical_public_input_is_rejected() {
+    let r: num_bigint::BigUint = R.parse().unwrap();
+    for value in [r.clone(), r + 35u32] {
+        let json = serde_json::json!([value.to_string()]);
+        assert!(matches!(
+            snarkjs_import::public_inputs_from_json::<ark_bls12_381::Fr>(&json),
+            Err(ImportError::NonCanonical(name)) if name == "public[0]"
+        ));
+    }
+
+    let dir = committed_copy("snarkjs-non-canonical");
+    fs::write(dir.join("public.json"), format!(r#"["{}"]"#, "52435875175126190479447740508185965837690552500527637822603658699938581184548")).unwrap();
+    assert!(matches!(verify_committed(&dir), Err(ImportError::NonCanonical(_))));
+    let output = verify_snarkjs(&dir);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(2), "{}", stdout);
+    assert!(stdout.contains("not less than the field modulus"), "{}", stdout);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// The same holds for curve coordinates, which are reduced by the base field
+#[test]
+fn non_canonical_coordinate_is_rejected() {
+    let mut vk = read_json(&committed().join("verification_key.json"));
+    vk["vk_alpha_1"][0] = "7403827899306714127349483099721636919273780140465800741357917885368164662943497920941459379273710923200714377408800".into();
+    assert!(matches!(
+        snarkjs_import::vk_from_json(&vk),
+        Err(ImportError::NonCanonical(name)) if name == "vk_alpha_1"
+    ));
+}
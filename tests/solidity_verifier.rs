@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G2Affine};
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{One, PrimeField};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use dogecoin_zkp_generator_qa1::{artifacts, prove, snarkjs_export, CircuitFromR1CS, Witness, R1CS};
+use num_bigint::BigUint;
+
+mod common;
+use common::{fixture, path_str, qa1, scratch_dir};
+
+// `mult_verifier.sol` is the contract this crate exports for the bn254
+// `out = x * y` fixture circuit after `setup --setup-seed 42`. It is our own
+// output, so comparing against it only catches changes; the other tests
+// check the contract independently, against snarkjs' template mapping and
+// by running its pairing check on a real proof.
+
+// Run setup with a fixed seed, returning the verifying key's path
+fn setup(dir: &Path) -> PathBuf {
+    let vk = dir.join("vk.bin");
+    let output = qa1(&[
+        "setup",
+        "--r1cs", path_str(&fixture("mult.r1cs")),
+        "--pk", path_str(&dir.join("pk.bin")),
+        "--vk", path_str(&vk),
+        "--setup-seed", "42",
+    ]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    vk
+}
+
+// Export the contract for the key at `vk`
+fn export(dir: &Path, vk: &Path) -> String {
+    let contract = dir.join("Verifier.sol");
+    let output = qa1(&["export-solidity", "--vk", path_str(vk), "--out", path_str(&contract), "--curve", "bn254"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+    fs::read_to_string(&contract).unwrap()
+}
+
+// The contract's `uint256 constant NAME = VALUE;` lines
+fn constants(contract: &str) -> HashMap<String, BigUint> {
+    contract.lines().filter_map(|line| {
+        let (name, value) = line.trim().strip_prefix("uint256 constant ")?.split_once('=')?;
+        Some((name.trim().to_string(), value.trim().trim_end_matches(';').parse().ok()?))
+    }).collect()
+}
+
+#[test]
+fn contract_matches_golden_file() {
+    let dir = scratch_dir("solidity-golden");
+    let vk = setup(&dir);
+    assert_eq!(export(&dir, &vk), fs::read_to_string(fixture("mult_verifier.sol")).unwrap());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// snarkjs' verifier_groth16.sol.ejs fills the constants from
+// verification_key.json as `betax1 = vk_beta_2[0][1]`, `betax2 =
+// vk_beta_2[0][0]` and so on: each G2 coordinate imaginary part first
+#[test]
+fn constants_follow_the_snarkjs_template() {
+    let dir = scratch_dir("solidity-constants");
+    let vk_path = setup(&dir);
+    let constants = constants(&export(&dir, &vk_path));
+    let vk = artifacts::load_verifying_key::<Bn254, _>(&vk_path).unwrap();
+    let json = snarkjs_export::vk_to_json(&vk);
+    let decimal = |value: &serde_json::Value| value.as_str().unwrap().parse::<BigUint>().unwrap();
+
+    assert_eq!(constants["r"], BigUint::from(Fr::MODULUS));
+    assert_eq!(constants["q"], BigUint::from(Fq::MODULUS));
+    assert_eq!(constants["alphax"], decimal(&json["vk_alpha_1"][0]));
+    assert_eq!(constants["alphay"], decimal(&json["vk_alpha_1"][1]));
+    for name in ["beta", "gamma", "delta"] {
+        let point = &json[format!("vk_{}_2", name)];
+        assert_eq!(constants[&format!("{}x1", name)], decimal(&point[0][1]), "{}", name);
+        assert_eq!(constants[&format!("{}x2", name)], decimal(&point[0][0]), "{}", name);
+        assert_eq!(constants[&format!("{}y1", name)], decimal(&point[1][1]), "{}", name);
+        assert_eq!(constants[&format!("{}y2", name)], decimal(&point[1][0]), "{}", name);
+    }
+    for (i, ic) in json["IC"].as_array().unwrap().iter().enumerate() {
+        assert_eq!(constants[&format!("IC{}x", i)], decimal(&ic[0]));
+        assert_eq!(constants[&format!("IC{}y", i)], decimal(&ic[1]));
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// The 24 words `checkPairing` stores for the pairing precompile (address
+// 8), given the proof as calldata and vk_x, by reading its `mstore` lines
+fn pairing_input(contract: &str, calldata: &HashMap<&str, Vec<BigUint>>, vk_x: &[BigUint; 2]) -> Vec<BigUint> {
+    let constants = constants(contract);
+    let q = &constants["q"];
+    let start = contract.find("// -A").unwrap();
+    let end = contract.find("let success := staticcall(sub(gas(), 2000), 8, _pPairing, 768, _pPairing, 0x20)").unwrap();
+
+    let load = |arg: &str| -> BigUint {
+        let (name, offset) = match arg.strip_prefix("add(") {
+            Some(rest) => {
+                let (name, offset) = rest.trim_end_matches(')').split_once(", ").unwrap();
+                (name, offset.parse::<usize>().unwrap())
+            }
+            None => (arg, 0),
+        };
+        calldata[name][offset / 32].clone()
+    };
+    let value = |expr: &str| -> BigUint {
+        if let Some(arg) = expr.strip_prefix("mod(sub(q, calldataload(") {
+            (q - load(arg.strip_suffix(")), q)").unwrap())) % q
+        } else if let Some(arg) = expr.strip_prefix("calldataload(") {
+            load(&arg[..arg.len() - 1])
+        } else if expr == "mload(add(pMem, pVk))" {
+            vk_x[0].clone()
+        } else if expr == "mload(add(pMem, add(pVk, 32)))" {
+            vk_x[1].clone()
+        } else {
+            constants[expr].clone()
+        }
+    };
+
+    let mut words = vec![None; 24];
+    for line in contract[start..end].lines().map(str::trim).filter(|line| line.starts_with("mstore(")) {
+        let args = &line["mstore(".len()..line.len() - 1];
+        let (offset, expr) = match args.strip_prefix("add(_pPairing, ") {
+            Some(rest) => {
+                let (offset, expr) = rest.split_once("), ").unwrap();
+                (offset.parse::<usize>().unwrap(), expr)
+            }
+            None => (0, args.strip_prefix("_pPairing, ").unwrap()),
+        };
+        assert_eq!(offset % 32, 0, "{}", line);
+        assert!(words[offset / 32].replace(value(expr)).is_none(), "word stored twice: {}", line);
+    }
+    words.into_iter().map(|word| word.expect("every word of the pairing input is stored")).collect()
+}
+
+// Decode the precompile input as EIP-197 does, four (G1, G2) pairs with G2
+// coordinates imaginary part first, and check that the pairing product is 1
+fn pairing_check(words: &[BigUint]) -> bool {
+    let fq = |word: &BigUint| Fq::from(word.clone());
+    let (g1, g2): (Vec<G1Affine>, Vec<G2Affine>) = words.chunks(6).map(|pair| {
+        let a = G1Affine::new_unchecked(fq(&pair[0]), fq(&pair[1]));
+        let x = Fq2::new(fq(&pair[3]), fq(&pair[2]));
+        let y = Fq2::new(fq(&pair[5]), fq(&pair[4]));
+        let b = G2Affine::new_unchecked(x, y);
+        assert!(a.is_on_curve() && b.is_on_curve() && b.is_in_correct_subgroup_assuming_on_curve());
+        (a, b)
+    }).unzip();
+    Bn254::multi_pairing(g1, g2).0.is_one()
+}
+
+// Run the contract's pairing check on a real proof, and on the same proof
+// with the wrong public output
+#[test]
+fn pairing_check_accepts_a_valid_proof() {
+    let dir = scratch_dir("solidity-pairing");
+    let vk = setup(&dir);
+    let contract = export(&dir, &vk);
+    let constants = constants(&contract);
+
+    let r1cs = R1CS::<Fr>::read(fixture("mult.r1cs")).unwrap();
+    let circuit = CircuitFromR1CS::new(&r1cs, Witness::read(fixture("mult.wtns")).unwrap()).unwrap();
+    let pk = artifacts::load_proving_key::<Bn254, _>(dir.join("pk.bin")).unwrap();
+    let proof = prove(&pk, circuit, &mut StdRng::seed_from_u64(299)).unwrap();
+
+    let big = |value: &Fq| BigUint::from(value.into_bigint());
+    let (a, b, c) = (proof.a.xy().unwrap(), proof.b.xy().unwrap(), proof.c.xy().unwrap());
+    let calldata = HashMap::from([
+        ("pA", vec![big(a.0), big(a.1)]),
+        ("pB", vec![big(&b.0.c1), big(&b.0.c0), big(&b.1.c1), big(&b.1.c0)]),
+        ("pC", vec![big(c.0), big(c.1)]),
+    ]);
+
+    // vk_x = IC0 + pubSignals[0]·IC1, as the contract's g1_mulAccC calls it
+    assert!(contract.contains("g1_mulAccC(_pVk, IC1x, IC1y, calldataload(add(pubSignals, 0)))"));
+    let ic = |i: usize| G1Affine::new(
+        Fq::from(constants[&format!("IC{}x", i)].clone()),
+        Fq::from(constants[&format!("IC{}y", i)].clone()),
+    );
+    let vk_x = |output: u64| {
+        let point = (ic(0) + ic(1) * Fr::from(output)).into_affine();
+        [big(&point.x), big(&point.y)]
+    };
+
+    assert!(pairing_check(&pairing_input(&contract, &calldata, &vk_x(35))));
+    assert!(!pairing_check(&pairing_input(&contract, &calldata, &vk_x(36))));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn bls12_381_is_rejected() {
+    let dir = scratch_dir("solidity-bls");
+    let vk = setup(&dir);
+
+    let output = qa1(&["export-solidity", "--vk", path_str(&vk), "--out", path_str(&dir.join("Verifier.sol")), "--curve", "bls12-381"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success(), "{}", stdout);
+    assert!(stdout.contains("need --curve bn254"), "{}", stdout);
+    assert!(!dir.join("Verifier.sol").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
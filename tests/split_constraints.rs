@@ -0,0 +1,93 @@
+use std::io::Cursor;
+use ark_bn254::Fr;
+use dogecoin_zkp_generator_qa1::r1cs::ReadOptions;
+use dogecoin_zkp_generator_qa1::{R1CSError, R1CS};
+
+mod common;
+use common::{fixture, scratch_dir};
+
+// `mult_split.r1cs` computes out = (x * y) * x over bn254 with the wires
+// [1, out, x, y, m] and two constraints, x · y = m and m · x = out. Each
+// constraint has its own constraints section, with the wire map between them.
+
+// The header's constraint count sits after the preamble (12 bytes), the
+// header section's type and size (12), and the field size, prime and
+// counts (4 + 32 + 16 + 8)
+const N_CONSTRAINTS: usize = 84;
+
+fn witness(out: u64) -> [Fr; 5] {
+    [Fr::from(1u64), Fr::from(out), Fr::from(2u64), Fr::from(3u64), Fr::from(6u64)]
+}
+
+fn assert_both_constraints(r1cs: &R1CS<Fr>) {
+    assert_eq!(r1cs.num_constraints(), 2);
+    assert_eq!(r1cs.constraints().len(), 2);
+    assert_eq!(r1cs.constraints()[0].to_string(), "(1·x2) · (1·x3) = 1·x4");
+    assert_eq!(r1cs.constraints()[1].to_string(), "(1·x4) · (1·x2) = 1·x1");
+    assert_eq!(r1cs.wire_to_label(), [0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn constraints_from_every_section_are_kept() {
+    for parallel in [false, true] {
+        let options = ReadOptions { parallel, ..ReadOptions::default() };
+        let r1cs = R1CS::<Fr>::read_with_options(fixture("mult_split.r1cs"), &options).unwrap();
+        assert_both_constraints(&r1cs);
+        assert_eq!(r1cs.check_witness(&witness(12)), Ok(()));
+        assert_eq!(r1cs.check_witness(&witness(13)), Err(1));
+    }
+}
+
+#[test]
+fn streaming_reads_every_section() {
+    let streamed: Vec<String> = R1CS::<Fr>::constraints_iter(fixture("mult_split.r1cs"))
+        .unwrap()
+        .map(|constraint| constraint.unwrap().to_string())
+        .collect();
+    assert_eq!(streamed, ["(1·x2) · (1·x3) = 1·x4", "(1·x4) · (1·x2) = 1·x1"]);
+}
+
+#[test]
+fn combined_total_must_match_the_header() {
+    let mut bytes = std::fs::read(fixture("mult_split.r1cs")).unwrap();
+    bytes[N_CONSTRAINTS..N_CONSTRAINTS + 4].copy_from_slice(&3u32.to_le_bytes());
+    match R1CS::<Fr>::from_reader(Cursor::new(bytes)) {
+        Err(R1CSError::ConstraintCountMismatch { declared, parsed }) => assert_eq!((declared, parsed), (3, 2)),
+        other => panic!("expected ConstraintCountMismatch, got {:?}", other.map(|_| ())),
+    }
+}
+
+// A header declaring one constraint fewer than the sections hold, whether
+// the extra one is in a later section or after the last one in the same
+// section, is rejected by streaming as by `read`
+#[test]
+fn streaming_rejects_a_trailing_extra_constraint() {
+    let split = std::fs::read(fixture("mult_split.r1cs")).unwrap();
+    let mut merged = Vec::new();
+    R1CS::<Fr>::from_reader(Cursor::new(&split)).unwrap().write_to(&mut merged).unwrap();
+
+    for mut bytes in [split, merged] {
+        bytes[N_CONSTRAINTS..N_CONSTRAINTS + 4].copy_from_slice(&1u32.to_le_bytes());
+        assert!(R1CS::<Fr>::from_reader(Cursor::new(&bytes)).is_err());
+
+        let path = scratch_dir("split-trailing").join("trailing.r1cs");
+        std::fs::write(&path, &bytes).unwrap();
+        let mut constraints = R1CS::<Fr>::constraints_iter(&path).unwrap();
+        assert_eq!(constraints.next().unwrap().unwrap().to_string(), "(1·x2) · (1·x3) = 1·x4");
+        assert!(matches!(
+            constraints.next(),
+            Some(Err(R1CSError::SectionSizeMismatch { section_type: 2, .. }))
+        ));
+        assert!(constraints.next().is_none());
+    }
+}
+
+// Writing merges the constraints into a single section
+#[test]
+fn split_file_writes_as_one_section() {
+    let r1cs = R1CS::<Fr>::read(fixture("mult_split.r1cs")).unwrap();
+    let mut bytes = Vec::new();
+    r1cs.write_to(&mut bytes).unwrap();
+    assert_eq!(u32::from_le_bytes(bytes[8..12].try_into().unwrap()), 3);
+    assert_both_constraints(&R1CS::<Fr>::from_reader(Cursor::new(bytes)).unwrap());
+}
@@ -0,0 +1,51 @@
+use std::io::Cursor;
+use ark_bn254::Fr;
+use dogecoin_zkp_generator_qa1::r1cs::ReadOptions;
+use dogecoin_zkp_generator_qa1::{R1CSError, R1CS};
+
+mod common;
+use common::{field_element, linear_combination, prime_le, r1cs_bytes};
+
+// The circuit has one constraint over the wires [1, out, x]:
+// A = (r + 2)·x, B = ONE, C = out. The A coefficient is written unreduced,
+// so it only means 2 after reducing it modulo r.
+const ONE: u32 = 0;
+const OUT: u32 = 1;
+const X: u32 = 2;
+
+// r + 2; r ends in 0x01, so adding 2 doesn't carry
+fn over_modulus() -> Vec<u8> {
+    let mut bytes = prime_le();
+    bytes[0] += 2;
+    bytes
+}
+
+fn constraint() -> Vec<u8> {
+    let mut bytes = linear_combination(&[(X, over_modulus())]);
+    bytes.extend(linear_combination(&[(ONE, field_element(1))]));
+    bytes.extend(linear_combination(&[(OUT, field_element(1))]));
+    bytes
+}
+
+fn parse(options: &ReadOptions) -> Result<R1CS<Fr>, R1CSError> {
+    R1CS::from_reader_with_options(Cursor::new(r1cs_bytes(&constraint())), options)
+}
+
+#[test]
+fn lenient_reading_reduces_the_coefficient() {
+    let r1cs = parse(&ReadOptions::default()).unwrap();
+    assert_eq!(r1cs.constraints()[0].a_terms[0].coefficient, Fr::from(2u64));
+}
+
+#[test]
+fn strict_reading_rejects_the_coefficient() {
+    for parallel in [false, true] {
+        let options = ReadOptions { strict: true, parallel, ..ReadOptions::default() };
+        match parse(&options) {
+            Err(R1CSError::UnreducedCoefficient { constraint, wire }) => {
+                assert_eq!((constraint, wire), (0, X));
+            }
+            other => panic!("expected UnreducedCoefficient, got {:?}", other.map(|_| ())),
+        }
+    }
+}
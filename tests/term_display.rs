@@ -0,0 +1,25 @@
+use ark_bn254::Fr;
+use dogecoin_zkp_generator_qa1::r1cs::{R1CSConstraint, Term};
+
+fn term(wire_id: u32, coefficient: Fr) -> Term<Fr> {
+    Term { wire_id, coefficient }
+}
+
+#[test]
+fn coefficients_print_in_decimal() {
+    assert_eq!(term(7, Fr::from(3u64)).to_string(), "3·x7");
+    assert_eq!(term(7, Fr::from(0u64)).to_string(), "0·x7");
+    assert_eq!(term(7, Fr::from(-2i64)).to_string(), "21888242871839275222246405745257275088548364400416034343698204186575808495615·x7");
+}
+
+#[test]
+fn minus_one_prints_as_a_sign() {
+    assert_eq!(term(7, -Fr::from(1u64)).to_string(), "-x7");
+
+    let constraint = R1CSConstraint {
+        a_terms: vec![term(0, Fr::from(5u64)), term(2, -Fr::from(1u64))],
+        b_terms: vec![term(0, Fr::from(1u64))],
+        c_terms: vec![],
+    };
+    assert_eq!(constraint.to_string(), "(5·x0 + -x2) · (1·x0) = 0");
+}
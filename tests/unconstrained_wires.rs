@@ -0,0 +1,45 @@
+use ark_bn254::Fr;
+use dogecoin_zkp_generator_qa1::R1CS;
+
+mod common;
+use common::fixture;
+
+// `mult.r1cs` has the wires [1, out, x, y] and the one constraint
+// x · y = out; `mult_split.r1cs` adds an intermediate wire m with
+// x · y = m and m · x = out.
+
+#[test]
+fn fully_constrained_circuits_have_none() {
+    assert!(R1CS::<Fr>::read(fixture("mult.r1cs")).unwrap().unconstrained_wires().is_empty());
+    assert!(R1CS::<Fr>::read(fixture("mult_split.r1cs")).unwrap().unconstrained_wires().is_empty());
+}
+
+// Dropping m · x = out leaves the output dangling
+#[test]
+fn dangling_output_is_reported() {
+    let mut r1cs = R1CS::<Fr>::read(fixture("mult_split.r1cs")).unwrap();
+    r1cs.constraints_mut().pop();
+    assert_eq!(r1cs.unconstrained_wires(), [1]);
+    assert_eq!(r1cs.stats().unconstrained_wires, [1]);
+}
+
+// Inputs are set by the prover, so an unused input isn't reported, and
+// terms whose coefficient is zero don't count as using a wire
+#[test]
+fn inputs_and_zero_terms_are_not_uses() {
+    let mut r1cs = R1CS::<Fr>::read(fixture("mult_split.r1cs")).unwrap();
+    zero_wire(&mut r1cs, 3);
+    assert!(r1cs.unconstrained_wires().is_empty());
+
+    zero_wire(&mut r1cs, 4);
+    assert_eq!(r1cs.unconstrained_wires(), [4]);
+}
+
+fn zero_wire(r1cs: &mut R1CS<Fr>, wire: u32) {
+    for constraint in r1cs.constraints_mut() {
+        let terms = constraint.a_terms.iter_mut().chain(&mut constraint.b_terms).chain(&mut constraint.c_terms);
+        for term in terms.filter(|term| term.wire_id == wire) {
+            term.coefficient = Fr::from(0u64);
+        }
+    }
+}
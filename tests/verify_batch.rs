@@ -0,0 +1,97 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use ark_bls12_381::{Bls12_381, Fr};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use dogecoin_zkp_generator_qa1::batch::{self, BatchError};
+use dogecoin_zkp_generator_qa1::{prove, setup, CircuitFromR1CS, Witness, R1CS};
+
+mod common;
+use common::{fixture, path_str, qa1_ok, scratch_dir};
+
+// The fixture circuit is `out = x * y` over bls12-381, with wires
+// [1, out, x, y] and the witness x = 5, y = 7, out = 35.
+
+// Prove the fixture twice with the same setup, exporting snarkjs JSON to
+// `dir/first` and `dir/second`
+fn prove_twice(dir: &Path) {
+    for name in ["first", "second"] {
+        qa1_ok(&[
+            "--r1cs", path_str(&fixture("mult_bls12_381.r1cs")),
+            "--wtns", path_str(&fixture("mult_bls12_381.wtns")),
+            "--setup-seed", "42",
+            "--out-dir", path_str(&dir.join(name)),
+        ]);
+    }
+}
+
+// Verify the batch, returning the exit status and stdout
+fn verify_batch(dir: &Path) -> (Option<i32>, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_dogecoin_zkp_generator_qa1"))
+        .args(["verify-batch"])
+        .args(["--vk", path_str(&dir.join("first/verification_key.json"))])
+        .args(["--proofs", path_str(&dir.join("proofs"))])
+        .args(["--public", path_str(&dir.join("public"))])
+        .output()
+        .unwrap();
+    (output.status.code(), String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+// Collect each run's proof and public inputs into the batch directories
+fn collect_batch(dir: &Path) {
+    fs::create_dir_all(dir.join("proofs")).unwrap();
+    fs::create_dir_all(dir.join("public")).unwrap();
+    for name in ["first", "second"] {
+        let file = format!("{}.json", name);
+        fs::copy(dir.join(name).join("proof.json"), dir.join("proofs").join(&file)).unwrap();
+        fs::copy(dir.join(name).join("public.json"), dir.join("public").join(&file)).unwrap();
+    }
+}
+
+#[test]
+fn batch_of_valid_proofs_verifies() {
+    let dir = scratch_dir("batch-valid");
+    prove_twice(&dir);
+    collect_batch(&dir);
+
+    let (code, stdout) = verify_batch(&dir);
+    assert_eq!(code, Some(0));
+    assert!(stdout.contains("2 of 2 proofs verified"), "{}", stdout);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn batch_reports_the_invalid_proof() {
+    let dir = scratch_dir("batch-invalid");
+    prove_twice(&dir);
+    collect_batch(&dir);
+    fs::write(dir.join("public/second.json"), r#"["36"]"#).unwrap();
+
+    let (code, stdout) = verify_batch(&dir);
+    assert_eq!(code, Some(1));
+    assert!(stdout.contains(&format!("✅ {}", dir.join("proofs/first.json").display())), "{}", stdout);
+    assert!(stdout.contains(&format!("❌ {}", dir.join("proofs/second.json").display())), "{}", stdout);
+    assert!(stdout.contains("1 of 2 proofs verified"), "{}", stdout);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn mismatched_counts_are_an_error() {
+    let mut rng = StdRng::seed_from_u64(282);
+    let r1cs = R1CS::<Fr>::read(fixture("mult_bls12_381.r1cs")).unwrap();
+    let witness = Witness::<Fr>::read(fixture("mult_bls12_381.wtns")).unwrap();
+    let public_inputs = witness.values[1..=r1cs.num_public_values() as usize].to_vec();
+
+    let pk = setup::<Bls12_381, _>(CircuitFromR1CS::new_with_dummy(&r1cs).unwrap(), &mut rng).unwrap();
+    let proof = prove(&pk, CircuitFromR1CS::new(&r1cs, witness).unwrap(), &mut rng).unwrap();
+    let pvk = ark_groth16::prepare_verifying_key(&pk.vk);
+
+    let inputs = vec![public_inputs; 2];
+    assert_eq!(batch::verify_batch(&pvk, &inputs[..1], std::slice::from_ref(&proof)).unwrap(), [true]);
+    match batch::verify_batch(&pvk, &inputs, &[proof]) {
+        Err(BatchError::CountMismatch { inputs, proofs }) => assert_eq!((inputs, proofs), (2, 1)),
+        other => panic!("expected CountMismatch, got {:?}", other),
+    }
+}
@@ -0,0 +1,123 @@
+use std::path::Path;
+use ark_bls12_381::{Bls12_381, Fr, G1Affine, G2Affine};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use serde_json::json;
+use dogecoin_zkp_generator_qa1::snarkjs_export::{point_to_json, vk_to_json};
+use dogecoin_zkp_generator_qa1::snarkjs_import::{g1_from_json, g2_from_json, vk_from_json, ImportError};
+use dogecoin_zkp_generator_qa1::{prove, setup, verify, CircuitFromR1CS, Witness, R1CS};
+
+mod common;
+use common::fixture;
+
+// snarkjs' setup fixes gamma = 1, so every bls12-381 `verification_key.json`
+// it writes carries the G2 generator as `vk_gamma_2`, exactly as below:
+// Fq2 coordinates as [c0, c1], then z = 1
+const SNARKJS_GAMMA_2: [[&str; 2]; 3] = [
+    [
+        "352701069587466618187139116011060144890029952792775240219908644239793785735715026873347600343865175952761926303160",
+        "3059144344244213709971259814753781636986470325476647558659373206291635324768958432433509563104347017837885763365758",
+    ],
+    [
+        "1985150602287291935568054521177171638300868978215655730859378665066344726373823718423869104263333984641494340347905",
+        "927553665492332455747201965776037880757740193453592970025027978793976877002675564980949289727957565575433344219582",
+    ],
+    ["1", "0"],
+];
+
+// `out = x * y` over bls12-381, with a witness for 5 * 7 = 35
+
+#[test]
+fn points_at_infinity_are_all_zero() {
+    assert_eq!(point_to_json(&G1Affine::zero()), json!(["0", "0", "0"]));
+    assert_eq!(point_to_json(&G2Affine::zero()), json!([["0", "0"], ["0", "0"], ["0", "0"]]));
+    assert!(g1_from_json(&point_to_json(&G1Affine::zero()), "g1").unwrap().is_zero());
+    assert!(g2_from_json(&point_to_json(&G2Affine::zero()), "g2").unwrap().is_zero());
+
+    // snarkjs' own [0, 1, 0] still reads as infinity
+    assert!(g1_from_json(&json!(["0", "1", "0"]), "g1").unwrap().is_zero());
+}
+
+// A public input that doesn't affect the proof has the point at infinity as
+// its IC element. Folding the fixture's output into IC[0] makes such a key:
+// vk_x = IC[0] + 35 * IC[1] is the same whatever the output.
+#[test]
+fn infinity_ic_element_round_trips_and_verifies() {
+    let mut rng = StdRng::seed_from_u64(332);
+    let r1cs = R1CS::<Fr>::read(fixture("mult_bls12_381.r1cs")).unwrap();
+    let witness = Witness::<Fr>::read(fixture("mult_bls12_381.wtns")).unwrap();
+    let pk = setup::<Bls12_381, _>(CircuitFromR1CS::new_with_dummy(&r1cs).unwrap(), &mut rng).unwrap();
+    let proof = prove(&pk, CircuitFromR1CS::new(&r1cs, witness).unwrap(), &mut rng).unwrap();
+
+    let mut vk = pk.vk.clone();
+    let ic = &mut vk.gamma_abc_g1;
+    ic[0] = (ic[0] + ic[1] * Fr::from(35u64)).into_affine();
+    ic[1] = G1Affine::zero();
+    assert!(verify(&vk, &[Fr::from(35u64)], &proof).unwrap());
+    assert!(verify(&vk, &[Fr::from(36u64)], &proof).unwrap());
+
+    let exported = vk_to_json(&vk);
+    assert_eq!(exported["nPublic"], json!(r1cs.num_public_values()));
+    assert_eq!(exported["IC"].as_array().unwrap().len(), r1cs.num_public_values() as usize + 1);
+    assert_eq!(exported["IC"][1], json!(["0", "0", "0"]));
+
+    let imported = vk_from_json(&exported).unwrap();
+    assert_eq!(imported, vk);
+    assert!(verify(&imported, &[Fr::from(35u64)], &proof).unwrap());
+}
+
+#[test]
+fn n_public_must_match_ic() {
+    let mut rng = StdRng::seed_from_u64(332);
+    let r1cs = R1CS::<Fr>::read(fixture("mult_bls12_381.r1cs")).unwrap();
+    let pk = setup::<Bls12_381, _>(CircuitFromR1CS::new_with_dummy(&r1cs).unwrap(), &mut rng).unwrap();
+
+    let mut exported = vk_to_json(&pk.vk);
+    exported["nPublic"] = json!(2);
+    match vk_from_json(&exported) {
+        Err(ImportError::PublicCountMismatch { n_public, ic }) => assert_eq!((n_public, ic), (2, 2)),
+        other => panic!("expected PublicCountMismatch, got {:?}", other.map(|_| ())),
+    }
+
+    // Keys without nPublic are taken at IC's word
+    exported.as_object_mut().unwrap().remove("nPublic");
+    assert_eq!(vk_from_json(&exported).unwrap(), pk.vk);
+}
+
+fn read_json(path: &Path) -> serde_json::Value {
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+// Reverse the x and y Fq2 coordinates, as in snarkjs' Solidity calldata
+fn swap_fq2(point: &serde_json::Value) -> serde_json::Value {
+    json!([[point[0][1], point[0][0]], [point[1][1], point[1][0]], point[2]])
+}
+
+#[test]
+fn g2_ordering_is_locked_by_snarkjs_gamma() {
+    let gamma = json!(SNARKJS_GAMMA_2);
+    assert_eq!(g2_from_json(&gamma, "vk_gamma_2").unwrap(), G2Affine::generator());
+    assert_eq!(point_to_json(&G2Affine::generator()), gamma);
+
+    // The committed key was set up like snarkjs', so it carries the same point
+    let vk = read_json(&fixture("snarkjs_bls12_381/verification_key.json"));
+    assert_eq!(vk["vk_gamma_2"], gamma);
+    let imported = vk_from_json(&vk).unwrap();
+    assert_eq!(imported.gamma_g2, G2Affine::generator());
+    for name in ["vk_beta_2", "vk_gamma_2", "vk_delta_2"] {
+        assert_eq!(point_to_json(&g2_from_json(&vk[name], name).unwrap()), vk[name]);
+    }
+}
+
+#[test]
+fn swapped_fq2_coordinates_are_rejected() {
+    let vk = read_json(&fixture("snarkjs_bls12_381/verification_key.json"));
+    for name in ["vk_beta_2", "vk_gamma_2", "vk_delta_2"] {
+        let mut swapped = vk.clone();
+        swapped[name] = swap_fq2(&vk[name]);
+        match vk_from_json(&swapped) {
+            Err(ImportError::SwappedFq2Coordinates(field)) => assert_eq!(field, name),
+            other => panic!("{}: expected SwappedFq2Coordinates, got {:?}", name, other.map(|_| ())),
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use std::fs;
+use std::io::Cursor;
+use ark_bls12_381::Fq;
+use ark_ff::{BigInteger, PrimeField};
+use dogecoin_zkp_generator_qa1::r1cs::{R1CSHeader, ReadOptions};
+use dogecoin_zkp_generator_qa1::{R1CSError, R1CS};
+
+mod common;
+use common::{fixture, scratch_dir};
+
+// `mult_fq48.r1cs` is `out = x * y` over bls12-381's 381-bit base field,
+// so its elements are 48 bytes. Wires [1, out, x, y], constraints
+// x * y = out and (-x) * y = -out.
+
+fn assert_mult(r1cs: &R1CS<Fq>) {
+    assert_eq!(r1cs.header().field_size, 48);
+    assert_eq!(r1cs.prime_field_modulus(), Fq::MODULUS.to_bytes_le());
+    assert_eq!(r1cs.num_constraints(), 2);
+    assert_eq!(r1cs.constraints()[1].a_terms[0].coefficient, -Fq::from(1u64));
+    assert_eq!(r1cs.constraints()[1].c_terms[0].coefficient, -Fq::from(1u64));
+
+    let witness = [1u64, 35, 5, 7].map(Fq::from);
+    assert_eq!(r1cs.check_witness(&witness), Ok(()));
+    assert_eq!(r1cs.check_witness(&[1u64, 36, 5, 7].map(Fq::from)), Err(0));
+}
+
+#[test]
+fn parses_with_every_reader() {
+    let path = fixture("mult_fq48.r1cs");
+    assert_eq!(R1CSHeader::read(&path).unwrap().field_size, 48);
+    assert_mult(&R1CS::<Fq>::read(&path).unwrap());
+
+    let parallel = ReadOptions { parallel: true, ..ReadOptions::default() };
+    assert_mult(&R1CS::<Fq>::read_with_options(&path, &parallel).unwrap());
+    let strict = ReadOptions { strict: true, ..ReadOptions::default() };
+    assert_mult(&R1CS::<Fq>::read_with_options(&path, &strict).unwrap());
+
+    let streamed: Vec<_> = R1CS::<Fq>::constraints_iter(&path).unwrap().collect::<Result<_, _>>().unwrap();
+    assert!(streamed == *R1CS::<Fq>::read(&path).unwrap().constraints());
+}
+
+#[test]
+fn round_trips_byte_for_byte() {
+    let original = R1CS::<Fq>::read(fixture("mult_fq48.r1cs")).unwrap();
+    let mut bytes = Vec::new();
+    original.write_to(&mut bytes).unwrap();
+    assert_eq!(bytes, fs::read(fixture("mult_fq48.r1cs")).unwrap());
+
+    let path = scratch_dir("wide-field").join("mult_fq48.r1cs");
+    original.write(&path).unwrap();
+    assert!(R1CS::<Fq>::read(&path).unwrap() == original);
+    assert!(R1CS::<Fq>::from_reader(Cursor::new(bytes)).unwrap() == original);
+}
+
+// A 48-byte element doesn't fit a 32-byte scalar field, and the prime says
+// which field the file is for
+#[test]
+fn is_not_mistaken_for_a_scalar_field() {
+    let r1cs = R1CS::<ark_bls12_381::Fr>::read(fixture("mult_fq48.r1cs")).unwrap();
+    assert_eq!(r1cs.detect_curve(), None);
+    assert!(matches!(
+        r1cs.assert_modulus_matches::<ark_bls12_381::Fr>(),
+        Err(R1CSError::ModulusMismatch { .. })
+    ));
+}
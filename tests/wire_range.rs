@@ -0,0 +1,48 @@
+use std::fs;
+use std::io::Cursor;
+use ark_bn254::Fr;
+use dogecoin_zkp_generator_qa1::r1cs::{Matrix, ReadOptions, Term};
+use dogecoin_zkp_generator_qa1::{R1CSError, R1CS};
+
+mod common;
+use common::{fixture, scratch_dir};
+
+// `mult.r1cs` is `out = x * y` over bn254, one constraint on wires 0 to 3
+
+// The fixture with a term on wire 9 added to B
+fn out_of_range_bytes() -> Vec<u8> {
+    let mut r1cs = R1CS::<Fr>::read(fixture("mult.r1cs")).unwrap();
+    r1cs.constraints_mut()[0].b_terms.push(Term { wire_id: 9, coefficient: Fr::from(1u64) });
+    let mut bytes = Vec::new();
+    r1cs.write_to(&mut bytes).unwrap();
+    bytes
+}
+
+fn assert_out_of_range<T>(result: Result<T, R1CSError>) {
+    match result {
+        Err(R1CSError::WireIndexOutOfRange { constraint, matrix, wire, num_wires }) => {
+            assert_eq!((constraint, matrix, wire, num_wires), (0, Matrix::B, 9, 4));
+        }
+        Err(e) => panic!("expected WireIndexOutOfRange, got {}", e),
+        Ok(_) => panic!("expected WireIndexOutOfRange"),
+    }
+}
+
+#[test]
+fn out_of_range_wire_is_rejected_when_parsing() {
+    for parallel in [false, true] {
+        let options = ReadOptions { parallel, ..ReadOptions::default() };
+        assert_out_of_range(R1CS::<Fr>::from_reader_with_options(Cursor::new(out_of_range_bytes()), &options));
+    }
+
+    let path = scratch_dir("wire-range").join("out_of_range.r1cs");
+    fs::write(&path, out_of_range_bytes()).unwrap();
+    let mut constraints = R1CS::<Fr>::constraints_iter(&path).unwrap();
+    assert_out_of_range(constraints.next().unwrap());
+}
+
+#[test]
+fn error_names_the_constraint_and_matrix() {
+    let e = R1CS::<Fr>::from_reader(Cursor::new(out_of_range_bytes())).err().unwrap();
+    assert_eq!(e.to_string(), "Constraint #0 refers to wire 9 in B, but the circuit has 4 wires");
+}
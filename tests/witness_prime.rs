@@ -0,0 +1,48 @@
+use std::process::Command;
+use ark_bn254::Fr;
+use dogecoin_zkp_generator_qa1::{CircuitError, CircuitFromR1CS, R1CSError, Witness, R1CS};
+
+mod common;
+use common::fixture;
+
+// The same `out = x * y` circuit and witness over bn254 (`mult.*`) and
+// bls12-381 (`mult_bls12_381.*`)
+
+#[test]
+fn matching_primes_are_accepted() {
+    let r1cs = R1CS::<Fr>::read(fixture("mult.r1cs")).unwrap();
+    let witness = Witness::<Fr>::read(fixture("mult.wtns")).unwrap();
+    assert!(witness.assert_prime_matches(&r1cs).is_ok());
+    assert!(CircuitFromR1CS::new(&r1cs, witness).is_ok());
+}
+
+// A bls12-381 witness decodes into bn254 field elements without error,
+// so only the prime check catches it
+#[test]
+fn witness_for_another_curve_is_rejected() {
+    let r1cs = R1CS::<Fr>::read(fixture("mult.r1cs")).unwrap();
+    let witness = Witness::<Fr>::read(fixture("mult_bls12_381.wtns")).unwrap();
+    assert_eq!(witness.len(), r1cs.num_wires() as usize);
+
+    match CircuitFromR1CS::new(&r1cs, witness) {
+        Err(CircuitError::R1CS(R1CSError::WitnessCurveMismatch { r1cs: r1cs_prime, witness })) => {
+            assert_ne!(r1cs_prime, witness);
+            assert_eq!(r1cs_prime, r1cs.prime_field_modulus());
+        }
+        Err(e) => panic!("expected WitnessCurveMismatch, got {}", e),
+        Ok(_) => panic!("expected WitnessCurveMismatch, got a circuit"),
+    };
+}
+
+#[test]
+fn workflow_reports_the_mismatch() {
+    let output = Command::new(env!("CARGO_BIN_EXE_dogecoin_zkp_generator_qa1"))
+        .args(["--r1cs", fixture("mult.r1cs").to_str().unwrap()])
+        .args(["--wtns", fixture("mult_bls12_381.wtns").to_str().unwrap()])
+        .args(["--setup-seed", "1"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("the witness is for another curve"), "{}", stdout);
+}
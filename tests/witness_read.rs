@@ -0,0 +1,80 @@
+use std::fs;
+use ark_bn254::Fr;
+use dogecoin_zkp_generator_qa1::{R1CSError, Witness};
+
+mod common;
+use common::{fixture, scratch_dir};
+
+// `mult.wtns` is [1, 35, 5, 7] over bn254: the header section's size is at
+// byte 16, its field size at 24 and its value count at 60; the values
+// section's size is at 68 and its 128 bytes of values start at 76
+
+// Read the fixture with `patch` applied to its bytes
+fn read_patched(test: &str, patch: impl FnOnce(&mut Vec<u8>)) -> Result<Witness<Fr>, R1CSError> {
+    let mut bytes = fs::read(fixture("mult.wtns")).unwrap();
+    patch(&mut bytes);
+    let path = scratch_dir(test).join("witness.wtns");
+    fs::write(&path, bytes).unwrap();
+    Witness::<Fr>::read(&path)
+}
+
+#[test]
+fn fixture_reads() {
+    let witness = read_patched("witness-read-ok", |_| {}).unwrap();
+    assert_eq!(witness.values, [1u64, 35, 5, 7].map(Fr::from));
+}
+
+// Would otherwise allocate 4 GiB for the prime
+#[test]
+fn huge_field_size_is_rejected() {
+    let result = read_patched("witness-read-field-size", |bytes| {
+        bytes[24..28].copy_from_slice(&u32::MAX.to_le_bytes());
+    });
+    assert!(matches!(result, Err(R1CSError::InvalidFieldSize(u32::MAX))));
+    
+    let result = read_patched("witness-read-field-size-odd", |bytes| {
+        bytes[24..28].copy_from_slice(&33u32.to_le_bytes());
+    });
+    assert!(matches!(result, Err(R1CSError::InvalidFieldSize(33))));
+}
+
+// Would otherwise pre-allocate u32::MAX values
+#[test]
+fn huge_value_count_is_rejected() {
+    let result = read_patched("witness-read-count", |bytes| {
+        bytes[60..64].copy_from_slice(&u32::MAX.to_le_bytes());
+    });
+    assert!(matches!(result, Err(R1CSError::SectionSizeMismatch { section_type: 2, declared: 128, .. })));
+}
+
+#[test]
+fn section_sizes_must_match_their_contents() {
+    // A header section padded with 4 bytes the parser doesn't consume
+    let result = read_patched("witness-read-header-size", |bytes| {
+        bytes[16..24].copy_from_slice(&44u64.to_le_bytes());
+        bytes.splice(64..64, [0u8; 4]);
+    });
+    assert!(matches!(
+        result,
+        Err(R1CSError::SectionSizeMismatch { section_type: 1, declared: 44, consumed: 40 })
+    ));
+    
+    // A values section declaring one value fewer than the header
+    let result = read_patched("witness-read-values-size", |bytes| {
+        bytes[68..76].copy_from_slice(&96u64.to_le_bytes());
+        bytes.truncate(76 + 96);
+    });
+    assert!(matches!(
+        result,
+        Err(R1CSError::SectionSizeMismatch { section_type: 2, declared: 96, consumed: 128 })
+    ));
+}
+
+#[test]
+fn truncated_file_is_rejected() {
+    let result = read_patched("witness-read-truncated", |bytes| bytes.truncate(150));
+    assert!(matches!(
+        result,
+        Err(R1CSError::TruncatedSection { section_type: 2, expected: 128, got: 74 })
+    ));
+}
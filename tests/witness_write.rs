@@ -0,0 +1,47 @@
+use std::fs;
+use ark_ff::PrimeField;
+use dogecoin_zkp_generator_qa1::r1cs::{create_hardcoded_r1cs, hardcoded_witness};
+use dogecoin_zkp_generator_qa1::{CircuitFromR1CS, Witness};
+
+mod common;
+use common::{fixture, scratch_dir};
+
+// `mult.wtns` and `mult_bls12_381.wtns` are snarkjs witnesses for
+// `out = x * y` over bn254 and bls12-381
+
+fn assert_round_trips<F: PrimeField>(name: &str) {
+    let original = Witness::<F>::read(fixture(name)).unwrap();
+    let path = scratch_dir(&format!("witness-write-{}", name)).join(name);
+    original.write(&path).unwrap();
+
+    // snarkjs' own layout, so the bytes come out the same
+    assert_eq!(fs::read(&path).unwrap(), fs::read(fixture(name)).unwrap(), "{}", name);
+
+    let reread = Witness::<F>::read(&path).unwrap();
+    assert_eq!(reread.field_size, original.field_size);
+    assert_eq!(reread.prime_bytes, original.prime_bytes);
+    assert_eq!(reread.values, original.values);
+}
+
+#[test]
+fn snarkjs_witnesses_round_trip() {
+    assert_round_trips::<ark_bn254::Fr>("mult.wtns");
+    assert_round_trips::<ark_bls12_381::Fr>("mult_bls12_381.wtns");
+}
+
+// A witness computed in code can be handed to other tools
+#[test]
+fn computed_witness_is_readable() {
+    let r1cs = create_hardcoded_r1cs::<ark_bn254::Fr>().unwrap();
+    let witness = Witness {
+        field_size: r1cs.header().field_size,
+        prime_bytes: r1cs.prime_field_modulus().to_vec(),
+        values: hardcoded_witness(6, 9),
+    };
+    let path = scratch_dir("witness-write-computed").join("computed.wtns");
+    witness.write(&path).unwrap();
+
+    let reread = Witness::<ark_bn254::Fr>::read(&path).unwrap();
+    assert_eq!(reread.values, witness.values);
+    assert_eq!(CircuitFromR1CS::new(&r1cs, reread).unwrap().get_public_inputs(), [ark_bn254::Fr::from(54u64)]);
+}
@@ -0,0 +1,26 @@
+use ark_ff::Zero;
+use zeroize::Zeroize;
+use dogecoin_zkp_generator_qa1::Witness;
+
+mod common;
+use common::fixture;
+
+// `mult.wtns` is the bn254 witness [1, 35, 5, 7] for `out = x * y`
+
+#[test]
+fn zeroize_clears_every_value() {
+    let mut witness = Witness::<ark_bn254::Fr>::read(fixture("mult.wtns")).unwrap();
+    assert!(witness.values.iter().any(|value| !value.is_zero()));
+
+    witness.zeroize();
+    assert_eq!(witness.values.len(), 4);
+    assert!(witness.values.iter().all(Zero::is_zero));
+}
+
+// The values are read into one allocation of exactly the right size, so no
+// reallocation while reading left an unzeroized copy behind
+#[test]
+fn read_allocates_the_values_once() {
+    let witness = Witness::<ark_bn254::Fr>::read(fixture("mult.wtns")).unwrap();
+    assert_eq!(witness.values.capacity(), witness.values.len());
+}
@@ -0,0 +1,121 @@
+use std::fs;
+use ark_bn254::{Bn254, Fr};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use dogecoin_zkp_generator_qa1::r1cs::{KnownCurve, ReadOptions};
+use dogecoin_zkp_generator_qa1::snarkjs_export::Endianness;
+use dogecoin_zkp_generator_qa1::workflow::{self, Event, Hooks, Phase, PublicInput, WorkflowError};
+use dogecoin_zkp_generator_qa1::R1CS;
+
+mod common;
+use common::{fixture, scratch_dir};
+
+// `mult.r1cs` is `out = x * y` over bn254 and `mult.wtns` its witness
+// [1, 35, 5, 7]; `mult_bls12_381.r1cs` is the same circuit over bls12-381
+
+fn mult() -> R1CS<Fr> {
+    workflow::load_r1cs(fixture("mult.r1cs"), &ReadOptions::default()).unwrap()
+}
+
+// Records the phases and events a workflow reports
+#[derive(Default)]
+struct Recorder {
+    phases: Vec<Phase>,
+    events: Vec<String>,
+}
+
+impl Hooks<Fr> for Recorder {
+    fn phase<T, E>(&mut self, phase: Phase, run: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+        self.phases.push(phase);
+        run()
+    }
+
+    fn event(&mut self, event: Event<'_, Fr>) {
+        self.events.push(format!("{:?}", event));
+    }
+}
+
+#[test]
+fn curve_is_detected_or_checked() {
+    let options = ReadOptions::default();
+    assert_eq!(workflow::select_curve(fixture("mult.r1cs"), None, &options).unwrap(), KnownCurve::Bn254);
+    assert_eq!(workflow::select_curve(fixture("mult_bls12_381.r1cs"), None, &options).unwrap(), KnownCurve::Bls12_381);
+    assert!(matches!(
+        workflow::select_curve(fixture("mult.r1cs"), Some(KnownCurve::Bls12_381), &options),
+        Err(WorkflowError::CurveMismatch(KnownCurve::Bls12_381))
+    ));
+}
+
+#[test]
+fn run_proves_a_witness_that_verifies() {
+    let r1cs = mult();
+    let witness = workflow::load_witness(fixture("mult.wtns"), &r1cs).unwrap();
+    let mut hooks = Recorder::default();
+    let output = workflow::run::<Bn254, _, _>(&r1cs, Some(witness), 10, Some(&Fr::from(35u64)), &mut StdRng::seed_from_u64(1), &mut hooks).unwrap();
+
+    assert_eq!(output.public_inputs, vec![Fr::from(35u64)]);
+    assert!(output.verify(&mut hooks).unwrap());
+    assert_eq!(hooks.phases, [Phase::Setup, Phase::Prove, Phase::Verify]);
+    assert_eq!(hooks.events.len(), 2);
+    assert!(hooks.events[0].starts_with("OutputMatches"));
+    assert_eq!(hooks.events[1], "WitnessSatisfied { num_constraints: 1 }");
+}
+
+// A missing witness, a wrong output and an oversized circuit all stop the
+// run before setup
+#[test]
+fn run_checks_before_setup() {
+    let r1cs = mult();
+    let witness = || Some(workflow::load_witness(fixture("mult.wtns"), &r1cs).unwrap());
+    let mut hooks = Recorder::default();
+
+    let no_witness = workflow::run::<Bn254, _, _>(&r1cs, None, 10, None, &mut StdRng::seed_from_u64(1), &mut hooks);
+    assert!(matches!(no_witness, Err(WorkflowError::NoWitness)));
+    let mismatch = workflow::run::<Bn254, _, _>(&r1cs, witness(), 10, Some(&Fr::from(36u64)), &mut StdRng::seed_from_u64(1), &mut hooks);
+    assert!(matches!(mismatch, Err(WorkflowError::OutputMismatch { ref actual, ref expected }) if actual == "35" && expected == "36"));
+    let too_big = workflow::run::<Bn254, _, _>(&r1cs, witness(), 0, None, &mut StdRng::seed_from_u64(1), &mut hooks);
+    assert!(matches!(too_big, Err(WorkflowError::TooManyConstraints { num_constraints: 1, max_constraints: 0 })));
+    assert!(hooks.phases.is_empty());
+}
+
+#[test]
+fn prove_reports_the_unsatisfied_constraint() {
+    let r1cs = mult();
+    let pk = workflow::setup::<Bn254, _, _>(&r1cs, 10, &mut StdRng::seed_from_u64(1), &mut ()).unwrap();
+    let mut witness = workflow::load_witness(fixture("mult.wtns"), &r1cs).unwrap();
+    witness.values[1] = Fr::from(36u64);
+    assert!(matches!(workflow::prove(&r1cs, witness, &pk, &mut ()), Err(WorkflowError::Unsatisfied(0))));
+}
+
+#[test]
+fn selfcheck_agrees_on_good_and_bad_witnesses() {
+    let r1cs = mult();
+    let witness = workflow::load_witness(fixture("mult.wtns"), &r1cs).unwrap();
+    let mut hooks = Recorder::default();
+    workflow::selfcheck::<Bn254, _, _>(&r1cs, witness.clone(), 10, &mut StdRng::seed_from_u64(1), &mut hooks).unwrap();
+    assert_eq!(hooks.events[0], "Evaluated { native: Ok(()), arkworks: true }");
+
+    let mut bad = witness;
+    bad.values[3] = Fr::from(8u64);
+    let mut hooks = Recorder::default();
+    let result = workflow::selfcheck::<Bn254, _, _>(&r1cs, bad, 10, &mut StdRng::seed_from_u64(1), &mut hooks);
+    assert!(matches!(result, Err(WorkflowError::Unsatisfied(0))));
+    assert_eq!(hooks.events, ["Evaluated { native: Err(0), arkworks: false }"]);
+}
+
+#[test]
+fn public_inputs_come_from_a_file_and_wire_values() {
+    let dir = scratch_dir("workflow-public");
+    let public = dir.join("public.json");
+    fs::write(&public, r#"["35", "5"]"#).unwrap();
+    let file = PublicInput::File(public);
+    let value = |wire: usize, value: &str| PublicInput::Value { wire, value: value.to_string() };
+
+    let resolve = |inputs: &[PublicInput]| workflow::resolve_public_inputs::<Fr>(inputs, 2, Endianness::Big);
+    assert_eq!(resolve(std::slice::from_ref(&file)).unwrap(), [Fr::from(35u64), Fr::from(5u64)]);
+    assert_eq!(resolve(&[file.clone(), value(2, "0x07")]).unwrap(), [Fr::from(35u64), Fr::from(7u64)]);
+    assert_eq!(resolve(&[value(1, "1"), value(2, "2")]).unwrap(), [Fr::from(1u64), Fr::from(2u64)]);
+
+    for inputs in [vec![value(1, "1")], vec![value(3, "1")], vec![file.clone(), file], vec![value(1, "x"), value(2, "2")]] {
+        assert!(matches!(resolve(&inputs), Err(WorkflowError::InvalidPublicInput(_))), "{:?}", inputs);
+    }
+}
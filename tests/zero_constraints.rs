@@ -0,0 +1,67 @@
+use std::process::Output;
+use ark_bn254::Fr;
+use dogecoin_zkp_generator_qa1::{CircuitError, CircuitFromR1CS, R1CSError, R1CS};
+
+mod common;
+use common::{fixture, qa1};
+
+// `empty.r1cs` is a bn254 circuit with the wires [1, out, x] and no
+// constraints at all, so any proof for it would accept any output.
+
+fn assert_no_panic(output: &Output) {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains("panicked"), "{}", stderr);
+}
+
+#[test]
+fn parses_and_is_rejected_for_proving() {
+    let r1cs = R1CS::<Fr>::read(fixture("empty.r1cs")).unwrap();
+    assert_eq!(r1cs.num_constraints(), 0);
+    assert!(r1cs.constraints().is_empty());
+    assert_eq!(r1cs.check_witness(&[Fr::from(1u64), Fr::from(3u64), Fr::from(4u64)]), Ok(()));
+    assert!(matches!(r1cs.assert_groth16_compatible(), Err(R1CSError::NoConstraints)));
+    assert!(matches!(CircuitFromR1CS::new_with_dummy(&r1cs), Err(CircuitError::R1CS(R1CSError::NoConstraints))));
+}
+
+// The file is valid, so commands that only inspect it succeed, warning
+// that it can't be proven
+#[test]
+fn info_warns_about_it() {
+    for command in ["info", "stats"] {
+        let output = qa1(&[command, "--r1cs", fixture("empty.r1cs").to_str().unwrap()]);
+        assert_no_panic(&output);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(output.status.success(), "{}: {}", command, stdout);
+    }
+    
+    let output = qa1(&["info", "--r1cs", fixture("empty.r1cs").to_str().unwrap()]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Constraints: 0"), "{}", stdout);
+    assert!(stdout.contains("Warning: the circuit has no constraints"), "{}", stdout);
+}
+
+// Setup refuses the circuit with an error instead of carrying on
+#[test]
+fn setup_rejects_it_without_panicking() {
+    let dir = std::env::temp_dir().join(format!("qa1-zero-constraints-{}", std::process::id()));
+    let output = qa1(&[
+        "setup",
+        "--r1cs", fixture("empty.r1cs").to_str().unwrap(),
+        "--pk", dir.join("pk.bin").to_str().unwrap(),
+        "--vk", dir.join("vk.bin").to_str().unwrap(),
+        "--setup-seed", "1",
+    ]);
+    assert_no_panic(&output);
+    assert_eq!(output.status.code(), Some(2));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("no constraints"), "{}", stdout);
+}
+
+#[test]
+fn workflow_fails_without_panicking() {
+    let output = qa1(&["-vvv", "--r1cs", fixture("empty.r1cs").to_str().unwrap(), "--setup-seed", "1"]);
+    assert_no_panic(&output);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("Proof verified"), "{}", stdout);
+}